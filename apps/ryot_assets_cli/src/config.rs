@@ -4,6 +4,9 @@ use serde::Deserialize;
 #[derive(Debug, Copy, Clone, Deserialize)]
 pub struct SpriteSheetConfig {
     pub sheet_size: UVec2,
+    /// The size of a 1x1 sprite, from which the size of the other sprite layouts derive.
+    #[serde(default = "default_tile_size")]
+    pub tile_size: UVec2,
     #[serde(default)]
     pub compression_config: Option<CompressionConfig>,
     #[serde(default)]
@@ -14,6 +17,7 @@ impl Default for SpriteSheetConfig {
     fn default() -> Self {
         SpriteSheetConfig {
             sheet_size: UVec2::new(384, 384),
+            tile_size: default_tile_size(),
             compression_config: None,
             encoding_config: None,
         }
@@ -24,6 +28,7 @@ impl SpriteSheetConfig {
     pub fn tibia_sheet() -> SpriteSheetConfig {
         SpriteSheetConfig {
             sheet_size: UVec2::new(384, 384),
+            tile_size: default_tile_size(),
             compression_config: Some(CompressionConfig {
                 compressed_header_size: 32,
                 content_header_size: 122,
@@ -33,6 +38,10 @@ impl SpriteSheetConfig {
     }
 }
 
+pub fn default_tile_size() -> UVec2 {
+    UVec2::new(32, 32)
+}
+
 #[derive(Debug, Copy, Clone, Deserialize)]
 pub struct CompressionConfig {
     pub compressed_header_size: usize,
@@ -1,10 +1,11 @@
 use crate::SpriteSheetConfig;
+use ryot::prelude::VisualElements;
 use serde::Deserialize;
 use std::fs;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-pub static DYNAMIC_ASSETS_PATH: &str = "dyanmic.assets.ron";
+pub static DYNAMIC_ASSETS_PATH: &str = "dynamic.atlases.ron";
 
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct ContentConfigs {
@@ -45,3 +46,20 @@ pub fn get_full_file_buffer(path: &PathBuf) -> crate::Result<Vec<u8>> {
 
     Ok(buffer)
 }
+
+/// Reads the visual elements from an appearances file, either a Tibia `.dat` file or the JSON and
+/// RON appearance definitions.
+pub fn load_visual_elements(path: &Path) -> crate::Result<VisualElements> {
+    let bytes = get_full_file_buffer(&path.to_path_buf())?;
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("dat") => ryot::tibia::from_bytes(&bytes)
+            .map_err(|err| crate::Error::Appearances(err.to_string())),
+        Some(extension) => ryot::assets::appearances::from_bytes(&bytes, extension)
+            .map_err(|err| crate::Error::Appearances(err.to_string())),
+        None => Err(crate::Error::Appearances(format!(
+            "Unknown appearances format: {}",
+            path.display()
+        ))),
+    }
+}
@@ -1,11 +1,12 @@
 use glam::UVec2;
 use image::error::{LimitError, LimitErrorKind};
 use image::{imageops, ImageFormat, Rgba, RgbaImage};
-use log::{debug, warn};
-use lzma_rs::lzma_decompress_with_options;
+use log::debug;
+use lzma_rs::{lzma_compress_with_options, lzma_decompress_with_options};
 use rayon::prelude::IntoParallelRefIterator;
 use rayon::prelude::*;
 use ryot::prelude::{get_decompressed_file_name, SPRITE_SHEET_FOLDER};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -16,6 +17,12 @@ pub use config::*;
 pub mod content;
 pub use content::*;
 
+pub mod manifest;
+pub use manifest::*;
+
+pub mod pack;
+pub use pack::*;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, Error)]
@@ -25,12 +32,18 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("Could not serialize/deserialize file: {0}")]
     Serde(#[from] serde_json::Error),
+    #[error("Could not serialize the RON file: {0}")]
+    Ron(#[from] ron::Error),
     #[error("Could not decompress file: {0}")]
     Lzma(#[from] lzma_rs::error::Error),
     #[error("Invalid image: {0}")]
     Image(#[from] image::ImageError),
     #[error("Could not find sprite.")]
     SpriteNotFound,
+    #[error("Could not read the appearances: {0}")]
+    Appearances(String),
+    #[error("Sprite '{0}' is {1}x{2} pixels, which doesn't fit any sprite layout")]
+    InvalidSpriteSize(String, u32, u32),
 }
 
 pub fn load_sprite_sheet_image(
@@ -60,14 +73,12 @@ pub fn create_image_from_data(
 ) -> Result<RgbaImage> {
     let mut background_img = match &sheet_config.compression_config {
         None => image::load_from_memory_with_format(&data, ImageFormat::Png)?.to_rgba8(),
-        Some(compression_config) => RgbaImage::from_raw(
-            sheet_size.x,
-            sheet_size.y,
-            data[compression_config.content_header_size..].to_vec(),
-        )
-        .ok_or(image::ImageError::Limits(LimitError::from_kind(
-            LimitErrorKind::DimensionError,
-        )))?,
+        Some(compression_config) => data
+            .get(compression_config.content_header_size..)
+            .and_then(|content| RgbaImage::from_raw(sheet_size.x, sheet_size.y, content.to_vec()))
+            .ok_or(image::ImageError::Limits(LimitError::from_kind(
+                LimitErrorKind::DimensionError,
+            )))?,
     };
 
     if let Some(encoding_config) = &sheet_config.encoding_config {
@@ -115,30 +126,254 @@ pub fn decompress_lzma_sprite_sheet(
     Ok(decompressed)
 }
 
+/// The constant byte sequence that precedes the LZMA size in the header of Tibia's sprite sheets.
+const LZMA_HEADER_SIGNATURE: [u8; 5] = [0x70, 0x0A, 0xFA, 0x80, 0x24];
+
+/// The size of the LZMA properties, dictionary size and unpacked size that precede the compressed
+/// data of a sprite sheet.
+const LZMA_PROPERTIES_SIZE: usize = 13;
+
+/// Saves a sprite sheet image to the given path, the inverse of [load_sprite_sheet_image].
+/// Without a compression config the sheet is saved as a PNG, otherwise in Tibia's LZMA/BMP format.
+pub fn save_sprite_sheet_image(
+    image: &RgbaImage,
+    path: &Path,
+    sheet_config: SpriteSheetConfig,
+) -> Result<()> {
+    let data = create_data_from_image(image, &sheet_config)?;
+
+    let output_data = match &sheet_config.compression_config {
+        None => data,
+        Some(compression_config) => compress_lzma_sprite_sheet(data, compression_config)?,
+    };
+
+    fs::write(path, output_data)?;
+
+    Ok(())
+}
+
+/// Creates the sprite sheet data from an image, the inverse of [create_image_from_data].
+/// Without a compression config the data is a PNG. Otherwise it's a BMP with a BITMAPV4 header
+/// padded to the content header size, and its pixels encoded as set in the encoding config.
+pub fn create_data_from_image(
+    image: &RgbaImage,
+    sheet_config: &SpriteSheetConfig,
+) -> Result<Vec<u8>> {
+    let mut image = image.clone();
+
+    if let Some(encoding_config) = &sheet_config.encoding_config {
+        if encoding_config.reversed_r_b_channels {
+            reverse_channels(&mut image);
+        }
+
+        if encoding_config.vertically_flipped {
+            flip_vertically(&mut image);
+        }
+    }
+
+    let Some(compression_config) = &sheet_config.compression_config else {
+        let mut data = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut data, ImageFormat::Png)?;
+        return Ok(data.into_inner());
+    };
+
+    let (width, height) = image.dimensions();
+    let mut data = bmp_header(width, height, compression_config.content_header_size);
+    data.extend_from_slice(image.as_raw());
+
+    Ok(data)
+}
+
+/// Compresses the sprite sheet data into Tibia's format, the inverse of
+/// [decompress_lzma_sprite_sheet]. Like Tibia, the LZMA header holds the compressed size instead of
+/// the decompressed one, and the data ends with an end-of-stream marker. The sprite sheet header
+/// holds the signature followed by the size of the LZMA data as a 7-bit integer, and is padded
+/// with NULL bytes at the start to the compressed header size.
+pub fn compress_lzma_sprite_sheet(
+    buffer: Vec<u8>,
+    compression_config: &CompressionConfig,
+) -> Result<Vec<u8>> {
+    let mut compressed = Vec::new();
+    lzma_compress_with_options(
+        &mut buffer.as_slice(),
+        &mut compressed,
+        &lzma_rs::compress::Options {
+            unpacked_size: lzma_rs::compress::UnpackedSize::WriteToHeader(None),
+        },
+    )?;
+
+    let compressed_size = (compressed.len() - LZMA_PROPERTIES_SIZE) as u64;
+    compressed[5..LZMA_PROPERTIES_SIZE].copy_from_slice(&compressed_size.to_le_bytes());
+
+    let mut header = LZMA_HEADER_SIGNATURE.to_vec();
+    let mut size = compressed.len();
+
+    loop {
+        let byte = (size & 0x7F) as u8;
+        size >>= 7;
+
+        if size == 0 {
+            header.push(byte);
+            break;
+        }
+
+        header.push(byte | 0x80);
+    }
+
+    let padding = compression_config
+        .compressed_header_size
+        .saturating_sub(header.len());
+
+    let mut output = vec![0; padding];
+    output.extend(header);
+    output.extend(compressed);
+
+    Ok(output)
+}
+
+/// A BITMAPV4 header for a bottom-up 32 bits BGRA bitmap, padded with NULL bytes to the given
+/// header size, where the pixel data starts.
+fn bmp_header(width: u32, height: u32, header_size: usize) -> Vec<u8> {
+    let file_size = header_size as u32 + width * height * 4;
+
+    let mut header = Vec::with_capacity(header_size);
+    header.extend_from_slice(b"BM");
+    header.extend_from_slice(&file_size.to_le_bytes());
+    header.extend_from_slice(&[0; 4]);
+    header.extend_from_slice(&(header_size as u32).to_le_bytes());
+
+    // BITMAPV4HEADER
+    header.extend_from_slice(&108u32.to_le_bytes());
+    header.extend_from_slice(&width.to_le_bytes());
+    header.extend_from_slice(&height.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&32u16.to_le_bytes());
+    // BI_BITFIELDS compression, followed by image size, resolution and palette, all unset.
+    header.extend_from_slice(&3u32.to_le_bytes());
+    header.extend_from_slice(&[0; 20]);
+    // Red, green, blue and alpha masks.
+    for mask in [0x00FF0000u32, 0x0000FF00, 0x000000FF, 0xFF000000] {
+        header.extend_from_slice(&mask.to_le_bytes());
+    }
+    header.extend_from_slice(b"Win ");
+    // Color space endpoints and gamma, unused by the Windows color space.
+    header.extend_from_slice(&[0; 48]);
+
+    header.resize(header_size, 0);
+    header
+}
+
+/// The outcome of an extraction, listing the source files by what happened to them.
+#[derive(Debug, Default)]
+pub struct ExtractSummary {
+    pub extracted: Vec<String>,
+    pub unchanged: Vec<String>,
+    /// The sources that no longer exist, whose outputs were removed.
+    pub removed: Vec<String>,
+    pub failed: Vec<(String, Error)>,
+}
+
+impl fmt::Display for ExtractSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} extracted, {} unchanged, {} removed, {} failed",
+            self.extracted.len(),
+            self.unchanged.len(),
+            self.removed.len(),
+            self.failed.len()
+        )?;
+
+        for (file, err) in &self.failed {
+            write!(f, "\n  {}: {}", file, err)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Decompress and save the plain sprite sheets to the given destination path.
 /// This is used to generate a decompressed cache of the sprite sheets, to optimize reading.
 /// Trade off here is that we use way more disk space in pro of faster sprite loading.
+///
+/// The extraction is incremental: a manifest of the source hashes is kept along with the sheets,
+/// so only the sources that changed since the last extraction are decompressed again, and the
+/// sheets of sources that are gone are removed. Sources that fail are reported in the summary
+/// and retried on the next extraction.
 pub fn decompress_sprite_sheets(
     content_configs: ContentConfigs,
     sheet_size: &UVec2,
     files: &Vec<String>,
-) {
+) -> Result<ExtractSummary> {
+    let source_path = &content_configs.directories.source_path;
     let sprite_sheet_path = content_configs
         .directories
         .destination_path
         .join(SPRITE_SHEET_FOLDER);
 
-    fs::create_dir_all(sprite_sheet_path.clone()).expect("Failed to create sprite sheets folder");
+    fs::create_dir_all(&sprite_sheet_path)?;
 
-    files.par_iter().for_each(|file| {
-        decompress_sprite_sheet(
-            file,
-            &content_configs.directories.source_path,
-            &sprite_sheet_path,
-            content_configs.sprite_sheet,
-            sheet_size,
-        );
-    });
+    let manifest_path = sprite_sheet_path.join(EXTRACT_MANIFEST_PATH);
+    let mut previous = ExtractManifest::load(&manifest_path);
+
+    let outcomes = files
+        .par_iter()
+        .map(|file| {
+            let hash = hash_file(&source_path.join(file))?;
+            let output = get_decompressed_file_name(file);
+
+            let unchanged = previous
+                .sources
+                .get(file)
+                .is_some_and(|extracted| extracted.hash == hash)
+                && sprite_sheet_path.join(&output).exists();
+
+            if !unchanged {
+                decompress_sprite_sheet(
+                    file,
+                    source_path,
+                    &sprite_sheet_path,
+                    content_configs.sprite_sheet,
+                    sheet_size,
+                )?;
+            }
+
+            Ok((ExtractedFile { hash, output }, unchanged))
+        })
+        .collect::<Vec<Result<_>>>();
+
+    let mut manifest = ExtractManifest::default();
+    let mut summary = ExtractSummary::default();
+
+    for (file, outcome) in files.iter().zip(outcomes) {
+        previous.sources.remove(file);
+
+        match outcome {
+            Ok((extracted, unchanged)) => {
+                manifest.sources.insert(file.clone(), extracted);
+
+                if unchanged {
+                    summary.unchanged.push(file.clone());
+                } else {
+                    summary.extracted.push(file.clone());
+                }
+            }
+            Err(err) => summary.failed.push((file.clone(), err)),
+        }
+    }
+
+    for (file, orphan) in previous.sources {
+        match fs::remove_file(sprite_sheet_path.join(&orphan.output)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                summary.failed.push((file, err.into()))
+            }
+            _ => summary.removed.push(file),
+        }
+    }
+
+    manifest.save(&manifest_path)?;
+
+    Ok(summary)
 }
 
 pub fn decompress_sprite_sheet(
@@ -147,31 +382,14 @@ pub fn decompress_sprite_sheet(
     destination_path: &Path,
     sheet_config: SpriteSheetConfig,
     sheet_size: &UVec2,
-) {
-    let destination_file = &format!(
-        "{}/{}",
-        destination_path.display(),
-        get_decompressed_file_name(file)
-    );
-
-    if PathBuf::from(destination_file).exists() {
-        return;
-    }
+) -> Result<()> {
+    let destination_file = destination_path.join(get_decompressed_file_name(file));
 
     debug!("Decompressing sprite sheet {} {}", path.display(), file);
-    match load_sprite_sheet_image(
-        &format!("{}/{}", path.display(), file).into(),
-        sheet_config,
-        sheet_size,
-    ) {
-        Ok(sheet) => sheet
-            .save_with_format(destination_file, ImageFormat::Png)
-            .map_err(|e| {
-                warn!("Failed to save sprite sheet {}: {}", destination_file, e);
-            })
-            .expect("Failed to save sprite sheet"),
-        Err(e) => panic!("{:?}", e),
-    }
+    load_sprite_sheet_image(&path.join(file), sheet_config, sheet_size)?
+        .save_with_format(destination_file, ImageFormat::Png)?;
+
+    Ok(())
 }
 
 fn flip_vertically(img: &mut RgbaImage) {
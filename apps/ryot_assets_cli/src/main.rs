@@ -2,7 +2,11 @@ use clap::{Parser, Subcommand};
 use config::Config;
 use glam::UVec2;
 use log::*;
-use ryot_assets_cli::{decompress_sprite_sheets, ContentConfigs};
+use ryot::prelude::{ContentDiff, ContentIndex, ContentQuery};
+use ryot_assets_cli::{
+    decompress_sprite_sheets, load_sprites, load_visual_elements, pack_sprites,
+    save_packed_sprites, ContentConfigs, SpriteSheetConfig,
+};
 use simple_logger::SimpleLogger;
 use std::path::{Path, PathBuf};
 use std::{fs, result};
@@ -25,6 +29,47 @@ struct Cli {
 enum Commands {
     /// Extracts assets into sprite sheets
     Extract,
+    /// Packs individual sprite PNGs into sprite sheets, with their catalog and atlas layouts
+    Pack {
+        /// The folder with the sprite PNGs
+        source: PathBuf,
+        /// The output folder, defaults to the destination folder of the config
+        #[arg(short, long, value_name = "FOLDER")]
+        destination: Option<PathBuf>,
+        /// The id of the first packed sprite
+        #[arg(long, default_value_t = 1)]
+        first_id: u32,
+    },
+    /// Compares two appearances files, printing the added, removed and changed content
+    Diff {
+        /// The old appearances file
+        old: PathBuf,
+        /// The new appearances file
+        new: PathBuf,
+        /// Prints the differences as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Inspects the content
+    Content {
+        #[command(subcommand)]
+        command: ContentCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ContentCommands {
+    /// Searches the appearances, printing the matching content ids ranked by relevance
+    Search {
+        /// The query, like `torch id:100-200 category:decor group:object walkable !sight animated light`
+        query: Vec<String>,
+        /// The appearances file, defaults to the appearances.dat in the destination folder
+        #[arg(short, long, value_name = "FILE")]
+        appearances: Option<PathBuf>,
+        /// The maximum number of results
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+    },
 }
 
 fn main() {
@@ -41,12 +86,101 @@ fn main() {
                 .run()
                 .expect("Failed to build assets");
         }
+        Some(Commands::Pack {
+            source,
+            destination,
+            first_id,
+        }) => {
+            info!("Running pack command");
+            let content_configs = read_content_configs(config_path);
+            let destination = destination
+                .clone()
+                .unwrap_or(content_configs.directories.destination_path);
+
+            pack(
+                source,
+                &destination,
+                content_configs.sprite_sheet,
+                *first_id,
+            )
+            .expect("Failed to pack sprites");
+        }
+        Some(Commands::Content {
+            command:
+                ContentCommands::Search {
+                    query,
+                    appearances,
+                    limit,
+                },
+        }) => {
+            let appearances = appearances.clone().unwrap_or_else(|| {
+                read_content_configs(config_path)
+                    .directories
+                    .destination_path
+                    .join("appearances.dat")
+            });
+
+            search_content(&appearances, &query.join(" "), *limit)
+                .expect("Failed to search content");
+        }
+        Some(Commands::Diff { old, new, json }) => {
+            diff_content(old, new, *json).expect("Failed to diff content");
+        }
         None => {
             println!("No command provided. Use --help to see available commands");
         }
     }
 }
 
+fn search_content(appearances: &Path, query: &str, limit: usize) -> color_eyre::Result<()> {
+    let visual_elements = load_visual_elements(appearances)?;
+    let query: ContentQuery = query.parse()?;
+    let results = ContentIndex::from(&visual_elements).search(&query);
+
+    info!("Found {} results for {:?}", results.len(), query);
+
+    for content_id in results.into_iter().take(limit) {
+        let name = content_id
+            .as_group_and_id()
+            .and_then(|(group, id)| visual_elements.get_for_group_and_id(group, id))
+            .map_or("", |element| element.name.as_str());
+
+        println!("{}\t{}", content_id, name);
+    }
+
+    Ok(())
+}
+
+fn pack(
+    source: &Path,
+    destination: &Path,
+    sheet_config: SpriteSheetConfig,
+    first_sprite_id: u32,
+) -> color_eyre::Result<()> {
+    let packed = pack_sprites(load_sprites(source)?, &sheet_config, first_sprite_id)?;
+    save_packed_sprites(&packed, destination, &sheet_config)?;
+
+    info!(
+        "Packed {} sprites into {} sheets",
+        packed.sprite_ids.len(),
+        packed.sheets.len()
+    );
+
+    Ok(())
+}
+
+fn diff_content(old: &Path, new: &Path, json: bool) -> color_eyre::Result<()> {
+    let diff = ContentDiff::between(&load_visual_elements(old)?, &load_visual_elements(new)?);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        print!("{}", diff);
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct ContentBuild {
     path: PathBuf,
@@ -127,7 +261,7 @@ fn copy_appearances(source_path: &Path, destination_path: &Path) -> Result<(), s
 fn decompress_sprites(
     content_configs: ContentConfigs,
     sheet_size: &UVec2,
-) -> result::Result<(), std::io::Error> {
+) -> ryot_assets_cli::Result<()> {
     let ContentConfigs { directories, .. } = content_configs.clone();
 
     let files = fs::read_dir(directories.source_path)?
@@ -150,7 +284,13 @@ fn decompress_sprites(
         })
         .collect::<Vec<String>>();
 
-    decompress_sprite_sheets(content_configs, sheet_size, &files);
+    let summary = decompress_sprite_sheets(content_configs, sheet_size, &files)?;
+
+    if summary.failed.is_empty() {
+        info!("Sprite sheets: {}", summary);
+    } else {
+        error!("Sprite sheets: {}", summary);
+    }
 
     Ok(())
 }
@@ -0,0 +1,54 @@
+//! The manifest of an extraction, mapping each source file to the hash of its content and the
+//! output file extracted from it. It lets the extraction skip the sources that didn't change since
+//! the last run, and clean up the outputs of the sources that no longer exist.
+use crate::Result;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+pub static EXTRACT_MANIFEST_PATH: &str = "extract-manifest.json";
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractManifest {
+    pub sources: BTreeMap<String, ExtractedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractedFile {
+    /// The SHA-256 of the source file, as a hex string.
+    pub hash: String,
+    pub output: String,
+}
+
+impl ExtractManifest {
+    /// Reads the manifest at the given path. A missing or unreadable manifest is an empty one, so
+    /// every source is extracted again.
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = fs::read(path) else {
+            return Self::default();
+        };
+
+        serde_json::from_slice(&content).unwrap_or_else(|err| {
+            warn!("Ignoring invalid manifest {}: {}", path.display(), err);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+pub fn hash_file(path: &Path) -> Result<String> {
+    let digest = Sha256::digest(fs::read(path)?);
+
+    Ok(digest.iter().fold(String::new(), |mut hash, byte| {
+        let _ = write!(hash, "{:02x}", byte);
+        hash
+    }))
+}
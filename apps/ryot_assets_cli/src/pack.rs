@@ -0,0 +1,212 @@
+//! Packs individual sprite images into sprite sheets, the reverse of the extraction.
+//!
+//! Sprites are binned by their size into the four sprite layouts, and each layout is packed into
+//! as many sheets of the configured size as needed. Sprite ids are assigned in sequence, layout by
+//! layout and in the file name order within a layout, so every sheet covers a contiguous range of
+//! ids, as the catalog expects.
+use crate::{Error, Result, SpriteSheetConfig};
+use glam::UVec2;
+use image::{imageops, ImageFormat, RgbaImage};
+use log::debug;
+use ryot::prelude::{ContentRecord, SpriteLayout, SpriteSheet, SPRITE_SHEET_FOLDER};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub static CATALOG_CONTENT_PATH: &str = "catalog-content.json";
+pub static SPRITE_IDS_PATH: &str = "sprite-ids.json";
+
+const LAYOUTS: [SpriteLayout; 4] = [
+    SpriteLayout::OneByOne,
+    SpriteLayout::OneByTwo,
+    SpriteLayout::TwoByOne,
+    SpriteLayout::TwoByTwo,
+];
+
+/// The sprite sheets packed from a set of sprites, along with the id assigned to each sprite.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PackedSprites {
+    pub sheets: Vec<(SpriteSheet, RgbaImage)>,
+    pub sprite_ids: BTreeMap<String, u32>,
+}
+
+impl PackedSprites {
+    pub fn catalog(&self) -> Vec<ContentRecord> {
+        self.sheets
+            .iter()
+            .map(|(sheet, _)| ContentRecord::SpriteSheet(sheet.clone()))
+            .collect()
+    }
+}
+
+/// Reads the PNG sprites of a folder, keyed by their file name without extension and sorted by it.
+pub fn load_sprites(path: &Path) -> Result<Vec<(String, RgbaImage)>> {
+    let mut sprites = Vec::new();
+
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+
+        if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("png") {
+            continue;
+        }
+
+        let Some(name) = path.file_stem().and_then(|name| name.to_str()) else {
+            continue;
+        };
+
+        let sprite = image::open(&path)?.to_rgba8();
+        sprites.push((name.to_string(), sprite));
+    }
+
+    sprites.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(sprites)
+}
+
+/// Packs the sprites into sheets, assigning them ids starting from `first_sprite_id`.
+/// Every sprite must have the size of one of the sprite layouts for the configured tile size, and
+/// fit in a sheet.
+pub fn pack_sprites(
+    sprites: Vec<(String, RgbaImage)>,
+    sheet_config: &SpriteSheetConfig,
+    first_sprite_id: u32,
+) -> Result<PackedSprites> {
+    let tile_size = sheet_config.tile_size;
+    let mut binned: BTreeMap<usize, Vec<(String, RgbaImage)>> = BTreeMap::new();
+
+    for (name, sprite) in sprites {
+        let size = UVec2::from(sprite.dimensions());
+        let Some(layout) = LAYOUTS.iter().position(|layout| {
+            layout.get_size(&tile_size) == size && size.cmple(sheet_config.sheet_size).all()
+        }) else {
+            return Err(Error::InvalidSpriteSize(name, size.x, size.y));
+        };
+
+        binned.entry(layout).or_default().push((name, sprite));
+    }
+
+    let mut packed = PackedSprites::default();
+    let mut next_sprite_id = first_sprite_id;
+
+    for (layout, sprites) in binned {
+        let layout = LAYOUTS[layout];
+        let sprite_size = layout.get_size(&tile_size);
+        let columns = sheet_config.sheet_size.x / sprite_size.x;
+        let capacity = (columns * (sheet_config.sheet_size.y / sprite_size.y)) as usize;
+
+        for chunk in sprites.chunks(capacity) {
+            let first_sprite_id = next_sprite_id;
+            let last_sprite_id = first_sprite_id + chunk.len() as u32 - 1;
+            next_sprite_id = last_sprite_id + 1;
+
+            debug!(
+                "Packing {} {:?} sprites into sheet {}-{}",
+                chunk.len(),
+                layout,
+                first_sprite_id,
+                last_sprite_id
+            );
+
+            let mut image = RgbaImage::new(sheet_config.sheet_size.x, sheet_config.sheet_size.y);
+
+            for (index, (name, sprite)) in chunk.iter().enumerate() {
+                let (column, row) = (index as u32 % columns, index as u32 / columns);
+                imageops::replace(
+                    &mut image,
+                    sprite,
+                    (column * sprite_size.x) as i64,
+                    (row * sprite_size.y) as i64,
+                );
+                packed
+                    .sprite_ids
+                    .insert(name.clone(), first_sprite_id + index as u32);
+            }
+
+            let sheet = SpriteSheet {
+                file: format!("sprites-{}-{}.png", first_sprite_id, last_sprite_id),
+                layout,
+                first_sprite_id,
+                last_sprite_id,
+                // The side, in pixels, of the square that bounds the sprites of the sheet.
+                area: sprite_size.max_element(),
+            };
+
+            packed.sheets.push((sheet, image));
+        }
+    }
+
+    Ok(packed)
+}
+
+/// Writes the sheets into the sprite sheets folder of the destination, along with the catalog,
+/// the atlas layouts and the ids assigned to each sprite.
+pub fn save_packed_sprites(
+    packed: &PackedSprites,
+    destination_path: &Path,
+    sheet_config: &SpriteSheetConfig,
+) -> Result<()> {
+    let sprite_sheet_path = destination_path.join(SPRITE_SHEET_FOLDER);
+    fs::create_dir_all(&sprite_sheet_path)?;
+
+    for (sheet, image) in &packed.sheets {
+        image.save_with_format(sprite_sheet_path.join(&sheet.file), ImageFormat::Png)?;
+    }
+
+    fs::write(
+        destination_path.join(CATALOG_CONTENT_PATH),
+        serde_json::to_string_pretty(&packed.catalog())?,
+    )?;
+
+    fs::write(
+        destination_path.join(SPRITE_IDS_PATH),
+        serde_json::to_string_pretty(&packed.sprite_ids)?,
+    )?;
+
+    fs::write(
+        destination_path.join(crate::DYNAMIC_ASSETS_PATH),
+        atlas_layouts(sheet_config)?,
+    )?;
+
+    Ok(())
+}
+
+/// A dynamic asset file, mapping each asset key to the assets loaded for it.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DynamicAssets(pub BTreeMap<String, Vec<DynamicAsset>>);
+
+/// An asset of a [DynamicAssets] file, in the format of the dynamic assets of bevy_asset_loader.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DynamicAsset {
+    TextureAtlasLayout {
+        tile_size_x: f32,
+        tile_size_y: f32,
+        columns: u32,
+        rows: u32,
+    },
+}
+
+/// The dynamic asset file with the texture atlas layouts of each sprite layout, in the format
+/// loaded by the visual content plugin.
+pub fn atlas_layouts(sheet_config: &SpriteSheetConfig) -> Result<String> {
+    let layouts = LAYOUTS
+        .iter()
+        .map(|layout| {
+            let size = layout.get_size(&sheet_config.tile_size);
+
+            DynamicAsset::TextureAtlasLayout {
+                tile_size_x: size.x as f32,
+                tile_size_y: size.y as f32,
+                columns: sheet_config.sheet_size.x / size.x,
+                rows: sheet_config.sheet_size.y / size.y,
+            }
+        })
+        .collect();
+
+    let assets = DynamicAssets(BTreeMap::from([("layouts".to_string(), layouts)]));
+
+    Ok(ron::ser::to_string_pretty(
+        &assets,
+        ron::ser::PrettyConfig::default(),
+    )?)
+}
@@ -0,0 +1,87 @@
+use glam::UVec2;
+use image::{Rgba, RgbaImage};
+use ryot::prelude::SPRITE_SHEET_FOLDER;
+use ryot_assets_cli::*;
+use std::fs;
+
+#[test]
+fn test_decompress_sprite_sheets_incrementally() {
+    let root = std::env::temp_dir().join("ryot_assets_cli_extraction");
+    let _ = fs::remove_dir_all(&root);
+
+    let content_configs = ContentConfigs {
+        directories: DirectoryConfigs {
+            source_path: root.join("source"),
+            destination_path: root.join("destination"),
+        },
+        sprite_sheet: SpriteSheetConfig::tibia_sheet(),
+    };
+
+    let source_path = &content_configs.directories.source_path;
+    let sprite_sheet_path = content_configs
+        .directories
+        .destination_path
+        .join(SPRITE_SHEET_FOLDER);
+
+    fs::create_dir_all(source_path).unwrap();
+    for file in ["a.bmp.lzma", "b.bmp.lzma"] {
+        fs::copy("tests/fixtures/1.bmp.lzma", source_path.join(file)).unwrap();
+    }
+    fs::write(source_path.join("c.bmp.lzma"), vec![0; 64]).unwrap();
+
+    let extract = |files: &[&str]| {
+        let files = files.iter().map(|file| file.to_string()).collect();
+        decompress_sprite_sheets(content_configs.clone(), &UVec2::new(384, 384), &files).unwrap()
+    };
+
+    let summary = extract(&["a.bmp.lzma", "b.bmp.lzma", "c.bmp.lzma"]);
+    assert_eq!(summary.extracted, vec!["a.bmp.lzma", "b.bmp.lzma"]);
+    assert!(summary.unchanged.is_empty() && summary.removed.is_empty());
+    assert_eq!(summary.failed.len(), 1);
+    assert_eq!(summary.failed[0].0, "c.bmp.lzma");
+
+    let summary = extract(&["a.bmp.lzma", "b.bmp.lzma", "c.bmp.lzma"]);
+    assert!(summary.extracted.is_empty());
+    assert_eq!(summary.unchanged, vec!["a.bmp.lzma", "b.bmp.lzma"]);
+    assert_eq!(summary.failed.len(), 1);
+
+    let changed = RgbaImage::from_pixel(384, 384, Rgba([1, 2, 3, 255]));
+    save_sprite_sheet_image(
+        &changed,
+        &source_path.join("b.bmp.lzma"),
+        content_configs.sprite_sheet,
+    )
+    .unwrap();
+
+    let summary = extract(&["b.bmp.lzma"]);
+    assert_eq!(summary.extracted, vec!["b.bmp.lzma"]);
+    assert_eq!(summary.removed, vec!["a.bmp.lzma"]);
+    assert!(summary.unchanged.is_empty() && summary.failed.is_empty());
+    assert_eq!(
+        summary.to_string(),
+        "1 extracted, 0 unchanged, 1 removed, 0 failed"
+    );
+
+    assert!(!sprite_sheet_path.join("a.png").exists());
+    assert_eq!(
+        image::open(sprite_sheet_path.join("b.png"))
+            .unwrap()
+            .to_rgba8(),
+        changed
+    );
+
+    let manifest = ExtractManifest::load(&sprite_sheet_path.join(EXTRACT_MANIFEST_PATH));
+    assert_eq!(
+        manifest.sources.keys().collect::<Vec<_>>(),
+        vec!["b.bmp.lzma"]
+    );
+    assert_eq!(
+        manifest.sources["b.bmp.lzma"],
+        ExtractedFile {
+            hash: hash_file(&source_path.join("b.bmp.lzma")).unwrap(),
+            output: "b.png".to_string(),
+        }
+    );
+
+    fs::remove_dir_all(&root).unwrap();
+}
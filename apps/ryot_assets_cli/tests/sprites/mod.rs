@@ -1 +1,4 @@
+mod extraction;
+mod packing;
+mod sheet_encoding;
 mod sheet_loading;
@@ -0,0 +1,102 @@
+use glam::UVec2;
+use image::{Rgba, RgbaImage};
+use rstest::{fixture, rstest};
+use ryot::prelude::{ContentRecord, SpriteLayout, SpriteSheet};
+use ryot_assets_cli::*;
+
+#[fixture]
+fn sheet_config() -> SpriteSheetConfig {
+    SpriteSheetConfig {
+        sheet_size: UVec2::new(64, 64),
+        ..Default::default()
+    }
+}
+
+fn sprite(width: u32, height: u32, color: u8) -> RgbaImage {
+    RgbaImage::from_pixel(width, height, Rgba([color, color, color, 255]))
+}
+
+#[rstest]
+fn test_pack_sprites(sheet_config: SpriteSheetConfig) {
+    let sprites = vec![
+        ("a".to_string(), sprite(32, 32, 1)),
+        ("b".to_string(), sprite(64, 64, 2)),
+        ("c".to_string(), sprite(32, 32, 3)),
+        ("d".to_string(), sprite(32, 32, 4)),
+        ("e".to_string(), sprite(32, 32, 5)),
+        ("f".to_string(), sprite(32, 64, 6)),
+        ("g".to_string(), sprite(32, 32, 7)),
+    ];
+
+    let packed = pack_sprites(sprites, &sheet_config, 100).unwrap();
+
+    assert_eq!(
+        packed.catalog(),
+        vec![
+            ContentRecord::SpriteSheet(SpriteSheet {
+                file: "sprites-100-103.png".to_string(),
+                layout: SpriteLayout::OneByOne,
+                first_sprite_id: 100,
+                last_sprite_id: 103,
+                area: 32,
+            }),
+            ContentRecord::SpriteSheet(SpriteSheet {
+                file: "sprites-104-104.png".to_string(),
+                layout: SpriteLayout::OneByOne,
+                first_sprite_id: 104,
+                last_sprite_id: 104,
+                area: 32,
+            }),
+            ContentRecord::SpriteSheet(SpriteSheet {
+                file: "sprites-105-105.png".to_string(),
+                layout: SpriteLayout::OneByTwo,
+                first_sprite_id: 105,
+                last_sprite_id: 105,
+                area: 64,
+            }),
+            ContentRecord::SpriteSheet(SpriteSheet {
+                file: "sprites-106-106.png".to_string(),
+                layout: SpriteLayout::TwoByTwo,
+                first_sprite_id: 106,
+                last_sprite_id: 106,
+                area: 64,
+            }),
+        ]
+    );
+
+    assert_eq!(packed.sprite_ids["a"], 100);
+    assert_eq!(packed.sprite_ids["g"], 104);
+    assert_eq!(packed.sprite_ids["f"], 105);
+    assert_eq!(packed.sprite_ids["b"], 106);
+
+    let (_, first_sheet) = &packed.sheets[0];
+    assert_eq!(first_sheet.dimensions(), (64, 64));
+    assert_eq!(first_sheet.get_pixel(0, 0), &Rgba([1, 1, 1, 255]));
+    assert_eq!(first_sheet.get_pixel(32, 0), &Rgba([3, 3, 3, 255]));
+    assert_eq!(first_sheet.get_pixel(0, 32), &Rgba([4, 4, 4, 255]));
+    assert_eq!(first_sheet.get_pixel(63, 63), &Rgba([5, 5, 5, 255]));
+
+    let (_, one_by_two_sheet) = &packed.sheets[2];
+    assert_eq!(one_by_two_sheet.get_pixel(0, 63), &Rgba([6, 6, 6, 255]));
+    assert_eq!(one_by_two_sheet.get_pixel(32, 0), &Rgba([0, 0, 0, 0]));
+}
+
+#[rstest]
+#[case(sprite(32, 48, 1))]
+#[case(sprite(128, 128, 1))]
+fn test_pack_sprites_with_invalid_size(sheet_config: SpriteSheetConfig, #[case] sprite: RgbaImage) {
+    assert!(matches!(
+        pack_sprites(vec![("a".to_string(), sprite)], &sheet_config, 1),
+        Err(Error::InvalidSpriteSize(name, ..)) if name == "a"
+    ));
+}
+
+#[test]
+fn test_atlas_layouts() {
+    let parse = |ron: &str| ron::from_str::<DynamicAssets>(ron).unwrap();
+
+    assert_eq!(
+        parse(&atlas_layouts(&SpriteSheetConfig::default()).unwrap()),
+        parse(include_str!("../../../../assets/dynamic.atlases.ron"))
+    );
+}
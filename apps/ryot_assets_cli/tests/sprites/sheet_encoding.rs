@@ -0,0 +1,91 @@
+use glam::UVec2;
+use image::RgbaImage;
+use rstest::{fixture, rstest};
+use ryot_assets_cli::*;
+use std::path::PathBuf;
+
+#[rstest]
+#[case("tests/fixtures/1.bmp.lzma")]
+#[case("tests/fixtures/2.bmp.lzma")]
+fn test_create_data_from_image_matches_fixture(#[case] path: &str) {
+    let sheet_config = SpriteSheetConfig::tibia_sheet();
+    let compression_config = sheet_config.compression_config.unwrap();
+
+    let decompressed = decompress_lzma_sprite_sheet(
+        get_full_file_buffer(&PathBuf::from(path)).unwrap(),
+        &compression_config,
+    )
+    .unwrap();
+
+    let image =
+        create_image_from_data(decompressed.clone(), &sheet_config, &UVec2::new(384, 384)).unwrap();
+
+    assert_eq!(
+        create_data_from_image(&image, &sheet_config).unwrap(),
+        decompressed
+    );
+}
+
+#[rstest]
+fn test_compress_lzma_sprite_sheet_header(#[from(image_fixture)] image: RgbaImage) {
+    let sheet_config = SpriteSheetConfig::tibia_sheet();
+    let compression_config = sheet_config.compression_config.unwrap();
+
+    let data = create_data_from_image(&image, &sheet_config).unwrap();
+    let compressed = compress_lzma_sprite_sheet(data.clone(), &compression_config).unwrap();
+
+    let header = &compressed[..compression_config.compressed_header_size];
+    let signature = header
+        .windows(5)
+        .position(|window| window == [0x70, 0x0A, 0xFA, 0x80, 0x24])
+        .unwrap();
+    assert!(header[..signature].iter().all(|byte| *byte == 0));
+
+    let lzma_size = header[signature + 5..]
+        .iter()
+        .enumerate()
+        .fold(0, |size, (index, byte)| {
+            size | ((*byte as usize & 0x7F) << (7 * index))
+        });
+    assert_eq!(
+        lzma_size,
+        compressed.len() - compression_config.compressed_header_size
+    );
+
+    let size_field_start = compression_config.compressed_header_size + 5;
+    let size_field = u64::from_le_bytes(
+        compressed[size_field_start..size_field_start + 8]
+            .try_into()
+            .unwrap(),
+    );
+    assert_eq!(size_field as usize, compressed.len() - size_field_start - 8);
+
+    assert_eq!(
+        decompress_lzma_sprite_sheet(compressed, &compression_config).unwrap(),
+        data
+    );
+}
+
+#[rstest]
+#[case(SpriteSheetConfig::tibia_sheet(), "round_trip.bmp.lzma")]
+#[case(SpriteSheetConfig::default(), "round_trip.png")]
+fn test_save_sprite_sheet_image_round_trip(
+    #[from(image_fixture)] expected: RgbaImage,
+    #[case] sheet_config: SpriteSheetConfig,
+    #[case] file: &str,
+) {
+    let path = std::env::temp_dir().join(format!("ryot_assets_cli_{}", file));
+
+    save_sprite_sheet_image(&expected, &path, sheet_config).unwrap();
+    let img = load_sprite_sheet_image(&path, sheet_config, &UVec2::new(384, 384)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(img, expected);
+}
+
+#[fixture]
+fn image_fixture() -> RgbaImage {
+    image::open("tests/fixtures/expected.png")
+        .unwrap()
+        .to_rgba8()
+}
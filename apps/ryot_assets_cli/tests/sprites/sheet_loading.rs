@@ -26,6 +26,7 @@ fn test_load_sprite_sheet_image_for_compressed_image(#[from(image_fixture)] expe
 fn test_load_sprite_sheet_image_for_uncompressed_image(#[from(image_fixture)] expected: RgbaImage) {
     let sheet_config = SpriteSheetConfig {
         sheet_size: UVec2::new(384, 384),
+        tile_size: UVec2::new(32, 32),
         compression_config: None,
         encoding_config: None,
     };
@@ -52,7 +53,8 @@ fn test_decompress_sprite_sheet(#[from(image_fixture)] expected: RgbaImage) {
         &PathBuf::from("tests/fixtures/sprite-sheets"),
         sheet_config,
         &UVec2::new(384, 384),
-    );
+    )
+    .unwrap();
 
     let expected_path = PathBuf::from("tests/fixtures/sprite-sheets/2.png");
     assert!(expected_path.exists());
@@ -74,7 +76,8 @@ fn test_decompress_sprite_sheets(#[from(image_fixture)] expected: RgbaImage) {
         content_config,
         &UVec2::new(384, 384),
         &vec!["1.bmp.lzma".to_string(), "2.bmp.lzma".to_string()],
-    );
+    )
+    .unwrap();
 
     for expected_file in ["1.png", "2.png"] {
         let expected_path = PathBuf::from(format!(
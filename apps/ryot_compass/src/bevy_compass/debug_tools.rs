@@ -0,0 +1,131 @@
+use crate::helpers::CONTROL_COMMAND;
+use crate::{gui_is_not_in_use, inputs, Cursor};
+use bevy::prelude::*;
+use leafwing_input_manager::common_conditions::action_just_pressed;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::user_input::InputKind;
+use ryot::plugins::*;
+use ryot::prelude::*;
+
+/// The debug tools plugin allows designers to inspect the ray casting and pathfinding behavior
+/// of the map being edited. It places a test viewer, that continuously casts its sight around,
+/// or a test walker, that looks for a path to the cursor, and draws their results on the map.
+pub struct DebugToolsPlugin;
+
+impl Plugin for DebugToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<DebugToolAction>::default())
+            .init_resource::<ActionState<DebugToolAction>>()
+            .insert_resource(DebugToolAction::get_default_input_map())
+            .add_plugins((
+                NavigablePlugin::<Flags>::default(),
+                PathFindingPlugin,
+                RayCastingDebugPlugin::<TestViewer>::default(),
+                PathFindingDebugPlugin,
+            ))
+            .add_tiled_ray_casting::<TestViewer>()
+            .add_systems(
+                Update,
+                (
+                    place_test_viewer.run_if(action_just_pressed(DebugToolAction::PlaceViewer)),
+                    place_test_walker.run_if(action_just_pressed(DebugToolAction::PlaceWalker)),
+                    clear_debug_tools.run_if(action_just_pressed(DebugToolAction::Clear)),
+                )
+                    .run_if(in_state(RyotContentState::Ready))
+                    .run_if(gui_is_not_in_use()),
+            );
+    }
+}
+
+/// A spectator placed at the cursor, whose sight is cast and drawn every few milliseconds.
+/// It's also the context of the ray casting requests of the viewer.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct TestViewer;
+
+/// A walker placed at the cursor. Placing it again somewhere else looks for a path from the walker
+/// to the cursor, instead of moving it.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct TestWalker;
+
+#[derive(Actionlike, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebugToolAction {
+    PlaceViewer,
+    PlaceWalker,
+    Clear,
+}
+
+impl DebugToolAction {
+    pub fn get_default_input_map() -> InputMap<DebugToolAction> {
+        InputMap::default()
+            .insert_chord(
+                DebugToolAction::PlaceViewer,
+                inputs![CONTROL_COMMAND, Modifier::Alt, KeyCode::KeyV],
+            )
+            .insert_chord(
+                DebugToolAction::PlaceWalker,
+                inputs![CONTROL_COMMAND, Modifier::Alt, KeyCode::KeyW],
+            )
+            .insert_chord(
+                DebugToolAction::Clear,
+                inputs![CONTROL_COMMAND, Modifier::Alt, KeyCode::KeyC],
+            )
+            .build()
+    }
+}
+
+fn place_test_viewer(
+    mut commands: Commands,
+    q_cursor: Query<&TilePosition, With<Cursor>>,
+    q_viewer: Query<Entity, With<TestViewer>>,
+) {
+    let Ok(cursor_pos) = q_cursor.get_single() else {
+        return;
+    };
+
+    let ray_casting = tiled_visible_ray_casting::<TestViewer>(
+        RadialArea::circle()
+            .with_range_and_auto_angle_step(8)
+            .with_center_pos(*cursor_pos),
+    )
+    .with_execution_type(ExecutionType::every_in_ms(250));
+
+    match q_viewer.get_single() {
+        Ok(viewer) => {
+            commands.entity(viewer).insert((*cursor_pos, ray_casting));
+        }
+        Err(_) => {
+            commands.spawn((TestViewer, *cursor_pos, ray_casting));
+        }
+    }
+}
+
+fn place_test_walker(
+    mut commands: Commands,
+    q_cursor: Query<&TilePosition, With<Cursor>>,
+    q_walker: Query<(Entity, &TilePosition), With<TestWalker>>,
+) {
+    let Ok(cursor_pos) = q_cursor.get_single() else {
+        return;
+    };
+
+    match q_walker.get_single() {
+        Ok((walker, walker_pos)) if walker_pos != cursor_pos => {
+            commands
+                .entity(walker)
+                .insert(TiledPathFindingQuery::new(*cursor_pos).with_success_distance(0.));
+        }
+        Ok(_) => (),
+        Err(_) => {
+            commands.spawn((TestWalker, *cursor_pos));
+        }
+    }
+}
+
+fn clear_debug_tools(
+    mut commands: Commands,
+    q_tools: Query<Entity, Or<(With<TestViewer>, With<TestWalker>)>>,
+) {
+    q_tools
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn());
+}
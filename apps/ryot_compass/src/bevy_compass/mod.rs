@@ -10,6 +10,11 @@ pub use camera::*;
 mod cursor;
 pub use cursor::*;
 
+#[cfg(feature = "debug")]
+mod debug_tools;
+#[cfg(feature = "debug")]
+pub use debug_tools::*;
+
 mod gui;
 pub use gui::*;
 
@@ -3,7 +3,7 @@ use crate::{
 };
 use bevy::log::warn;
 use bevy::prelude::*;
-use bevy::utils::HashMap;
+use bevy::utils::{HashMap, HashSet};
 use bevy_egui::EguiPlugin;
 use leafwing_input_manager::common_conditions::action_just_pressed;
 use ryot::prelude::*;
@@ -15,12 +15,18 @@ impl Plugin for PalettePlugin {
         app.add_optional_plugin(EguiPlugin)
             .init_resource::<Palette>()
             .init_resource::<PaletteState>()
+            .init_resource::<ContentIndex>()
             .add_systems(OnEnter(RyotContentState::Ready), setup_categories)
             .add_systems(
                 Update,
                 (
                     clear_selection.run_if(action_just_pressed(CompassAction::ClearSelection)),
-                    (update_palette_category, update_palette_items).chain(),
+                    (
+                        update_content_index.run_if(resource_changed::<VisualElements>),
+                        update_palette_category,
+                        update_palette_items,
+                    )
+                        .chain(),
                 )
                     .run_if(in_state(RyotContentState::Ready)),
             );
@@ -74,23 +80,69 @@ fn setup_categories(visual_elements: Res<VisualElements>, mut palettes: ResMut<P
     });
 }
 
-pub fn update_palette_category(palettes: Res<Palette>, mut palette_state: ResMut<PaletteState>) {
+/// Rebuilds the search index whenever the visual elements change, refreshing the palette.
+pub fn update_content_index(
+    visual_elements: Res<VisualElements>,
+    mut content_index: ResMut<ContentIndex>,
+) {
+    *content_index = visual_elements.as_ref().into();
+}
+
+/// Filters the sprites of the selected category with the palette search. An empty or invalid
+/// search shows the whole category. The last search and category are kept, so the sprites are
+/// only computed again when one of them or the search index changes.
+pub fn update_palette_category(
+    palettes: Res<Palette>,
+    content_index: Res<ContentIndex>,
+    mut palette_state: ResMut<PaletteState>,
+    mut last_search: Local<Option<(String, TilesetCategory)>>,
+) {
     if palettes.is_empty() {
         warn!("Cannot set category: palette is still empty");
         return;
     }
 
-    if !palette_state.category_sprites.is_empty() {
+    let search = (
+        palette_state.search.clone(),
+        palette_state.selected_category,
+    );
+
+    if !content_index.is_changed() && last_search.as_ref() == Some(&search) {
         return;
     }
 
+    *last_search = Some(search);
+
     let category = palette_state.selected_category;
-    palette_state.category_sprites.extend(
-        palettes
-            .get_for_category(&category)
-            .iter()
-            .map(|content_id| ContentId::Object(*content_id)),
-    );
+    let mut category_sprites = palettes
+        .get_for_category(&category)
+        .iter()
+        .map(|content_id| ContentId::Object(*content_id))
+        .collect::<Vec<_>>();
+
+    let query = match palette_state.search.trim() {
+        "" => None,
+        search => match search.parse::<ContentQuery>() {
+            Ok(query) => Some(query.with_group(ContentType::Object)),
+            Err(err) => {
+                debug!("Invalid palette search: {}", err);
+                None
+            }
+        },
+    };
+
+    let Some(query) = query else {
+        category_sprites.sort();
+        palette_state.category_sprites = category_sprites;
+        return;
+    };
+
+    let category_sprites = category_sprites.into_iter().collect::<HashSet<_>>();
+    palette_state.category_sprites = content_index
+        .search(&query)
+        .into_iter()
+        .filter(|content_id| category_sprites.contains(content_id))
+        .collect();
 }
 
 pub fn update_palette_items(
@@ -108,8 +160,7 @@ pub fn update_palette_items(
     let begin = palette_state.begin().min(if len < 5 { 0 } else { len - 5 });
     let end = palette_state.end().min(len);
 
-    let mut object_ids = palette_state.category_sprites.to_vec();
-    object_ids.sort();
+    let object_ids = palette_state.category_sprites.to_vec();
     let object_ids = &object_ids[begin..end];
 
     if palette_state
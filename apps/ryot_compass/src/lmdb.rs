@@ -160,5 +160,18 @@ fn load_tile_content(world: &mut World) {
             (bundle.position, bundle.layer, Visibility::Visible, None),
             CommandState::default().persist(),
         );
+
+        let Some(count) = bundle.count else {
+            continue;
+        };
+
+        let entity = world
+            .resource::<MapTiles<Entity>>()
+            .get(&bundle.position)
+            .and_then(|tile| tile.peek_for_layer(bundle.layer));
+
+        if let Some(entity) = entity {
+            world.entity_mut(entity).insert(count);
+        }
     }
 }
@@ -78,6 +78,9 @@ fn main() {
     #[cfg(all(feature = "lmdb", not(target_arch = "wasm32")))]
     app.add_plugins(LmdbPlugin);
 
+    #[cfg(feature = "debug")]
+    app.add_plugins(DebugToolsPlugin);
+
     #[cfg(feature = "diagnostics")]
     app.add_plugins((
         FrameTimeDiagnosticsPlugin,
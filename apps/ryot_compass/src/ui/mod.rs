@@ -14,6 +14,7 @@ pub struct PaletteState {
     pub selected_tile: Option<ContentId>,
     pub selected_category: TilesetCategory,
     pub category_sprites: Vec<ContentId>,
+    pub search: String,
     pub visible_rows: Range<usize>,
     pub loaded_images: Vec<(ContentId, Handle<Image>, egui::Vec2, egui::Rect)>,
 }
@@ -29,6 +30,7 @@ impl Default for PaletteState {
             selected_tile: None,
             selected_category: TilesetCategory::Raw,
             category_sprites: Vec::default(),
+            search: String::default(),
             visible_rows: Range { start: 0, end: 10 },
             loaded_images: vec![],
         }
@@ -143,10 +145,17 @@ pub fn draw_palette_picker(
                     .clicked()
                 {
                     palette_state.selected_category = *key;
-                    palette_state.category_sprites.clear();
                 }
             }
         });
+    ui.add_space(5.0);
+
+    let width = palette_state.width;
+    ui.add(
+        egui::TextEdit::singleline(&mut palette_state.search)
+            .hint_text("Search: torch id:100-200 walkable !sight animated light")
+            .desired_width(width),
+    );
     ui.add_space(5.0)
 }
 
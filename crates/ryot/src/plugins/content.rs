@@ -1,7 +1,9 @@
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, Update};
 use bevy_asset_loader::prelude::*;
 use bevy_common_assets::json::JsonAssetPlugin;
-use bevy_ecs::prelude::{IntoSystemConfigs, OnEnter};
+#[cfg(feature = "content_packs")]
+use bevy_ecs::prelude::OnExit;
+use bevy_ecs::prelude::{in_state, IntoSystemConfigs, OnEnter};
 use ryot_internal::prelude::*;
 use std::marker::PhantomData;
 
@@ -25,6 +27,7 @@ impl<C: VisualElementsAsset + Default> Plugin for BaseContentPlugin<C> {
         app.init_state::<RyotContentState>()
             .init_resource::<C>()
             .init_resource::<VisualElements>()
+            .add_event::<VisualElementsChanged>()
             .register_type::<TilePosition>()
             .add_loading_state(
                 LoadingState::new(RyotContentState::LoadingContent)
@@ -34,10 +37,19 @@ impl<C: VisualElementsAsset + Default> Plugin for BaseContentPlugin<C> {
             .add_systems(
                 OnEnter(RyotContentState::PreparingContent),
                 prepare_visual_elements::<C>,
+            )
+            .add_systems(
+                Update,
+                reload_visual_elements::<C>
+                    .before(SpriteSystems::Load)
+                    .run_if(in_state(RyotContentState::Ready)),
             );
 
         #[cfg(feature = "tibia")]
         app.add_plugins(tibia::TibiaAssetsPlugin);
+
+        #[cfg(feature = "appearances")]
+        app.add_plugins(AppearancesAssetsPlugin);
     }
 }
 
@@ -79,6 +91,27 @@ where
                 )
                     .chain()
                     .after(prepare_visual_elements::<C>),
+            )
+            .add_systems(
+                Update,
+                reload_sprite_sheets::<C>
+                    .before(SpriteSystems::Load)
+                    .run_if(in_state(RyotContentState::Ready)),
             );
     }
 }
+
+#[cfg(feature = "content_packs")]
+content_plugin!(ContentPacksPlugin, ContentPacksAsset);
+
+/// Layers the content packs of the content assets on top of the base content, once it's prepared
+/// and before the content is ready. Add it next to the Meta or Visual content plugin.
+#[cfg(feature = "content_packs")]
+impl<C: ContentPacksAsset + Default> Plugin for ContentPacksPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ContentPacksAssetsPlugin).add_systems(
+            OnExit(RyotContentState::PreparingContent),
+            prepare_content_packs::<C>,
+        );
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+use bevy_app::{App, Plugin, PostUpdate, Update};
+#[cfg(feature = "ray_casting")]
+use bevy_ecs::prelude::*;
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+use ryot_internal::prelude::*;
+#[cfg(feature = "ray_casting")]
+use std::marker::PhantomData;
+
+/// `RayCastingDebugPlugin` draws the results of the ray casting requests of a given context
+/// `Marker`: the area of interest and the collisions are highlighted as HUD overlays, while the
+/// rays are drawn as gizmo lines from the spectator to the edge of the area or to each collision.
+#[cfg(feature = "ray_casting")]
+pub struct RayCastingDebugPlugin<Marker>(PhantomData<Marker>);
+
+#[cfg(feature = "ray_casting")]
+impl<Marker> Default for RayCastingDebugPlugin<Marker> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "ray_casting")]
+impl<Marker: Copy + ThreadSafe> Plugin for RayCastingDebugPlugin<Marker> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlayColors>()
+            .add_systems(
+                Update,
+                (
+                    draw_ray_propagation_overlay::<Marker>,
+                    draw_ray_casting_gizmos::<Marker>,
+                )
+                    .after(RayCastingSystems::Process),
+            )
+            .add_systems(
+                PostUpdate,
+                remove_stale_debug_overlays::<TiledRayPropagation<Marker>>
+                    .after(RayCastingSystems::CleanUp),
+            );
+    }
+}
+
+/// `PathFindingDebugPlugin` draws the computed paths of the pathfinding requests, highlighting
+/// their tiles as HUD overlays and linking their steps with gizmo lines.
+#[cfg(feature = "pathfinding")]
+pub struct PathFindingDebugPlugin;
+
+#[cfg(feature = "pathfinding")]
+impl Plugin for PathFindingDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlayColors>()
+            .add_systems(Update, (draw_path_overlay, draw_path_gizmos))
+            .add_systems(PostUpdate, remove_stale_debug_overlays::<TiledPath>);
+    }
+}
@@ -0,0 +1,27 @@
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::prelude::*;
+use ryot_internal::prelude::*;
+use std::marker::PhantomData;
+
+/// `FogOfWarPlugin` renders the fog of war of the spectators of a given ray casting context
+/// `Marker`, dimming the tiles that were explored before and hiding the ones that were never seen.
+/// The ray casting itself must be registered with `add_ray_casting::<Marker, TilePosition, Flags>`
+/// and the spectators need a `FogOfWar<Marker, TilePosition>` component.
+pub struct FogOfWarPlugin<Marker>(PhantomData<Marker>);
+
+impl<Marker> Default for FogOfWarPlugin<Marker> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<Marker: Copy + ThreadSafe> Plugin for FogOfWarPlugin<Marker> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FogOfWarRendering>().add_systems(
+            Update,
+            apply_fog_of_war_to_sprites::<Marker>
+                .after(RayCastingSystems::Process)
+                .before(SpriteSystems::Update),
+        );
+    }
+}
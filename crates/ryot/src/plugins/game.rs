@@ -20,6 +20,7 @@ impl Plugin for ElevationPlugin {
             (
                 initialize_elevation.in_set(SpriteSystems::Initialize),
                 apply_elevation,
+                apply_displacement,
             ),
         )
         .add_systems(Last, track_position_changes);
@@ -0,0 +1,53 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_ecs::prelude::*;
+use bevy_render::render_phase::AddRenderCommand;
+use bevy_render::render_resource::SpecializedRenderPipelines;
+use bevy_render::{ExtractSchedule, Render, RenderApp, RenderSet};
+use ryot_internal::prelude::*;
+
+/// `InstancedSpritePlugin` draws the entities marked with `InstancedSprite` with a single draw call
+/// for each texture, instead of a material mesh for each entity. It's meant to be added along with
+/// the `RyotSpritePlugin`, and works best with the sprites packed into the runtime atlases.
+pub struct InstancedSpritePlugin;
+
+impl Plugin for InstancedSpritePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                detach_instanced_sprite_meshes,
+                restore_instanced_sprite_meshes,
+            ),
+        );
+
+        embed_instanced_sprite_assets(app);
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .add_render_command::<Transparent2d, DrawInstancedSprites>()
+            .init_resource::<SpecializedRenderPipelines<InstancedSpritePipeline>>()
+            .init_resource::<PreparedInstancedSprites>()
+            .add_systems(ExtractSchedule, extract_instanced_sprites)
+            .add_systems(
+                Render,
+                (
+                    queue_instanced_sprites.in_set(RenderSet::Queue),
+                    prepare_instanced_sprites.in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<InstancedSpritePipeline>()
+            .init_resource::<InstancedSpriteQuad>();
+    }
+}
@@ -4,7 +4,12 @@
 //! the Ryot framework. It facilitates the integration and management of Bevy engine
 //! functionalities, streamlining game development.
 pub mod content;
+#[cfg(feature = "debug")]
+pub mod debug_overlay;
+#[cfg(feature = "ray_casting")]
+pub mod fog_of_war;
 pub mod game;
+pub mod instancing;
 #[cfg(feature = "lmdb")]
 pub mod lmdb;
 #[cfg(feature = "pathfinding")]
@@ -17,15 +22,28 @@ pub mod prelude {
         plugins::{
             content::{BaseContentPlugin, MetaContentPlugin, VisualContentPlugin},
             game::{ElevationPlugin, GamePlugin, NavigablePlugin},
+            instancing::InstancedSpritePlugin,
             sprites::{RyotDrawingPlugin, RyotSpritePlugin},
         },
     };
 
+    #[cfg(feature = "content_packs")]
+    pub use crate::plugins::content::ContentPacksPlugin;
+
     #[cfg(feature = "lmdb")]
     pub use crate::plugins::lmdb::LmdbPlugin;
 
     #[cfg(feature = "pathfinding")]
     pub use crate::plugins::pathfinding::PathFindingPlugin;
+
+    #[cfg(feature = "ray_casting")]
+    pub use crate::plugins::fog_of_war::FogOfWarPlugin;
+
+    #[cfg(all(feature = "debug", feature = "ray_casting"))]
+    pub use crate::plugins::debug_overlay::RayCastingDebugPlugin;
+
+    #[cfg(all(feature = "debug", feature = "pathfinding"))]
+    pub use crate::plugins::debug_overlay::PathFindingDebugPlugin;
 }
 
 pub use prelude::*;
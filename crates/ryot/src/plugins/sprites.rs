@@ -1,4 +1,5 @@
 use bevy_app::{App, Plugin, PostUpdate, Update};
+use bevy_diagnostic::{Diagnostic, RegisterDiagnostic};
 use bevy_ecs::prelude::*;
 use bevy_render::view::{check_visibility, VisibilitySystems};
 use bevy_sprite::Material2dPlugin;
@@ -41,28 +42,45 @@ impl Plugin for RyotSpritePlugin {
             .init_resource::<SpriteAnimationEnabled>()
             .init_resource::<SynchronizedAnimationTimers>()
             .init_resource::<LoadedAppearances>()
+            .init_resource::<SpriteAtlases>()
+            .init_resource::<SpriteCache>()
+            .init_resource::<SpritePlaceholder>()
+            .init_resource::<PlaceholderMaterials>()
+            .register_diagnostic(Diagnostic::new(SpriteCache::MEMORY).with_suffix(" bytes"))
+            .register_diagnostic(Diagnostic::new(SpriteCache::LOADED_APPEARANCES))
             .add_event::<LoadAppearanceEvent>()
+            .add_event::<AppearanceReadyEvent>()
+            .add_event::<VisualElementsChanged>()
+            .add_event::<AnimationFinished>()
             .add_plugins(Material2dPlugin::<SpriteMaterial>::default())
             .init_resource::<RectMeshes>()
             .init_resource::<SpriteMeshes>()
             .init_resource::<TextureAtlasLayouts>()
             .add_systems(
                 Update,
-                initialize_sprite_material.in_set(SpriteSystems::Initialize),
+                (initialize_sprite_material, compose_outfit_system)
+                    .in_set(SpriteSystems::Initialize),
             )
             .add_systems(
                 Update,
                 (
                     #[cfg(feature = "debug")]
                     debug_sprite_position,
+                    invalidate_changed_appearances_system.in_set(SpriteSystems::Load),
                     load_from_entities_system.in_set(SpriteSystems::Load),
                     process_load_events_system
                         .pipe(load_sprite_system)
                         .pipe(store_loaded_appearances_system)
                         .run_if(on_event::<LoadAppearanceEvent>())
                         .in_set(SpriteSystems::Load),
+                    (pack_sprite_atlas_system, evict_unused_atlas_sprites_system)
+                        .in_set(SpriteSystems::Load),
+                    (check_appearance_readiness_system, evict_sprite_cache_system)
+                        .in_set(SpriteSystems::Load),
                     #[cfg(feature = "debug")]
                     debug_sprites.in_set(SpriteSystems::Initialize),
+                    (sync_outfit_parts_system, resolve_sprite_variant_system)
+                        .in_set(SpriteSystems::Update),
                     update_sprite_system
                         .in_set(SpriteSystems::Update)
                         .after(SpriteSystems::Initialize),
@@ -70,6 +88,7 @@ impl Plugin for RyotSpritePlugin {
                     tick_animation_system
                         .run_if(resource_exists_and_equals(SpriteAnimationEnabled(true)))
                         .in_set(AnimationSystems::Update),
+                    despawn_finished_animations_system.in_set(AnimationSystems::Update),
                 )
                     .chain()
                     .run_if(in_state(RyotContentState::Ready)),
@@ -77,6 +96,7 @@ impl Plugin for RyotSpritePlugin {
             .add_systems(
                 PostUpdate,
                 (
+                    sprite_cache_diagnostics_system,
                     update_sprite_position,
                     (move_sprites_with_animation, finish_position_animation).chain(),
                 ),
@@ -0,0 +1,87 @@
+//! Loads `VisualElements` from appearance definitions written in JSON or RON, for games that
+//! ship their own content instead of the Tibia one.
+//!
+//! The files follow the `AppearanceDefinitions` structure and must be named with the
+//! `.appearances.json` or `.appearances.ron` extension, so they don't clash with the other JSON
+//! and RON assets, like the catalog or the dynamic atlases.
+use bevy_app::{App, Plugin};
+use bevy_asset::io::Reader;
+use bevy_asset::{AssetApp, AssetLoader, Assets, AsyncReadExt, LoadContext};
+use bevy_utils::BoxedFuture;
+use ryot_core::prelude::{AppearanceDefinitions, VisualElements};
+use thiserror::Error;
+
+#[derive(Default)]
+pub struct AppearancesAssetLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum AppearancesAssetLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Could not read the file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [JSON Error](serde_json::Error)
+    #[error("Could not parse the JSON appearances: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A [RON Error](ron::error::SpannedError)
+    #[error("Could not parse the RON appearances: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+/// Parses the appearance definitions from the bytes of a file with the given extension, which
+/// is either `json` or `ron`.
+pub fn from_bytes(
+    bytes: &[u8],
+    extension: &str,
+) -> Result<VisualElements, AppearancesAssetLoaderError> {
+    let definitions: AppearanceDefinitions = match extension {
+        "ron" => ron::de::from_bytes(bytes)?,
+        _ => serde_json::from_slice(bytes)?,
+    };
+
+    Ok(definitions.into())
+}
+
+/// Registers the loader of the JSON and RON appearance definitions.
+pub struct AppearancesAssetsPlugin;
+
+impl Plugin for AppearancesAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        // The Tibia assets plugin may have initialized the asset already.
+        if !app.world.contains_resource::<Assets<VisualElements>>() {
+            app.init_asset::<VisualElements>();
+        }
+
+        app.register_asset_loader(AppearancesAssetLoader);
+    }
+}
+
+impl AssetLoader for AppearancesAssetLoader {
+    type Asset = VisualElements;
+    type Settings = ();
+    type Error = AppearancesAssetLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let extension = load_context
+                .path()
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .unwrap_or_default();
+
+            from_bytes(&bytes, extension)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["appearances.json", "appearances.ron"]
+    }
+}
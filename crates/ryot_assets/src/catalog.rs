@@ -1,8 +1,10 @@
-use bevy_asset::{Asset, Assets, Handle};
+use bevy_asset::{Asset, AssetEvent, Assets, Handle};
 use bevy_ecs::change_detection::ResMut;
+use bevy_ecs::event::EventReader;
 use bevy_ecs::prelude::Res;
 use bevy_reflect::TypePath;
 use bevy_utils::tracing::debug;
+use ryot_core::prelude::ContentPackLayers;
 use ryot_sprites::prelude::SpriteSheets;
 
 pub trait CatalogAsset: crate::RyotAsset {
@@ -18,7 +20,7 @@ pub struct Catalog {
 
 pub fn prepare_sprite_sheets<C: CatalogAsset>(
     content_assets: Res<C>,
-    mut contents: ResMut<Assets<Catalog>>,
+    contents: Res<Assets<Catalog>>,
     mut sprite_sheets: ResMut<SpriteSheets>,
 ) {
     debug!("Preparing sprite sheets");
@@ -30,7 +32,38 @@ pub fn prepare_sprite_sheets<C: CatalogAsset>(
         .clone()
         .into();
 
-    contents.remove(content_assets.catalog_content());
-
     debug!("Finished preparing sprite sheets");
 }
+
+/// Replaces the SpriteSheets resource once the catalog is modified, like when the asset server
+/// watches for changes and the catalog is edited. The sprite sheets of the applied content packs
+/// are added again, so they are still looked up first. The loaded appearances whose sprites moved
+/// to another sprite sheet are loaded again by the sprite systems.
+pub fn reload_sprite_sheets<C: CatalogAsset>(
+    content_assets: Res<C>,
+    mut asset_events: EventReader<AssetEvent<Catalog>>,
+    contents: Res<Assets<Catalog>>,
+    content_packs: Option<Res<ContentPackLayers>>,
+    mut sprite_sheets: ResMut<SpriteSheets>,
+) {
+    let handle = content_assets.catalog_content();
+
+    if !asset_events
+        .read()
+        .any(|event| event.is_modified(handle.id()))
+    {
+        return;
+    }
+
+    let Some(catalog) = contents.get(handle) else {
+        return;
+    };
+
+    *sprite_sheets = catalog.content.clone().into();
+
+    if let Some(content_packs) = content_packs {
+        sprite_sheets.splice(0..0, content_packs.sprite_sheets());
+    }
+
+    debug!("Sprite sheets reloaded");
+}
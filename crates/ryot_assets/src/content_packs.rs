@@ -0,0 +1,191 @@
+//! Loads content packs, folders with a `.pack.ron` manifest that add visual elements, override
+//! fields of existing ones and add sprite sheets on top of the base content.
+//!
+//! The appearances and catalog of a pack are loaded with the loaders registered for their
+//! extensions, so a pack can ship its appearances as `.dat`, `.appearances.json` or
+//! `.appearances.ron`. The sprite sheets listed in the catalog of a pack are loaded from the same
+//! `sprite-sheets` folder as the base ones.
+use crate::catalog::Catalog;
+use crate::RyotAsset;
+use bevy_app::{App, Plugin};
+use bevy_asset::io::Reader;
+use bevy_asset::{
+    Asset, AssetApp, AssetLoader, Assets, AsyncReadExt, Handle, LoadContext, LoadDirectError,
+};
+use bevy_ecs::change_detection::{Res, ResMut};
+use bevy_ecs::system::Commands;
+use bevy_reflect::TypePath;
+use bevy_utils::tracing::{debug, error, warn};
+use bevy_utils::BoxedFuture;
+use ryot_core::prelude::*;
+use ryot_sprites::prelude::SpriteSheets;
+use thiserror::Error;
+
+pub trait ContentPacksAsset: RyotAsset {
+    fn content_packs(&self) -> &Vec<Handle<ContentPack>>;
+}
+
+/// A content pack, with its manifest and the content loaded from it.
+#[derive(Debug, Clone, TypePath, Asset)]
+pub struct ContentPack {
+    pub manifest: ContentPackManifest,
+    pub visual_elements: Option<VisualElements>,
+    pub sprite_sheets: Vec<SpriteSheet>,
+}
+
+#[derive(Default)]
+pub struct ContentPackLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ContentPackLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Could not read the file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::error::SpannedError)
+    #[error("Could not parse the content pack manifest: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    /// A [LoadDirectError] of the appearances or catalog of the pack
+    #[error("Could not load the content of the pack: {0}")]
+    Content(#[from] LoadDirectError),
+    #[error("'{0}' is not a valid {1} for a content pack")]
+    InvalidContent(String, &'static str),
+}
+
+/// Registers the loader of the content pack manifests.
+pub struct ContentPacksAssetsPlugin;
+
+impl Plugin for ContentPacksAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<ContentPack>()
+            .register_asset_loader(ContentPackLoader);
+    }
+}
+
+impl AssetLoader for ContentPackLoader {
+    type Asset = ContentPack;
+    type Settings = ();
+    type Error = ContentPackLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let manifest: ContentPackManifest = ron::de::from_bytes(&bytes)?;
+            let folder = load_context
+                .path()
+                .parent()
+                .map(|folder| folder.to_path_buf())
+                .unwrap_or_default();
+
+            let visual_elements = match &manifest.appearances {
+                Some(path) => {
+                    let loaded = load_context.load_direct(folder.join(path)).await?;
+                    Some(loaded.take::<VisualElements>().ok_or_else(|| {
+                        ContentPackLoaderError::InvalidContent(path.clone(), "appearances")
+                    })?)
+                }
+                None => None,
+            };
+
+            let sprite_sheets = match &manifest.catalog {
+                Some(path) => {
+                    let loaded = load_context.load_direct(folder.join(path)).await?;
+                    let catalog = loaded.take::<Catalog>().ok_or_else(|| {
+                        ContentPackLoaderError::InvalidContent(path.clone(), "catalog")
+                    })?;
+
+                    catalog.content.into_iter().filter_map(Into::into).collect()
+                }
+                None => vec![],
+            };
+
+            Ok(ContentPack {
+                manifest,
+                visual_elements,
+                sprite_sheets,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["pack.ron"]
+    }
+}
+
+/// Layers the content packs on top of the VisualElements and SpriteSheets resources, after the
+/// base content is prepared. Packs are applied after the packs they depend on, and otherwise in
+/// the order they are declared, so later packs win. Sprite sheets of later packs are looked up
+/// first, so packs can also replace the sprites of the base content. Packs whose dependencies
+/// can't be resolved are logged and skipped, together with the packs depending on them.
+///
+/// The applied packs are kept in the [ContentPackLayers] resource, so they are applied again
+/// when the base content or catalog is reloaded.
+pub fn prepare_content_packs<C: ContentPacksAsset>(
+    mut commands: Commands,
+    content_assets: Res<C>,
+    mut content_packs: ResMut<Assets<ContentPack>>,
+    mut visual_elements: ResMut<VisualElements>,
+    sprite_sheets: Option<ResMut<SpriteSheets>>,
+) {
+    debug!("Preparing content packs");
+
+    let packs = content_assets
+        .content_packs()
+        .iter()
+        .map(|handle| {
+            content_packs
+                .remove(handle)
+                .expect("No content pack loaded")
+        })
+        .collect::<Vec<_>>();
+
+    let manifests = packs
+        .iter()
+        .map(|pack| pack.manifest.clone())
+        .collect::<Vec<_>>();
+
+    let (order, errors) = resolve_available_pack_order(&manifests);
+    for err in errors {
+        error!("Skipping content packs: {}", err);
+    }
+
+    let mut packs = packs.into_iter().map(Some).collect::<Vec<_>>();
+    let mut layers = ContentPackLayers::default();
+
+    for index in order {
+        let Some(pack) = packs[index].take() else {
+            continue;
+        };
+
+        let layer = ContentPackLayer {
+            name: pack.manifest.name,
+            visual_elements: pack.visual_elements,
+            overrides: pack.manifest.overrides,
+            sprite_sheets: pack.sprite_sheets,
+        };
+
+        for (group, id) in layer.apply(&mut visual_elements) {
+            warn!(
+                "Content pack '{}' overrides {:?} {}, which doesn't exist",
+                layer.name, group, id
+            );
+        }
+
+        layers.push(layer);
+    }
+
+    if let Some(mut sprite_sheets) = sprite_sheets {
+        sprite_sheets.splice(0..0, layers.sprite_sheets());
+    }
+
+    commands.insert_resource(layers);
+
+    debug!("Content packs prepared");
+}
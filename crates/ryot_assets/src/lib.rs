@@ -9,8 +9,12 @@ use bevy_asset_loader::asset_collection::AssetCollection;
 use bevy_ecs::prelude::Resource;
 use ryot_utils::prelude::*;
 
+#[cfg(feature = "appearances")]
+pub mod appearances;
 pub mod atlas;
 pub mod catalog;
+#[cfg(feature = "content_packs")]
+pub mod content_packs;
 pub mod sprites;
 pub mod visual_elements;
 
@@ -19,16 +23,28 @@ pub trait RyotAsset = Resource + AssetCollection + ThreadSafe;
 pub mod prelude {
     pub use crate::{
         atlas::AtlasLayoutsAsset,
-        catalog::{prepare_sprite_sheets, Catalog, CatalogAsset},
+        catalog::{prepare_sprite_sheets, reload_sprite_sheets, Catalog, CatalogAsset},
         ryot_asset,
         sprites::{prepare_sprite_layouts, prepare_sprite_meshes},
-        visual_elements::{prepare_visual_elements, VisualElementsAsset},
+        visual_elements::{prepare_visual_elements, reload_visual_elements, VisualElementsAsset},
+    };
+
+    #[cfg(feature = "appearances")]
+    pub use crate::appearances::{AppearancesAssetLoader, AppearancesAssetsPlugin};
+
+    #[cfg(feature = "content_packs")]
+    pub use crate::content_packs::{
+        prepare_content_packs, ContentPack, ContentPackLoader, ContentPacksAsset,
+        ContentPacksAssetsPlugin,
     };
 }
 
+#[cfg(test)]
+mod tests;
+
 /// In the Ryot ecosystem the main asset struct must implement AtlasLayoutsAsset, CatalogAsset, and
 /// VisualElementsAsset. Those assets are consumed during the preloading phase of the Ryot
-/// application and later on they are no longer needed.
+/// application, and kept around afterwards so changes to their sources can be reloaded.
 ///
 /// They use AssetCollection to dynamically load the assets from path/keys using bevy asset loader.
 /// This macro simplifies the implementation of this main asset struct by providing a default struct
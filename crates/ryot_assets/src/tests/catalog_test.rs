@@ -0,0 +1,74 @@
+use crate::prelude::*;
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_asset_loader::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_sprite::TextureAtlasLayout;
+use ryot_core::prelude::*;
+use ryot_sprites::prelude::SpriteSheets;
+
+ryot_asset!(TestAssets);
+
+fn sprite_sheet(file: &str, first_sprite_id: u32, last_sprite_id: u32) -> SpriteSheet {
+    SpriteSheet {
+        file: file.to_string(),
+        layout: SpriteLayout::OneByOne,
+        first_sprite_id,
+        last_sprite_id,
+        area: 32,
+    }
+}
+
+fn pack(name: &str, sprite_sheets: Vec<SpriteSheet>) -> ContentPackLayer {
+    ContentPackLayer {
+        name: name.to_string(),
+        sprite_sheets,
+        ..Default::default()
+    }
+}
+
+fn files(world: &World) -> Vec<String> {
+    world
+        .resource::<SpriteSheets>()
+        .iter()
+        .map(|sheet| sheet.file.clone())
+        .collect()
+}
+
+#[test]
+fn test_reload_catalog_keeps_content_pack_sprite_sheets() {
+    let mut world = World::new();
+    world.init_resource::<Events<AssetEvent<Catalog>>>();
+    world.init_resource::<SpriteSheets>();
+
+    let catalog = world
+        .get_resource_or_insert_with(Assets::<Catalog>::default)
+        .add(Catalog {
+            content: vec![ContentRecord::SpriteSheet(sprite_sheet("base-1", 1, 100))],
+        });
+    world.insert_resource(TestAssets {
+        catalog_content: catalog.clone(),
+        ..Default::default()
+    });
+
+    let mut layers = ContentPackLayers::default();
+    layers.push(pack("winter", vec![sprite_sheet("winter", 1, 10)]));
+    layers.push(pack("event", vec![sprite_sheet("event", 5, 6)]));
+    world.insert_resource(layers);
+
+    world
+        .resource_mut::<Assets<Catalog>>()
+        .get_mut(&catalog)
+        .unwrap()
+        .content
+        .push(ContentRecord::SpriteSheet(sprite_sheet("base-2", 101, 200)));
+    world.send_event(AssetEvent::Modified { id: catalog.id() });
+    world.run_system_once(reload_sprite_sheets::<TestAssets>);
+
+    assert_eq!(files(&world), vec!["event", "winter", "base-1", "base-2"]);
+
+    let sheets = world.resource::<SpriteSheets>();
+    assert_eq!(sheets.get_by_sprite_id(5).unwrap().file, "event");
+    assert_eq!(sheets.get_by_sprite_id(7).unwrap().file, "winter");
+    assert_eq!(sheets.get_by_sprite_id(150).unwrap().file, "base-2");
+}
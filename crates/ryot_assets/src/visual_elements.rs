@@ -1,30 +1,69 @@
-use bevy_asset::Assets;
+use bevy_asset::{AssetEvent, Assets};
 use bevy_ecs::change_detection::{Res, ResMut};
+use bevy_ecs::event::{EventReader, EventWriter};
 use bevy_utils::tracing::debug;
-use ryot_core::prelude::VisualElements;
+use ryot_core::prelude::{ContentPackLayers, VisualElements, VisualElementsChanged};
 
 pub trait VisualElementsAsset: crate::RyotAsset {
     fn visual_elements(&self) -> &bevy_asset::Handle<VisualElements>;
 }
 
-/// Reads the VisualElements and stores it in the VisualElements resource, removing the asset.
-/// Since VisualElements is a single resource, there is no need to keep the asset handle around,
-/// allowing direct access to the VisualElements resource.
+/// Reads the VisualElements and stores it in the VisualElements resource, allowing direct access
+/// to it. The asset is kept around, so changes to its source can be picked up by
+/// [reload_visual_elements].
 pub fn prepare_visual_elements<C: VisualElementsAsset>(
     content_assets: Res<C>,
     mut visual_elements_res: ResMut<VisualElements>,
-    mut visual_elements_assets: ResMut<Assets<VisualElements>>,
+    visual_elements_assets: Res<Assets<VisualElements>>,
 ) {
     debug!("Preparing visual elements");
 
-    let key = content_assets.visual_elements();
-
-    let Some(visual_elements) = visual_elements_assets.get(key) else {
+    let Some(visual_elements) = visual_elements_assets.get(content_assets.visual_elements()) else {
         panic!("No visual elements found")
     };
 
     *visual_elements_res = visual_elements.clone();
-    visual_elements_assets.remove(key);
 
     debug!("Visual elements prepared");
 }
+
+/// Replaces the VisualElements resource once its asset is modified, like when the asset server
+/// watches for changes and the appearances are edited, sending a [VisualElementsChanged] event
+/// with the content ids that changed. The content packs in [ContentPackLayers], if any, are applied
+/// again on top of the reloaded content before comparing it with the current one.
+pub fn reload_visual_elements<C: VisualElementsAsset>(
+    content_assets: Res<C>,
+    mut asset_events: EventReader<AssetEvent<VisualElements>>,
+    visual_elements_assets: Res<Assets<VisualElements>>,
+    content_packs: Option<Res<ContentPackLayers>>,
+    mut visual_elements_res: ResMut<VisualElements>,
+    mut changed_events: EventWriter<VisualElementsChanged>,
+) {
+    let handle = content_assets.visual_elements();
+
+    if !asset_events
+        .read()
+        .any(|event| event.is_modified(handle.id()))
+    {
+        return;
+    }
+
+    let Some(visual_elements) = visual_elements_assets.get(handle) else {
+        return;
+    };
+
+    let mut visual_elements = visual_elements.clone();
+    if let Some(content_packs) = content_packs {
+        content_packs.apply(&mut visual_elements);
+    }
+
+    let changed = visual_elements_res.diff(&visual_elements);
+    debug!("Visual elements reloaded, {} changed", changed.len());
+
+    if changed.is_empty() {
+        return;
+    }
+
+    *visual_elements_res = visual_elements;
+    changed_events.send(VisualElementsChanged(changed));
+}
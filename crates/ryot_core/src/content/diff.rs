@@ -0,0 +1,242 @@
+//! Comparing two versions of the visual elements, like the `appearances.dat` of two client
+//! updates.
+//!
+//! A [ContentDiff] reports, per content type, the ids that were added and removed and what changed
+//! in the elements present in both versions. It can be printed as human-readable text through its
+//! `Display` implementation, or serialized to JSON for tooling.
+use crate::prelude::*;
+use serde::Serialize;
+use std::fmt;
+
+#[cfg(feature = "bevy")]
+use bevy_utils::HashMap;
+#[cfg(not(feature = "bevy"))]
+use std::collections::HashMap;
+
+/// The differences between two versions of the visual elements, grouped by content type.
+pub type ContentDiff = ContentGroups<GroupDiff>;
+
+/// The differences between two versions of the visual elements of a content type.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct GroupDiff {
+    pub added: Vec<u32>,
+    pub removed: Vec<u32>,
+    pub changed: Vec<ElementChanges>,
+}
+
+/// What changed in a visual element present in both versions. Unchanged parts are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ElementChanges {
+    pub id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<Change<Flags>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<Change<Category>>,
+    /// The sprite ids of each frame group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sprite_ids: Option<Change<Vec<Vec<u32>>>>,
+    /// The animation of each frame group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animations: Option<Change<Vec<Option<Animation>>>>,
+    /// Whether anything else changed, like the layout of the sprites or the item properties.
+    pub other: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Change<T> {
+    pub old: T,
+    pub new: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    fn of(old: T, new: T) -> Option<Self> {
+        (old != new).then_some(Self { old, new })
+    }
+}
+
+impl ContentDiff {
+    pub fn between(old: &VisualElements, new: &VisualElements) -> Self {
+        let mut diff = Self::default();
+
+        for group in ContentType::ALL {
+            let empty = HashMap::default();
+            diff[group] = GroupDiff::between(
+                old.get_all_for_group(group).unwrap_or(&empty),
+                new.get_all_for_group(group).unwrap_or(&empty),
+            );
+        }
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().all(|(_, group)| group.is_empty())
+    }
+}
+
+impl GroupDiff {
+    fn between(old: &HashMap<u32, VisualElement>, new: &HashMap<u32, VisualElement>) -> Self {
+        let mut added = new
+            .keys()
+            .filter(|id| !old.contains_key(*id))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut removed = old
+            .keys()
+            .filter(|id| !new.contains_key(*id))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut changed = old
+            .iter()
+            .filter_map(|(id, old)| ElementChanges::between(old, new.get(id)?))
+            .collect::<Vec<_>>();
+
+        added.sort();
+        removed.sort();
+        changed.sort_by_key(|changes| changes.id);
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl ElementChanges {
+    fn between(old: &VisualElement, new: &VisualElement) -> Option<Self> {
+        if old == new {
+            return None;
+        }
+
+        let sprite_ids = |element: &VisualElement| {
+            element
+                .sprites_info
+                .iter()
+                .map(|info| info.ids.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let animations = |element: &VisualElement| {
+            element
+                .sprites_info
+                .iter()
+                .map(|info| info.animation.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let changes = Self {
+            id: old.id,
+            name: Change::of(old.name.clone(), new.name.clone()),
+            flags: Change::of(old.flags, new.flags),
+            category: Change::of(old.category, new.category),
+            sprite_ids: Change::of(sprite_ids(old), sprite_ids(new)),
+            animations: Change::of(animations(old), animations(new)),
+            other: false,
+        };
+
+        // Anything not covered by the changes above is reported as a whole.
+        let mut rest = old.clone();
+        rest.name.clone_from(&new.name);
+        rest.flags = new.flags;
+        rest.category = new.category;
+        rest.main_sprite_id = new.main_sprite_id;
+        for (info, new_info) in rest.sprites_info.iter_mut().zip(&new.sprites_info) {
+            info.ids.clone_from(&new_info.ids);
+            info.animation.clone_from(&new_info.animation);
+        }
+
+        Some(Self {
+            other: rest.sprites_info.len() != new.sprites_info.len() || &rest != new,
+            ..changes
+        })
+    }
+}
+
+impl fmt::Display for ContentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        for (group, diff) in self.iter() {
+            if diff.is_empty() {
+                continue;
+            }
+
+            writeln!(
+                f,
+                "{:?}: {} added, {} removed, {} changed",
+                group,
+                diff.added.len(),
+                diff.removed.len(),
+                diff.changed.len()
+            )?;
+
+            if !diff.added.is_empty() {
+                writeln!(f, "  added: {}", join_ids(&diff.added))?;
+            }
+
+            if !diff.removed.is_empty() {
+                writeln!(f, "  removed: {}", join_ids(&diff.removed))?;
+            }
+
+            for changes in &diff.changed {
+                write!(f, "{}", changes)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ElementChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  changed {}:", self.id)?;
+
+        if let Some(Change { old, new }) = &self.name {
+            writeln!(f, "    name: {:?} -> {:?}", old, new)?;
+        }
+
+        if let Some(Change { old, new }) = &self.flags {
+            writeln!(
+                f,
+                "    flags: walkable {} -> {}, blocks sight {} -> {}",
+                old.is_walkable, new.is_walkable, old.blocks_sight, new.blocks_sight
+            )?;
+        }
+
+        if let Some(Change { old, new }) = &self.category {
+            writeln!(f, "    category: {:?} -> {:?}", old, new)?;
+        }
+
+        if let Some(Change { old, new }) = &self.sprite_ids {
+            writeln!(f, "    sprite ids: {:?} -> {:?}", old, new)?;
+        }
+
+        if self.animations.is_some() {
+            writeln!(f, "    animations changed")?;
+        }
+
+        if self.other {
+            writeln!(f, "    other properties changed")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn join_ids(ids: &[u32]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
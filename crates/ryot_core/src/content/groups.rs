@@ -0,0 +1,47 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ops::{Index, IndexMut};
+
+/// A value for each content type, like the visual elements of each group of a content file.
+/// It's serialized as one field per group and can be indexed by [ContentType].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentGroups<T> {
+    pub objects: T,
+    pub outfits: T,
+    pub effects: T,
+    pub missiles: T,
+}
+
+impl<T> ContentGroups<T> {
+    /// Iterates over the value of each content type, in the order of [ContentType::ALL].
+    pub fn iter(&self) -> impl Iterator<Item = (ContentType, &T)> {
+        ContentType::ALL
+            .into_iter()
+            .map(move |group| (group, &self[group]))
+    }
+}
+
+impl<T> Index<ContentType> for ContentGroups<T> {
+    type Output = T;
+
+    fn index(&self, group: ContentType) -> &Self::Output {
+        match group {
+            ContentType::Object => &self.objects,
+            ContentType::Outfit => &self.outfits,
+            ContentType::Effect => &self.effects,
+            ContentType::Missile => &self.missiles,
+        }
+    }
+}
+
+impl<T> IndexMut<ContentType> for ContentGroups<T> {
+    fn index_mut(&mut self, group: ContentType) -> &mut Self::Output {
+        match group {
+            ContentType::Object => &mut self.objects,
+            ContentType::Outfit => &mut self.outfits,
+            ContentType::Effect => &mut self.effects,
+            ContentType::Missile => &mut self.missiles,
+        }
+    }
+}
@@ -30,6 +30,15 @@ impl Default for ContentId {
     }
 }
 
+impl ContentType {
+    pub const ALL: [ContentType; 4] = [
+        ContentType::Object,
+        ContentType::Outfit,
+        ContentType::Effect,
+        ContentType::Missile,
+    ];
+}
+
 impl ContentId {
     pub fn is_none(&self) -> bool {
         self.get_id() == 0
@@ -62,6 +71,17 @@ impl ContentId {
     }
 }
 
+impl From<(ContentType, u32)> for ContentId {
+    fn from((group, id): (ContentType, u32)) -> Self {
+        match group {
+            ContentType::Object => ContentId::Object(id),
+            ContentType::Outfit => ContentId::Outfit(id),
+            ContentType::Effect => ContentId::Effect(id),
+            ContentType::Missile => ContentId::Missile(id),
+        }
+    }
+}
+
 impl fmt::Display for ContentId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1,9 +1,18 @@
 mod id;
 pub use id::{ContentId, ContentType};
 
+mod groups;
+pub use groups::ContentGroups;
+
+pub mod diff;
+
+pub mod pack;
+
 pub mod sprite;
 
 mod state;
 pub use state::{transition_to_ready, RyotContentState};
 
 pub mod record;
+
+pub mod search;
@@ -0,0 +1,260 @@
+//! Content packs layered on top of the base content.
+//!
+//! A content pack is a folder with a manifest describing what it adds to the game: new visual
+//! elements, overrides of individual fields of existing ones, and new sprite sheets. Packs are
+//! applied in order, after the packs they depend on, so mods and seasonal events can ship small
+//! overlay packs instead of full content dumps.
+use crate::prelude::*;
+use derive_more::Deref;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[cfg(feature = "bevy")]
+use bevy_utils::{HashMap, HashSet};
+#[cfg(not(feature = "bevy"))]
+use std::collections::{HashMap, HashSet};
+
+/// The manifest of a content pack, usually written in RON next to the content of the pack.
+/// The paths of the appearances and catalog are relative to the manifest.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentPackManifest {
+    pub name: String,
+    /// The names of the packs that must be applied before this one.
+    pub dependencies: Vec<String>,
+    /// The visual elements added by the pack, replacing the existing ones with the same id.
+    pub appearances: Option<String>,
+    /// The catalog with the sprite sheets added by the pack.
+    pub catalog: Option<String>,
+    /// The fields of existing visual elements changed by the pack.
+    pub overrides: AppearanceOverrides,
+}
+
+/// Overrides of individual fields of visual elements, grouped by content type.
+pub type AppearanceOverrides = ContentGroups<Vec<VisualElementOverride>>;
+
+/// The fields of a visual element changed by a content pack. Missing fields are kept as they are.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VisualElementOverride {
+    pub id: u32,
+    pub name: Option<String>,
+    pub main_sprite_id: Option<u32>,
+    pub sprites_info: Option<Vec<SpriteInfo>>,
+    pub flags: Option<Flags>,
+    pub category: Option<Category>,
+    pub properties: Option<Properties>,
+    pub item_properties: Option<ItemProperties>,
+}
+
+impl VisualElementOverride {
+    pub fn apply(&self, element: &mut VisualElement) {
+        let overrides = self.clone();
+
+        element.name = overrides.name.unwrap_or(element.name.clone());
+        element.main_sprite_id = overrides.main_sprite_id.or(element.main_sprite_id);
+        element.flags = overrides.flags.unwrap_or(element.flags);
+        element.category = overrides.category.unwrap_or(element.category);
+        element.properties = overrides.properties.unwrap_or(element.properties);
+
+        if let Some(sprites_info) = overrides.sprites_info {
+            element.sprites_info = sprites_info;
+        }
+
+        if let Some(item_properties) = overrides.item_properties {
+            element.item_properties = item_properties;
+        }
+    }
+}
+
+impl AppearanceOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.iter().all(|(_, overrides)| overrides.is_empty())
+    }
+}
+
+impl VisualElements {
+    /// Adds the visual elements of a pack, replacing the existing ones with the same id.
+    pub fn layer(&mut self, mut pack: VisualElements) {
+        for (group, elements) in pack.drain() {
+            self.entry(group).or_default().extend(elements);
+        }
+    }
+
+    /// Applies the overrides of a pack, returning the overrides whose visual element doesn't exist.
+    pub fn apply_overrides(&mut self, overrides: &AppearanceOverrides) -> Vec<(ContentType, u32)> {
+        let mut missing = Vec::new();
+
+        for (group, overrides) in overrides.iter() {
+            for element_override in overrides {
+                match self
+                    .get_mut(&group)
+                    .and_then(|elements| elements.get_mut(&element_override.id))
+                {
+                    Some(element) => element_override.apply(element),
+                    None => missing.push((group, element_override.id)),
+                }
+            }
+        }
+
+        missing
+    }
+}
+
+/// The content of a pack applied on top of the base content: the visual elements it adds, the
+/// overrides of existing ones and the sprite sheets it adds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContentPackLayer {
+    pub name: String,
+    pub visual_elements: Option<VisualElements>,
+    pub overrides: AppearanceOverrides,
+    pub sprite_sheets: Vec<SpriteSheet>,
+}
+
+impl ContentPackLayer {
+    /// Adds the visual elements of the pack and applies its overrides, returning the overrides
+    /// whose visual element doesn't exist.
+    pub fn apply(&self, visual_elements: &mut VisualElements) -> Vec<(ContentType, u32)> {
+        if let Some(pack_elements) = &self.visual_elements {
+            visual_elements.layer(pack_elements.clone());
+        }
+
+        visual_elements.apply_overrides(&self.overrides)
+    }
+}
+
+/// The content packs applied on top of the base content, in the order they were applied, so they
+/// can be applied again when the base content is reloaded.
+#[derive(Clone, Debug, Default, PartialEq, Deref)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::prelude::Resource))]
+pub struct ContentPackLayers(Vec<ContentPackLayer>);
+
+impl ContentPackLayers {
+    pub fn push(&mut self, layer: ContentPackLayer) {
+        self.0.push(layer);
+    }
+
+    /// Applies every pack in order, skipping the overrides whose visual element doesn't exist.
+    pub fn apply(&self, visual_elements: &mut VisualElements) {
+        for layer in &self.0 {
+            layer.apply(visual_elements);
+        }
+    }
+
+    /// Returns the sprite sheets of every pack, the ones of later packs first, so they are looked
+    /// up before the sheets of earlier packs and of the base content.
+    pub fn sprite_sheets(&self) -> Vec<SpriteSheet> {
+        self.0
+            .iter()
+            .rev()
+            .flat_map(|layer| layer.sprite_sheets.iter().cloned())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ContentPackError {
+    #[error("Content pack '{0}' is declared more than once")]
+    Duplicated(String),
+    #[error("Content pack '{pack}' depends on '{dependency}', which is not available")]
+    MissingDependency { pack: String, dependency: String },
+    #[error("Content packs {0:?} depend on each other")]
+    CyclicDependency(Vec<String>),
+}
+
+/// Returns the order in which the packs must be applied: each pack comes after its dependencies,
+/// and otherwise keeps its declared order, so later packs override earlier ones.
+///
+/// Fails with the first problem found, see [resolve_available_pack_order] to skip the affected
+/// packs instead.
+pub fn resolve_pack_order(
+    manifests: &[ContentPackManifest],
+) -> Result<Vec<usize>, ContentPackError> {
+    let (order, errors) = resolve_available_pack_order(manifests);
+
+    match errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(order),
+    }
+}
+
+/// Returns the order in which the packs that can be applied must be, like [resolve_pack_order],
+/// together with the problems that kept the other packs out. A pack declared more than once is
+/// only kept the first time, and the packs with a missing dependency or part of a dependency
+/// cycle are skipped, as well as the packs depending on them.
+pub fn resolve_available_pack_order(
+    manifests: &[ContentPackManifest],
+) -> (Vec<usize>, Vec<ContentPackError>) {
+    let mut errors = Vec::new();
+    let mut names: HashMap<&str, usize> = HashMap::default();
+    let mut skipped: HashSet<usize> = HashSet::default();
+
+    for (index, manifest) in manifests.iter().enumerate() {
+        if names.contains_key(manifest.name.as_str()) {
+            errors.push(ContentPackError::Duplicated(manifest.name.clone()));
+            skipped.insert(index);
+        } else {
+            names.insert(manifest.name.as_str(), index);
+        }
+    }
+
+    // Skipping a pack makes its dependents unavailable too, so this runs until nothing changes.
+    loop {
+        let missing = manifests.iter().enumerate().find_map(|(index, manifest)| {
+            if skipped.contains(&index) {
+                return None;
+            }
+
+            manifest
+                .dependencies
+                .iter()
+                .find(|dependency| {
+                    names
+                        .get(dependency.as_str())
+                        .map_or(true, |dependency| skipped.contains(dependency))
+                })
+                .map(|dependency| (index, dependency))
+        });
+
+        let Some((index, dependency)) = missing else {
+            break;
+        };
+
+        errors.push(ContentPackError::MissingDependency {
+            pack: manifests[index].name.clone(),
+            dependency: dependency.clone(),
+        });
+        skipped.insert(index);
+    }
+
+    let mut applied: HashSet<usize> = HashSet::default();
+    let mut order = Vec::with_capacity(manifests.len());
+
+    while order.len() + skipped.len() < manifests.len() {
+        let next = manifests.iter().enumerate().find(|(index, manifest)| {
+            !applied.contains(index)
+                && !skipped.contains(index)
+                && manifest
+                    .dependencies
+                    .iter()
+                    .all(|dependency| applied.contains(&names[dependency.as_str()]))
+        });
+
+        let Some((index, _)) = next else {
+            errors.push(ContentPackError::CyclicDependency(
+                manifests
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !applied.contains(index) && !skipped.contains(index))
+                    .map(|(_, manifest)| manifest.name.clone())
+                    .collect(),
+            ));
+            break;
+        };
+
+        applied.insert(index);
+        order.push(index);
+    }
+
+    (order, errors)
+}
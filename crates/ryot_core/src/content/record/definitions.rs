@@ -0,0 +1,54 @@
+use crate::prelude::*;
+
+/// A serde representation of `VisualElements`, for games that define their own appearances
+/// instead of using the Tibia ones. It maps one-to-one onto `VisualElement` and its parts, grouped
+/// by content type, and can be written in any serde format, like JSON or RON.
+///
+/// The main sprite of an element can be omitted, in which case the first sprite of its first
+/// frame group is used. Elements without an id or sprites are skipped, like in the Tibia content.
+pub type AppearanceDefinitions = ContentGroups<Vec<VisualElement>>;
+
+impl From<AppearanceDefinitions> for VisualElements {
+    fn from(definitions: AppearanceDefinitions) -> Self {
+        let mut visual_elements = VisualElements::default();
+
+        for (group, elements) in definitions.iter() {
+            for element in elements {
+                if element.id == 0 || element.sprites_info.is_empty() {
+                    continue;
+                }
+
+                let main_sprite_id = element.main_sprite_id.or_else(|| {
+                    element
+                        .sprites_info
+                        .first()
+                        .and_then(|sprite_info| sprite_info.ids.first().copied())
+                });
+
+                visual_elements.entry(group).or_default().insert(
+                    element.id,
+                    VisualElement {
+                        main_sprite_id,
+                        ..element.clone()
+                    },
+                );
+            }
+        }
+
+        visual_elements
+    }
+}
+
+impl From<&VisualElements> for AppearanceDefinitions {
+    fn from(visual_elements: &VisualElements) -> Self {
+        let mut definitions = AppearanceDefinitions::default();
+
+        for (group, elements) in visual_elements.iter() {
+            let group_elements = &mut definitions[*group];
+            group_elements.extend(elements.values().cloned());
+            group_elements.sort_by_key(|element| element.id);
+        }
+
+        definitions
+    }
+}
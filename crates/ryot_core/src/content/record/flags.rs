@@ -1,4 +1,5 @@
 use crate::prelude::Navigable;
+use serde::{Deserialize, Serialize};
 
 /// Standard implementation of `Navigable` used within the Ryot framework.
 ///
@@ -10,8 +11,9 @@ use crate::prelude::Navigable;
 /// # Attributes
 /// * `is_walkable` - Indicates whether the element permits movement over it.
 /// * `blocks_sight` - Determines if the element impedes vision.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 #[cfg_attr(feature = "bevy", derive(bevy_ecs::prelude::Component))]
+#[serde(default)]
 pub struct Flags {
     pub is_walkable: bool,
     pub blocks_sight: bool,
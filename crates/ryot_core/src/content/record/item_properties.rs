@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+
+/// The full set of properties of an item, as described by the content catalog.
+///
+/// `Flags`, `Category` and `Properties` only keep what is needed for navigation, drawing and
+/// organizing the content. `ItemProperties` keeps everything else, so game and editor code can
+/// answer questions like "can this be picked up" or "how much light does this emit" without going
+/// back to the original content format.
+///
+/// Items are movable unless told otherwise, like in the original content format, so the default
+/// properties describe a plain movable item.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ItemProperties {
+    /// The walking speed over the item, if it's a ground.
+    pub ground_speed: Option<u32>,
+    pub is_ground: bool,
+    pub is_edge: bool,
+    pub is_bottom: bool,
+    pub is_top: bool,
+    pub is_container: bool,
+    pub is_stackable: bool,
+    pub is_usable: bool,
+    pub can_force_use: bool,
+    pub is_multi_use: bool,
+    pub write: Option<WriteInfo>,
+    pub is_liquid_pool: bool,
+    pub is_liquid_container: bool,
+    pub is_not_movable: bool,
+    pub is_avoidable: bool,
+    pub has_no_movement_animation: bool,
+    pub is_pickupable: bool,
+    pub is_hangable: bool,
+    pub hook: HookInfo,
+    pub is_rotatable: bool,
+    pub light: Option<Light>,
+    pub cannot_hide: bool,
+    pub is_translucent: bool,
+    pub should_always_animate: bool,
+    pub minimap_color: Option<u32>,
+    pub lens_help: Option<u32>,
+    pub should_ignore_look: bool,
+    /// The equipment slot the item is worn in.
+    pub slot: Option<u32>,
+    pub default_action: Option<ItemAction>,
+    pub market: Option<MarketInfo>,
+    pub is_wrappable: bool,
+    pub is_unwrappable: bool,
+    pub npc_sales: Vec<NpcSale>,
+    /// The item this one turns back into once it expires.
+    pub changed_to_expire: Option<u32>,
+    pub is_corpse: bool,
+    pub is_player_corpse: bool,
+    pub cyclopedia_id: Option<u32>,
+    pub is_ammo: bool,
+    pub should_show_off_socket: bool,
+    pub is_reportable: bool,
+    pub upgrade_classification: Option<u32>,
+    pub reverse_addons: ReverseAddons,
+    pub wears_out: bool,
+    pub clock_expires: bool,
+    pub expires: bool,
+    pub expire_stops: bool,
+    pub is_decoration_kit: bool,
+}
+
+impl ItemProperties {
+    pub fn is_movable(&self) -> bool {
+        !self.is_not_movable
+    }
+}
+
+/// How much text can be written on an item, and whether it can only be written once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WriteInfo {
+    pub max_length: u32,
+    pub once: bool,
+}
+
+/// The walls an item can be hung on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HookInfo {
+    pub south: bool,
+    pub east: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Light {
+    pub brightness: u32,
+    pub color: u32,
+}
+
+/// The action performed when the item is clicked without any modifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ItemAction {
+    Look,
+    Use,
+    Open,
+    AutoWalk,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketInfo {
+    pub category: Option<i32>,
+    pub trade_as_object_id: Option<u32>,
+    pub show_as_object_id: Option<u32>,
+    pub name: Option<String>,
+    pub restrict_to_vocations: Vec<Vocation>,
+    pub minimum_level: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Vocation {
+    Any,
+    None,
+    Knight,
+    Paladin,
+    Sorcerer,
+    Druid,
+    Promoted,
+}
+
+/// Where an item is sold or bought by an NPC, and for how much.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NpcSale {
+    pub name: String,
+    pub location: String,
+    pub sale_price: u32,
+    pub buy_price: u32,
+    pub currency_id: u32,
+    pub currency_quest_flag_display_name: Option<String>,
+}
+
+/// The directions in which the addons of an outfit are drawn reversed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReverseAddons {
+    pub east: bool,
+    pub west: bool,
+    pub south: bool,
+    pub north: bool,
+}
@@ -4,11 +4,20 @@ use serde::{Deserialize, Serialize};
 mod category;
 pub use category::Category;
 
+mod definitions;
+pub use definitions::AppearanceDefinitions;
+
 mod flags;
 pub use flags::Flags;
 
+mod item_properties;
+pub use item_properties::{
+    HookInfo, ItemAction, ItemProperties, Light, MarketInfo, NpcSale, ReverseAddons, Vocation,
+    WriteInfo,
+};
+
 mod visual_element;
-pub use visual_element::{VisualElement, VisualElements};
+pub use visual_element::{VisualElement, VisualElements, VisualElementsChanged};
 
 /// Ryot expects the sprite sheets to be cataloged in a JSON file. This file contains a list of
 /// elements of type `SpriteSheet` that represents the sprite sheet information.
@@ -1,5 +1,6 @@
 use crate::prelude::*;
 use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "bevy")]
 use bevy_utils::HashMap;
@@ -21,9 +22,34 @@ impl VisualElements {
     pub fn get_for_group_and_id(&self, group: ContentType, id: u32) -> Option<&VisualElement> {
         self.get(&group)?.get(&id)
     }
+
+    /// Returns the content ids whose visual elements were added, removed or changed in `other`,
+    /// sorted.
+    pub fn diff(&self, other: &VisualElements) -> Vec<ContentId> {
+        let mut changed = self
+            .iter()
+            .chain(other.iter())
+            .flat_map(|(group, elements)| elements.keys().map(|id| (*group, *id)))
+            .filter(|(group, id)| {
+                self.get_for_group_and_id(*group, *id) != other.get_for_group_and_id(*group, *id)
+            })
+            .map(ContentId::from)
+            .collect::<Vec<_>>();
+
+        changed.sort();
+        changed.dedup();
+        changed
+    }
 }
 
-#[derive(Clone, PartialEq, Default, Debug)]
+/// An event sent when the visual elements are reloaded, with the content ids whose visual
+/// elements were added, removed or changed.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::event::Event))]
+pub struct VisualElementsChanged(pub Vec<ContentId>);
+
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct VisualElement {
     pub id: u32,
     pub name: String,
@@ -32,4 +58,5 @@ pub struct VisualElement {
     pub flags: Flags,
     pub category: Category,
     pub properties: Properties,
+    pub item_properties: ItemProperties,
 }
@@ -0,0 +1,344 @@
+//! Searching the visual elements by name, id, category and flags.
+//!
+//! A [ContentIndex] is built once from the [VisualElements] and answers [ContentQuery]s with the
+//! matching content ids, ranked by how well their names match the query text. Queries can also be
+//! parsed from text, so the same syntax works in search boxes and command lines:
+//!
+//! ```text
+//! torch !lit id:100-200 category:decor group:object walkable !sight animated light
+//! ```
+use crate::prelude::*;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A query over the visual elements. Empty fields match everything.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentQuery {
+    /// The text matched against the names, either as a substring or fuzzily, or against the ids.
+    pub text: String,
+    /// The words that must not be part of the names.
+    pub excluded: Vec<String>,
+    pub groups: Vec<ContentType>,
+    pub ids: Option<RangeInclusive<u32>>,
+    pub categories: Vec<Category>,
+    pub is_walkable: Option<bool>,
+    pub blocks_sight: Option<bool>,
+    pub has_animation: Option<bool>,
+    pub has_light: Option<bool>,
+}
+
+impl ContentQuery {
+    pub fn with_text(self, text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..self
+        }
+    }
+
+    pub fn without_text(mut self, text: impl Into<String>) -> Self {
+        self.excluded.push(text.into().to_lowercase());
+        self
+    }
+
+    pub fn with_group(mut self, group: ContentType) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    pub fn with_ids(self, ids: RangeInclusive<u32>) -> Self {
+        Self {
+            ids: Some(ids),
+            ..self
+        }
+    }
+
+    pub fn with_category(mut self, category: Category) -> Self {
+        self.categories.push(category);
+        self
+    }
+
+    pub fn with_walkable(self, is_walkable: bool) -> Self {
+        Self {
+            is_walkable: Some(is_walkable),
+            ..self
+        }
+    }
+
+    pub fn with_blocks_sight(self, blocks_sight: bool) -> Self {
+        Self {
+            blocks_sight: Some(blocks_sight),
+            ..self
+        }
+    }
+
+    pub fn with_animation(self, has_animation: bool) -> Self {
+        Self {
+            has_animation: Some(has_animation),
+            ..self
+        }
+    }
+
+    pub fn with_light(self, has_light: bool) -> Self {
+        Self {
+            has_light: Some(has_light),
+            ..self
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn matches(&self, entry: &IndexEntry) -> bool {
+        let (group, id) = entry
+            .content_id
+            .as_group_and_id()
+            .expect("Indexed content must have a group");
+
+        let matches_flag = |filter: Option<bool>, value: bool| filter.map_or(true, |f| f == value);
+
+        (self.groups.is_empty() || self.groups.contains(&group))
+            && !self.excluded.iter().any(|text| entry.name.contains(text))
+            && self.ids.as_ref().map_or(true, |ids| ids.contains(&id))
+            && (self.categories.is_empty() || self.categories.contains(&entry.category))
+            && matches_flag(self.is_walkable, entry.flags.is_walkable)
+            && matches_flag(self.blocks_sight, entry.flags.blocks_sight)
+            && matches_flag(self.has_animation, entry.has_animation)
+            && matches_flag(self.has_light, entry.has_light)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ContentQueryError {
+    #[error("Invalid id range '{0}', expected 'id:<id>' or 'id:<from>-<to>'")]
+    InvalidIds(String),
+    #[error("Unknown category '{0}'")]
+    UnknownCategory(String),
+    #[error("Unknown group '{0}'")]
+    UnknownGroup(String),
+}
+
+impl FromStr for ContentQuery {
+    type Err = ContentQueryError;
+
+    /// Parses a query from whitespace separated terms. `id:`, `category:` and `group:` terms
+    /// filter by id range, category and content type, `walkable`, `sight`, `animated` and `light`
+    /// filter by flags, negated with a leading `!`, and every other term is part of the text,
+    /// unless negated as well, which excludes the names containing it.
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let mut text = Vec::new();
+        let mut content_query = ContentQuery::default();
+
+        for term in query.split_whitespace() {
+            let (value, term) = match term.strip_prefix('!') {
+                Some(term) => (false, term),
+                None => (true, term),
+            };
+
+            content_query = match term.to_lowercase().split_once(':') {
+                Some(("id", ids)) => content_query.with_ids(parse_ids(ids)?),
+                Some(("category", category)) => {
+                    content_query.with_category(parse_category(category)?)
+                }
+                Some(("group", group)) => content_query.with_group(parse_group(group)?),
+                _ => match term.to_lowercase().as_str() {
+                    "walkable" => content_query.with_walkable(value),
+                    "sight" => content_query.with_blocks_sight(value),
+                    "animated" => content_query.with_animation(value),
+                    "light" => content_query.with_light(value),
+                    _ if !value => content_query.without_text(term),
+                    _ => {
+                        text.push(term);
+                        content_query
+                    }
+                },
+            };
+        }
+
+        Ok(content_query.with_text(text.join(" ")))
+    }
+}
+
+fn parse_ids(ids: &str) -> Result<RangeInclusive<u32>, ContentQueryError> {
+    let invalid = || ContentQueryError::InvalidIds(ids.to_string());
+    let parse = |id: &str, default: u32| match id {
+        "" => Ok(default),
+        id => id.parse::<u32>().map_err(|_| invalid()),
+    };
+
+    match ids.split_once('-') {
+        Some((from, to)) => Ok(parse(from, u32::MIN)?..=parse(to, u32::MAX)?),
+        None => {
+            let id = ids.parse::<u32>().map_err(|_| invalid())?;
+            Ok(id..=id)
+        }
+    }
+}
+
+fn parse_category(category: &str) -> Result<Category, ContentQueryError> {
+    Ok(match category {
+        "bottom" => Category::Bottom,
+        "containers" => Category::Containers,
+        "corpses" => Category::Corpses,
+        "decor" => Category::Decor,
+        "edges" => Category::Edges,
+        "ground" => Category::Ground,
+        "miscellaneous" => Category::Miscellaneous,
+        "top" => Category::Top,
+        "wearable" => Category::Wearable,
+        custom => Category::Custom(
+            custom
+                .parse()
+                .map_err(|_| ContentQueryError::UnknownCategory(custom.to_string()))?,
+        ),
+    })
+}
+
+fn parse_group(group: &str) -> Result<ContentType, ContentQueryError> {
+    Ok(match group {
+        "object" | "objects" => ContentType::Object,
+        "outfit" | "outfits" => ContentType::Outfit,
+        "effect" | "effects" => ContentType::Effect,
+        "missile" | "missiles" => ContentType::Missile,
+        group => return Err(ContentQueryError::UnknownGroup(group.to_string())),
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct IndexEntry {
+    content_id: ContentId,
+    name: String,
+    category: Category,
+    flags: Flags,
+    has_animation: bool,
+    has_light: bool,
+}
+
+/// An index over the visual elements, sorted by content id, that answers [ContentQuery]s.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::prelude::Resource))]
+pub struct ContentIndex(Vec<IndexEntry>);
+
+impl From<&VisualElements> for ContentIndex {
+    fn from(visual_elements: &VisualElements) -> Self {
+        let mut entries = visual_elements
+            .iter()
+            .flat_map(|(group, elements)| {
+                elements.iter().map(|(id, element)| IndexEntry {
+                    content_id: (*group, *id).into(),
+                    name: element.name.to_lowercase(),
+                    category: element.category,
+                    flags: element.flags,
+                    has_animation: element
+                        .sprites_info
+                        .iter()
+                        .any(|info| info.animation.is_some()),
+                    has_light: element.item_properties.light.is_some(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        entries.sort_by_key(|entry| entry.content_id);
+        Self(entries)
+    }
+}
+
+impl ContentIndex {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the content ids matching the query, most relevant first. Without a query text,
+    /// every content matching the filters is returned, sorted by content id.
+    pub fn search(&self, query: &ContentQuery) -> Vec<ContentId> {
+        let text = query.text.trim().to_lowercase();
+
+        let mut results = self
+            .0
+            .iter()
+            .filter(|entry| query.matches(entry))
+            .filter_map(|entry| Some((relevance(entry, &text)?, entry)))
+            .collect::<Vec<_>>();
+
+        // Among equally relevant matches, the shorter names are the closest ones to the text.
+        results.sort_by_key(|(relevance, entry)| {
+            let name_len = if text.is_empty() { 0 } else { entry.name.len() };
+            (u32::MAX - relevance, name_len, entry.content_id)
+        });
+
+        results
+            .into_iter()
+            .map(|(_, entry)| entry.content_id)
+            .collect()
+    }
+}
+
+/// How well the entry matches the text, from exact names and ids down to fuzzy matches, or None
+/// if it doesn't match at all.
+fn relevance(entry: &IndexEntry, text: &str) -> Option<u32> {
+    if text.is_empty() {
+        return Some(0);
+    }
+
+    if entry.name == text || entry.content_id.get_id().to_string() == text {
+        return Some(1000);
+    }
+
+    if entry.name.starts_with(text) {
+        return Some(800);
+    }
+
+    if entry
+        .name
+        .split_whitespace()
+        .any(|word| word.starts_with(text))
+    {
+        return Some(600);
+    }
+
+    if let Some(position) = entry.name.find(text) {
+        return Some(500 - position.min(100) as u32);
+    }
+
+    fuzzy_relevance(&entry.name, text)
+}
+
+/// Matches the characters of the text in order, scoring how close together they are in the name.
+/// The closest match is used, and matches with more gaps than half the length of the text are
+/// discarded, so short texts don't match unrelated names.
+fn fuzzy_relevance(name: &str, text: &str) -> Option<u32> {
+    let text = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<Vec<_>>();
+    let first = *text.first()?;
+    let max_gaps = (text.len() / 2).max(1);
+
+    let span = name
+        .char_indices()
+        .filter(|(_, c)| *c == first)
+        .filter_map(|(start, _)| {
+            let mut chars = name[start..].char_indices().skip(1);
+            let mut last = 0;
+
+            for expected in &text[1..] {
+                let (index, _) = chars.find(|(_, c)| c == expected)?;
+                last = index;
+            }
+
+            Some(name[start..start + last].chars().count() + 1)
+        })
+        .min()?;
+
+    let gaps = span.saturating_sub(text.len());
+    if gaps > max_gaps {
+        return None;
+    }
+
+    Some(300u32.saturating_sub(gaps as u32 * 10).max(1))
+}
@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 pub mod layout;
 pub mod sprite_sheet;
 
-#[derive(Clone, PartialEq, Default, Debug)]
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SpriteInfo {
     pub ids: Vec<u32>,
     pub layers: u32,
@@ -9,16 +12,37 @@ pub struct SpriteInfo {
     pub pattern_height: u32,
     pub pattern_depth: u32,
     pub animation: Option<Animation>,
+    /// The size, in pixels, of the square that bounds the sprites, anchored at the bottom right
+    /// of their tile. It's at least the size of a tile.
+    pub bounding_square: u32,
+    /// Whether the sprites fully cover what is drawn below them.
+    pub is_opaque: bool,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Animation {
     pub start_phase: u32,
     pub synchronized: bool,
     pub is_start_random: bool,
+    pub loop_type: LoopType,
+    pub loop_count: u32,
     pub phases: Vec<(u32, u32)>,
 }
 
+/// How an animation behaves once it reaches its last phase.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LoopType {
+    /// Plays back and forth, from the first to the last phase and back to the first one.
+    PingPong,
+    /// Restarts from the first phase, forever.
+    #[default]
+    Infinite,
+    /// Restarts from the first phase until it's played `loop_count` times, then holds the last
+    /// phase. A single loop plays the animation once and holds it.
+    Counted,
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "bevy", derive(bevy_ecs::prelude::Component))]
 #[repr(usize)]
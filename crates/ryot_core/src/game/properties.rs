@@ -1,10 +1,17 @@
 use derive_more::{Deref, DerefMut};
+use glam::Vec2;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Properties {
     pub elevation: Elevation,
+    pub displacement: Displacement,
+    /// Whether the content lies on the ground, like a corpse, raising the creatures on top of it.
+    pub lying_object: bool,
+    /// Whether the content is drawn with an effect on top of it, like a magic field.
+    pub has_top_effect: bool,
 }
 
 #[derive(Debug, Clone, Default, Copy, PartialEq, Serialize, Deserialize, Deref, DerefMut)]
@@ -22,3 +29,21 @@ impl From<u32> for Elevation {
         Elevation(value as f32)
     }
 }
+
+/// The offset, in pixels, by which a sprite is drawn up and to the left of its tile.
+/// Wall torches and hanging signs, for example, are displaced to hang from their walls.
+#[derive(Debug, Clone, Default, Copy, PartialEq, Serialize, Deserialize, Deref, DerefMut)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::component::Component))]
+pub struct Displacement(Vec2);
+
+impl Display for Displacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "D:{},{}", self.0.x, self.0.y)
+    }
+}
+
+impl From<(u32, u32)> for Displacement {
+    fn from((x, y): (u32, u32)) -> Self {
+        Displacement(Vec2::new(x as f32, y as f32))
+    }
+}
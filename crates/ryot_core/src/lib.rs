@@ -10,15 +10,26 @@ pub mod game;
 pub mod prelude {
     pub use crate::{
         content::{
-            record::{Category, ContentRecord, Flags, VisualElement, VisualElements},
+            diff::{Change, ContentDiff, ElementChanges, GroupDiff},
+            pack::{
+                resolve_available_pack_order, resolve_pack_order, AppearanceOverrides,
+                ContentPackError, ContentPackLayer, ContentPackLayers, ContentPackManifest,
+                VisualElementOverride,
+            },
+            record::{
+                AppearanceDefinitions, Category, ContentRecord, Flags, HookInfo, ItemAction,
+                ItemProperties, Light, MarketInfo, NpcSale, ReverseAddons, VisualElement,
+                VisualElements, VisualElementsChanged, Vocation, WriteInfo,
+            },
+            search::{ContentIndex, ContentQuery, ContentQueryError},
             sprite::{
                 layout::{SpriteLayout, SpriteLayoutIter, TextureAtlasLayouts},
                 sprite_sheet::SpriteSheet,
-                Animation, FrameGroup, SpriteInfo,
+                Animation, FrameGroup, LoopType, SpriteInfo,
             },
-            ContentId, ContentType, RyotContentState,
+            ContentGroups, ContentId, ContentType, RyotContentState,
         },
-        game::{append_navigable, Elevation, Navigable, Point, Properties},
+        game::{append_navigable, Displacement, Elevation, Navigable, Point, Properties},
     };
 
     #[cfg(feature = "bevy")]
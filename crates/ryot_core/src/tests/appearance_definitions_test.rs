@@ -0,0 +1,77 @@
+use crate::prelude::*;
+
+const DEFINITIONS: &str = r#"{
+    "objects": [
+        {
+            "id": 100,
+            "name": "torch",
+            "sprites_info": [
+                {
+                    "ids": [10, 11],
+                    "layers": 1,
+                    "pattern_width": 1,
+                    "pattern_height": 1,
+                    "pattern_depth": 1,
+                    "animation": {
+                        "loop_type": "PingPong",
+                        "phases": [[100, 100], [200, 300]]
+                    }
+                }
+            ],
+            "flags": { "blocks_sight": true },
+            "category": { "Custom": 6 },
+            "properties": { "displacement": [8, 8] },
+            "item_properties": { "is_pickupable": true, "light": { "brightness": 3, "color": 206 } }
+        },
+        { "id": 0, "sprites_info": [{ "ids": [1] }] },
+        { "id": 101 }
+    ],
+    "effects": [
+        { "id": 1, "main_sprite_id": 42, "sprites_info": [{ "ids": [40] }] }
+    ]
+}"#;
+
+#[test]
+fn test_visual_elements_from_definitions() {
+    let definitions: AppearanceDefinitions = serde_json::from_str(DEFINITIONS).unwrap();
+    let visual_elements: VisualElements = definitions.into();
+
+    let objects = visual_elements
+        .get_all_for_group(ContentType::Object)
+        .unwrap();
+    assert_eq!(objects.len(), 1);
+
+    let torch = visual_elements
+        .get_for_group_and_id(ContentType::Object, 100)
+        .unwrap();
+    assert_eq!(torch.name, "torch");
+    assert_eq!(torch.main_sprite_id, Some(10));
+    assert_eq!(torch.flags, Flags::new(true, true));
+    assert_eq!(torch.category, Category::Custom(6));
+    assert_eq!(torch.properties.displacement, (8, 8).into());
+    assert!(torch.item_properties.is_pickupable);
+    assert_eq!(
+        torch.item_properties.light.map(|light| light.color),
+        Some(206)
+    );
+
+    let animation = torch.sprites_info[0].animation.as_ref().unwrap();
+    assert_eq!(animation.loop_type, LoopType::PingPong);
+    assert_eq!(animation.phases, vec![(100, 100), (200, 300)]);
+
+    let effect = visual_elements
+        .get_for_group_and_id(ContentType::Effect, 1)
+        .unwrap();
+    assert_eq!(effect.main_sprite_id, Some(42));
+}
+
+#[test]
+fn test_definitions_round_trip() {
+    let definitions: AppearanceDefinitions = serde_json::from_str(DEFINITIONS).unwrap();
+    let visual_elements: VisualElements = definitions.into();
+
+    let json = serde_json::to_string(&AppearanceDefinitions::from(&visual_elements)).unwrap();
+    let definitions: AppearanceDefinitions = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(VisualElements::from(definitions), visual_elements);
+}
@@ -0,0 +1,91 @@
+use crate::prelude::*;
+use crate::tests::{element, visual_elements};
+
+fn versions() -> (VisualElements, VisualElements) {
+    let old = visual_elements(
+        vec![
+            element(100, "torch", vec![1]),
+            element(101, "lamp", vec![2]),
+            element(102, "chair", vec![3]),
+        ],
+        vec![element(1, "spark", vec![10])],
+    );
+
+    let mut torch = element(100, "torch", vec![1, 4]);
+    torch.category = Category::Decor;
+    torch.sprites_info[0].animation = Some(Animation {
+        phases: vec![(100, 100)],
+        ..Default::default()
+    });
+
+    let mut lamp = element(101, "lamp", vec![2]);
+    lamp.item_properties.light = Some(Light {
+        brightness: 2,
+        color: 1,
+    });
+
+    let new = visual_elements(
+        vec![torch, lamp, element(103, "table", vec![5])],
+        vec![element(1, "spark", vec![10]), element(2, "smoke", vec![11])],
+    );
+
+    (old, new)
+}
+
+#[test]
+fn test_content_diff() {
+    let (old, new) = versions();
+    let diff = ContentDiff::between(&old, &new);
+
+    assert_eq!(diff.objects.added, vec![103]);
+    assert_eq!(diff.objects.removed, vec![102]);
+    assert_eq!(diff.effects.added, vec![2]);
+    assert!(diff.effects.removed.is_empty() && diff.effects.changed.is_empty());
+    assert!(diff.outfits.is_empty() && diff.missiles.is_empty());
+
+    let [torch, lamp] = diff.objects.changed.as_slice() else {
+        panic!("Expected two changed objects");
+    };
+
+    assert_eq!(
+        torch.category,
+        Some(Change {
+            old: Category::Miscellaneous,
+            new: Category::Decor
+        })
+    );
+    assert_eq!(
+        torch.sprite_ids,
+        Some(Change {
+            old: vec![vec![1]],
+            new: vec![vec![1, 4]]
+        })
+    );
+    assert!(torch.animations.is_some());
+    assert!(torch.flags.is_none() && torch.name.is_none() && !torch.other);
+
+    assert_eq!(lamp.id, 101);
+    assert!(lamp.other);
+    assert!(lamp.sprite_ids.is_none() && lamp.animations.is_none());
+
+    assert!(ContentDiff::between(&old, &old).is_empty());
+}
+
+#[test]
+fn test_content_diff_output() {
+    let (old, new) = versions();
+    let diff = ContentDiff::between(&old, &new);
+
+    let text = diff.to_string();
+    assert!(text.starts_with("Object: 1 added, 1 removed, 2 changed\n  added: 103\n"));
+    assert!(text.contains("  changed 100:\n    category: Miscellaneous -> Decor\n"));
+    assert!(text.contains("    sprite ids: [[1]] -> [[1, 4]]\n    animations changed\n"));
+    assert!(text.contains("  changed 101:\n    other properties changed\n"));
+    assert!(text.contains("Effect: 1 added, 0 removed, 0 changed\n  added: 2\n"));
+    assert_eq!(ContentDiff::default().to_string(), "No changes\n");
+
+    let json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(json["objects"]["added"], serde_json::json!([103]));
+    assert_eq!(json["objects"]["changed"][0]["category"]["new"], "Decor");
+    assert!(json["objects"]["changed"][1].get("flags").is_none());
+}
@@ -0,0 +1,181 @@
+use crate::prelude::*;
+use crate::tests::{element, visual_elements};
+
+const MANIFEST: &str = r#"(
+    name: "winter",
+    dependencies: ["base"],
+    appearances: Some("winter.appearances.ron"),
+    overrides: (
+        objects: [
+            (id: 100, name: Some("frozen torch"), flags: Some((is_walkable: false, blocks_sight: true))),
+            (id: 404, name: Some("missing")),
+        ],
+    ),
+)"#;
+
+fn manifest(name: &str, dependencies: &[&str]) -> ContentPackManifest {
+    ContentPackManifest {
+        name: name.to_string(),
+        dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_layer_adds_and_replaces_elements() {
+    let mut base = visual_elements(
+        vec![
+            element(100, "torch", vec![10]),
+            element(101, "lamp", vec![11]),
+        ],
+        vec![],
+    );
+    base.layer(visual_elements(
+        vec![
+            element(101, "lantern", vec![20]),
+            element(102, "snowman", vec![21]),
+        ],
+        vec![],
+    ));
+
+    let objects = base.get_all_for_group(ContentType::Object).unwrap();
+    assert_eq!(objects.len(), 3);
+    assert_eq!(objects[&100].name, "torch");
+    assert_eq!(objects[&101].name, "lantern");
+    assert_eq!(objects[&101].main_sprite_id, Some(20));
+    assert_eq!(objects[&102].name, "snowman");
+}
+
+#[test]
+fn test_manifest_from_ron() {
+    let manifest: ContentPackManifest = ron::from_str(MANIFEST).unwrap();
+
+    assert_eq!(manifest.name, "winter");
+    assert_eq!(manifest.dependencies, vec!["base".to_string()]);
+    assert_eq!(
+        manifest.appearances.as_deref(),
+        Some("winter.appearances.ron")
+    );
+    assert_eq!(manifest.catalog, None);
+    assert_eq!(manifest.overrides.objects.len(), 2);
+    assert!(manifest.overrides.outfits.is_empty());
+}
+
+#[test]
+fn test_overrides_only_change_the_given_fields() {
+    let manifest: ContentPackManifest = ron::from_str(MANIFEST).unwrap();
+    let mut base = visual_elements(vec![element(100, "torch", vec![10])], vec![]);
+
+    let missing = base.apply_overrides(&manifest.overrides);
+    assert_eq!(missing, vec![(ContentType::Object, 404)]);
+
+    let torch = base.get_for_group_and_id(ContentType::Object, 100).unwrap();
+    assert_eq!(torch.name, "frozen torch");
+    assert_eq!(torch.flags, Flags::new(false, true));
+    assert_eq!(torch.main_sprite_id, Some(10));
+    assert_eq!(torch.sprites_info[0].ids, vec![10]);
+}
+
+#[test]
+fn test_resolve_pack_order() {
+    let packs = [
+        manifest("winter", &["base", "fonts"]),
+        manifest("base", &[]),
+        manifest("event", &["winter"]),
+        manifest("fonts", &[]),
+    ];
+
+    assert_eq!(resolve_pack_order(&packs), Ok(vec![1, 3, 0, 2]));
+    assert_eq!(resolve_pack_order(&packs[1..2]), Ok(vec![0]));
+}
+
+#[test]
+fn test_resolve_pack_order_errors() {
+    assert_eq!(
+        resolve_pack_order(&[manifest("winter", &["base"])]),
+        Err(ContentPackError::MissingDependency {
+            pack: "winter".to_string(),
+            dependency: "base".to_string(),
+        })
+    );
+
+    assert_eq!(
+        resolve_pack_order(&[manifest("base", &[]), manifest("base", &[])]),
+        Err(ContentPackError::Duplicated("base".to_string()))
+    );
+
+    assert_eq!(
+        resolve_pack_order(&[
+            manifest("base", &[]),
+            manifest("a", &["b"]),
+            manifest("b", &["a"]),
+        ]),
+        Err(ContentPackError::CyclicDependency(vec![
+            "a".to_string(),
+            "b".to_string()
+        ]))
+    );
+}
+
+#[test]
+fn test_resolve_available_pack_order_skips_affected_packs() {
+    let packs = [
+        manifest("base", &[]),
+        manifest("winter", &["base", "fonts"]),
+        manifest("event", &["winter"]),
+        manifest("a", &["b"]),
+        manifest("b", &["a"]),
+        manifest("base", &[]),
+        manifest("summer", &["base"]),
+    ];
+
+    let (order, errors) = resolve_available_pack_order(&packs);
+
+    assert_eq!(order, vec![0, 6]);
+    assert_eq!(
+        errors,
+        vec![
+            ContentPackError::Duplicated("base".to_string()),
+            ContentPackError::MissingDependency {
+                pack: "winter".to_string(),
+                dependency: "fonts".to_string(),
+            },
+            ContentPackError::MissingDependency {
+                pack: "event".to_string(),
+                dependency: "winter".to_string(),
+            },
+            ContentPackError::CyclicDependency(vec!["a".to_string(), "b".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn test_content_pack_layers_apply_on_reloaded_content() {
+    let manifest: ContentPackManifest = ron::from_str(MANIFEST).unwrap();
+    let mut layers = ContentPackLayers::default();
+    layers.push(ContentPackLayer {
+        name: manifest.name,
+        visual_elements: Some(visual_elements(
+            vec![element(102, "snowman", vec![21])],
+            vec![],
+        )),
+        overrides: manifest.overrides,
+        sprite_sheets: vec![],
+    });
+
+    let mut reloaded = visual_elements(
+        vec![
+            element(100, "torch", vec![30]),
+            element(101, "lamp", vec![11]),
+        ],
+        vec![],
+    );
+    layers.apply(&mut reloaded);
+
+    let objects = reloaded.get_all_for_group(ContentType::Object).unwrap();
+    assert_eq!(objects.len(), 3);
+    assert_eq!(objects[&100].name, "frozen torch");
+    assert_eq!(objects[&100].sprites_info[0].ids, vec![30]);
+    assert_eq!(objects[&101].name, "lamp");
+    assert_eq!(objects[&102].name, "snowman");
+}
@@ -1,2 +1,31 @@
+use crate::prelude::*;
+
+mod appearance_definitions_test;
+mod content_diff_test;
+mod content_pack_test;
+mod search_test;
 mod sprite_layout_test;
 mod sprite_sheet_tests;
+mod visual_elements_test;
+
+/// A visual element with a single frame group made of the given sprites.
+fn element(id: u32, name: &str, sprite_ids: Vec<u32>) -> VisualElement {
+    VisualElement {
+        id,
+        name: name.to_string(),
+        sprites_info: vec![SpriteInfo {
+            ids: sprite_ids,
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+fn visual_elements(objects: Vec<VisualElement>, effects: Vec<VisualElement>) -> VisualElements {
+    AppearanceDefinitions {
+        objects,
+        effects,
+        ..Default::default()
+    }
+    .into()
+}
@@ -0,0 +1,91 @@
+use crate::prelude::*;
+use crate::tests::element;
+use rstest::rstest;
+
+fn index() -> ContentIndex {
+    let mut torch = element(100, "Torch", vec![100]);
+    torch.sprites_info[0].animation = Some(Animation::default());
+    torch.item_properties.light = Some(Light {
+        brightness: 3,
+        color: 206,
+    });
+
+    let mut wall = element(103, "stone wall", vec![103]);
+    wall.flags = Flags::new(false, true);
+    wall.category = Category::Bottom;
+
+    let definitions = AppearanceDefinitions {
+        objects: vec![
+            torch,
+            element(101, "lit torch", vec![101]),
+            element(102, "street lamp", vec![102]),
+            wall,
+            element(104, "tall ornate rack chest", vec![104]),
+        ],
+        effects: vec![element(100, "torch flames", vec![100])],
+        ..Default::default()
+    };
+
+    (&VisualElements::from(definitions)).into()
+}
+
+#[rstest]
+#[case("torch", vec![ContentId::Object(100), ContentId::Effect(100), ContentId::Object(101)])]
+#[case("torch group:object", vec![ContentId::Object(100), ContentId::Object(101)])]
+#[case("TORCH id:101-", vec![ContentId::Object(101)])]
+#[case("trch", vec![ContentId::Object(100), ContentId::Object(101), ContentId::Effect(100)])]
+#[case("torch !lit", vec![ContentId::Object(100), ContentId::Effect(100)])]
+#[case("!torch group:object", vec![ContentId::Object(102), ContentId::Object(103), ContentId::Object(104)])]
+#[case("tlch", vec![])]
+#[case("102", vec![ContentId::Object(102)])]
+#[case("lamp", vec![ContentId::Object(102)])]
+#[case("id:102-103", vec![ContentId::Object(102), ContentId::Object(103)])]
+#[case("!walkable", vec![ContentId::Object(103)])]
+#[case("sight category:bottom", vec![ContentId::Object(103)])]
+#[case("animated light", vec![ContentId::Object(100)])]
+#[case("!animated group:effect", vec![ContentId::Effect(100)])]
+#[case("dragon", vec![])]
+fn test_search(#[case] query: &str, #[case] expected: Vec<ContentId>) {
+    let query: ContentQuery = query.parse().unwrap();
+    assert_eq!(index().search(&query), expected);
+}
+
+#[test]
+fn test_empty_query_returns_everything_by_id() {
+    let results = index().search(&ContentQuery::default());
+
+    assert_eq!(results.len(), 6);
+    assert_eq!(results.first(), Some(&ContentId::Object(100)));
+    assert_eq!(results.last(), Some(&ContentId::Effect(100)));
+}
+
+#[test]
+fn test_parse_query() {
+    let query: ContentQuery = "stone Wall !Brick id:7 category:17 !sight walkable"
+        .parse()
+        .unwrap();
+
+    assert_eq!(
+        query,
+        ContentQuery::default()
+            .with_text("stone Wall")
+            .without_text("brick")
+            .with_ids(7..=7)
+            .with_category(Category::Custom(17))
+            .with_blocks_sight(false)
+            .with_walkable(true)
+    );
+
+    assert_eq!(
+        "id:a-b".parse::<ContentQuery>(),
+        Err(ContentQueryError::InvalidIds("a-b".to_string()))
+    );
+    assert_eq!(
+        "category:weapons".parse::<ContentQuery>(),
+        Err(ContentQueryError::UnknownCategory("weapons".to_string()))
+    );
+    assert_eq!(
+        "group:items".parse::<ContentQuery>(),
+        Err(ContentQueryError::UnknownGroup("items".to_string()))
+    );
+}
@@ -0,0 +1,47 @@
+use crate::prelude::*;
+use crate::tests::{element, visual_elements};
+
+#[test]
+fn test_diff_visual_elements() {
+    let old = visual_elements(
+        vec![
+            element(100, "torch", vec![100]),
+            element(101, "lamp", vec![101]),
+            element(102, "chair", vec![102]),
+        ],
+        vec![element(1, "spark", vec![1])],
+    );
+    let new = visual_elements(
+        vec![
+            element(100, "torch", vec![100]),
+            element(101, "lantern", vec![101]),
+            element(103, "table", vec![103]),
+        ],
+        vec![element(1, "spark", vec![1]), element(2, "smoke", vec![2])],
+    );
+
+    assert_eq!(
+        old.diff(&new),
+        vec![
+            ContentId::Object(101),
+            ContentId::Object(102),
+            ContentId::Object(103),
+            ContentId::Effect(2),
+        ]
+    );
+    assert_eq!(new.diff(&old), old.diff(&new));
+    assert!(old.diff(&old.clone()).is_empty());
+}
+
+#[test]
+fn test_content_id_from_group_and_id() {
+    for content_id in [
+        ContentId::Object(1),
+        ContentId::Outfit(2),
+        ContentId::Effect(3),
+        ContentId::Missile(4),
+    ] {
+        let (group, id) = content_id.as_group_and_id().unwrap();
+        assert_eq!(ContentId::from((group, id)), content_id);
+    }
+}
@@ -5,7 +5,8 @@ use bevy_ecs::prelude::*;
 use ryot_core::prelude::Navigable;
 use ryot_utils::prelude::*;
 
-/// Represents an App that can add one or more `RayCasting<T, P>` to its systems.
+/// Represents an App that can add one or more `RayCasting<T, P>` and `Projectile<T, P>` to its
+/// systems.
 /// Requires the `SimpleCache<RadialArea, Vec<Vec<P>>>` resource to be initialized.
 pub trait RayCastingApp {
     fn add_ray_casting<
@@ -15,6 +16,14 @@ pub trait RayCastingApp {
     >(
         &mut self,
     ) -> &mut Self;
+
+    fn add_projectiles<
+        Marker: Copy + ThreadSafe,
+        P: RayCastingPoint + Component,
+        N: Navigable + Copy + Default,
+    >(
+        &mut self,
+    ) -> &mut Self;
 }
 
 impl RayCastingApp for App {
@@ -35,6 +44,7 @@ impl RayCastingApp for App {
                         .in_set(RayCastingSystems::Process)
                         .after(CacheSystems::UpdateCache),
                     share_results::<Marker, P>.in_set(RayCastingSystems::Process),
+                    update_fog_of_war::<Marker, P>.in_set(RayCastingSystems::Process),
                 )
                     .chain(),
             )
@@ -49,4 +59,21 @@ impl RayCastingApp for App {
                     .chain(),
             )
     }
+
+    fn add_projectiles<
+        Marker: Copy + ThreadSafe,
+        P: RayCastingPoint + Component,
+        N: Navigable + Copy + Default,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        self.init_resource_once::<Cache<P, N>>()
+            .add_event::<ProjectileHit<Marker, P>>()
+            .add_systems(
+                Update,
+                process_projectiles::<Marker, P, N>
+                    .in_set(RayCastingSystems::Process)
+                    .after(CacheSystems::UpdateCache),
+            )
+    }
 }
@@ -0,0 +1,249 @@
+//! This module introduces the concept of `AreaShape`, a template-based representation of an area
+//! around a center position, like the areas of effect of spells: cones, beams, waves, rings,
+//! crosses and custom patterns in the classic spell-area grid style.
+//!
+//! While [RadialArea] describes an area based on angles and rays, `AreaShape` describes the exact
+//! tiles that are part of the area, facing a given direction. The tiles can be filtered by line of
+//! sight from the center position, using the same conditions used by [RayCasting] requests.
+use crate::prelude::*;
+use ryot_core::prelude::{Navigable, Point};
+use std::collections::VecDeque;
+
+/// The possible shapes of an [AreaShape], described as if the caster was facing north.
+///
+/// The `Pattern` shape follows the classic spell-area grid style: each row of the grid represents
+/// a line of the area, from north to south, where `0` is an unaffected tile, `1` is an affected
+/// tile, `2` is the caster position (not affected) and `3` is the caster position (affected).
+/// A pattern without a caster position is centered on the grid.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Shape {
+    /// A straight line of `length` tiles in front of the caster.
+    Beam { length: u8 },
+    /// A 90 degrees cone in front of the caster, widening by one tile per side every step.
+    Cone { range: u8 },
+    /// A narrow cone in front of the caster, widening by one tile per side every two steps.
+    Wave { range: u8 },
+    /// All the tiles whose distance to the caster is between `inner` and `outer`, inclusive.
+    Ring { inner: u8, outer: u8 },
+    /// Four arms of `range` tiles in the cardinal directions, including the caster position.
+    Cross { range: u8 },
+    /// A custom spell-area grid.
+    Pattern(Vec<Vec<u8>>),
+}
+
+/// The direction an [AreaShape] is facing. Shapes are described facing north and rotated
+/// clockwise by quarter turns to face the other directions.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum ShapeDirection {
+    #[default]
+    North,
+    East,
+    South,
+    West,
+}
+
+impl ShapeDirection {
+    /// Rotates an offset described facing north to face this direction.
+    pub fn rotate(&self, (dx, dy): (i32, i32)) -> (i32, i32) {
+        match self {
+            ShapeDirection::North => (dx, dy),
+            ShapeDirection::East => (dy, -dx),
+            ShapeDirection::South => (-dx, -dy),
+            ShapeDirection::West => (-dy, dx),
+        }
+    }
+}
+
+/// Defines an area of effect from a specific point in the game world, based on a [Shape] template
+/// facing a given direction. It's the tile-exact counterpart of [RadialArea], commonly used to
+/// describe the area of spells and abilities.
+///
+/// An `AreaShape` produces the set of tiles it covers through `get_tiles` and can be filtered by
+/// line of sight from its center through `propagate`, which reuses the condition of a
+/// [RayCasting] request and returns a [RayPropagation] compatible result.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AreaShape<P> {
+    pub shape: Shape,
+    pub center_pos: P,
+    pub direction: ShapeDirection,
+}
+
+impl<P: Point> AreaShape<P> {
+    pub fn new(shape: Shape) -> Self {
+        Self {
+            shape,
+            center_pos: P::generate(0, 0, 0),
+            direction: ShapeDirection::default(),
+        }
+    }
+
+    pub fn beam(length: u8) -> Self {
+        Self::new(Shape::Beam { length })
+    }
+
+    pub fn cone(range: u8) -> Self {
+        Self::new(Shape::Cone { range })
+    }
+
+    pub fn wave(range: u8) -> Self {
+        Self::new(Shape::Wave { range })
+    }
+
+    pub fn ring(inner: u8, outer: u8) -> Self {
+        Self::new(Shape::Ring { inner, outer })
+    }
+
+    pub fn cross(range: u8) -> Self {
+        Self::new(Shape::Cross { range })
+    }
+
+    pub fn pattern(grid: Vec<Vec<u8>>) -> Self {
+        Self::new(Shape::Pattern(grid))
+    }
+
+    pub fn with_center_pos(self, center_pos: P) -> Self {
+        Self { center_pos, ..self }
+    }
+
+    pub fn with_direction(self, direction: impl Into<ShapeDirection>) -> Self {
+        Self {
+            direction: direction.into(),
+            ..self
+        }
+    }
+
+    /// Returns the offsets of the shape relative to the center position, facing north.
+    pub fn get_offsets(&self) -> Vec<(i32, i32)> {
+        match &self.shape {
+            Shape::Beam { length } => (1..=*length as i32).map(|dy| (0, dy)).collect(),
+            Shape::Cone { range } => widening_offsets(*range, |step| step - 1),
+            Shape::Wave { range } => widening_offsets(*range, |step| step / 2),
+            Shape::Ring { inner, outer } => {
+                let outer = *outer as i32;
+
+                (-outer..=outer)
+                    .flat_map(|dy| (-outer..=outer).map(move |dx| (dx, dy)))
+                    .filter(|(dx, dy)| {
+                        let distance = ((dx * dx + dy * dy) as f32).sqrt().round() as i32;
+                        distance >= *inner as i32 && distance <= outer
+                    })
+                    .collect()
+            }
+            Shape::Cross { range } => std::iter::once((0, 0))
+                .chain((1..=*range as i32).flat_map(|d| [(0, d), (d, 0), (0, -d), (-d, 0)]))
+                .collect(),
+            Shape::Pattern(grid) => pattern_offsets(grid),
+        }
+    }
+
+    /// Returns the tiles covered by the shape, rotated to the shape direction and placed around
+    /// the center position.
+    pub fn get_tiles(&self) -> Vec<P> {
+        self.get_offsets()
+            .into_iter()
+            .map(|offset| {
+                let (dx, dy) = self.direction.rotate(offset);
+
+                P::generate(
+                    self.center_pos.x() + dx,
+                    self.center_pos.y() + dy,
+                    self.center_pos.z(),
+                )
+            })
+            .collect()
+    }
+
+    /// Filters the tiles of the shape by line of sight from the center position, using the
+    /// condition and the maximum number of collisions of the given ray casting request.
+    ///
+    /// A ray is traced from the center to each tile of the shape: the tile is part of the area of
+    /// interest if the ray reaches it, and the obstacle that stopped the ray is registered as a
+    /// collision otherwise. As in [`RayCasting::execute`], the obstacles a ray passes through are
+    /// registered as pierced collisions and are part of the area of interest.
+    pub fn propagate<T, N: Navigable>(
+        &self,
+        ray_casting: &RayCasting<T, P>,
+        get_nav_for_position: impl Fn(&P) -> N,
+    ) -> RayPropagation<T, P> {
+        let mut collisions: VecDeque<Collision<T, P>> = VecDeque::new();
+        let mut area_of_interest = VecDeque::new();
+
+        for tile in self.get_tiles() {
+            let mut remaining_collisions = ray_casting.max_collisions;
+            let mut previous_pos = self.center_pos;
+            let mut reached = true;
+
+            for pos in self.center_pos.draw_line_to(tile).into_iter().skip(1) {
+                let collided = !ray_casting.meets_condition(&get_nav_for_position(&pos), &pos);
+
+                if collided {
+                    remaining_collisions -= 1;
+                }
+
+                if collided && !collisions.iter().any(|c| c.position == pos) {
+                    let collision =
+                        Collision::new(pos, self.center_pos.distance_2d(&pos), previous_pos);
+
+                    if remaining_collisions >= 0 {
+                        collisions.push_back(collision.pierced());
+
+                        if !area_of_interest.contains(&pos) {
+                            area_of_interest.push_back(pos);
+                        }
+                    } else {
+                        collisions.push_back(collision);
+                    }
+                }
+
+                if remaining_collisions < 0 {
+                    reached = false;
+                    break;
+                }
+
+                previous_pos = pos;
+            }
+
+            if reached && !area_of_interest.contains(&tile) {
+                area_of_interest.push_back(tile);
+            }
+        }
+
+        RayPropagation::new(collisions, area_of_interest)
+    }
+}
+
+fn widening_offsets(range: u8, half_width: impl Fn(i32) -> i32) -> Vec<(i32, i32)> {
+    (1..=range as i32)
+        .flat_map(|step| {
+            let half_width = half_width(step);
+            (-half_width..=half_width).map(move |dx| (dx, step))
+        })
+        .collect()
+}
+
+fn pattern_offsets(grid: &[Vec<u8>]) -> Vec<(i32, i32)> {
+    let caster = grid
+        .iter()
+        .enumerate()
+        .find_map(|(row, cells)| {
+            cells
+                .iter()
+                .position(|cell| matches!(cell, 2 | 3))
+                .map(|col| (row as i32, col as i32))
+        })
+        .unwrap_or_else(|| {
+            let width = grid.iter().map(Vec::len).max().unwrap_or_default();
+            ((grid.len() / 2) as i32, (width / 2) as i32)
+        });
+
+    grid.iter()
+        .enumerate()
+        .flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .filter(|(_, cell)| matches!(cell, 1 | 3))
+                .map(move |(col, _)| (col as i32 - caster.1, caster.0 - row as i32))
+        })
+        .collect()
+}
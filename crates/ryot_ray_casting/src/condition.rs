@@ -0,0 +1,116 @@
+//! This module contains the building blocks that allow ray casting requests to carry their own
+//! behavior and data: the `NavigationCondition`, that decides whether a ray can go through a given
+//! point, and the `RequestContext`, an arbitrary piece of data attached to a request.
+//!
+//! Both are reference counted and compared by identity, so requests remain cheap to clone and keep
+//! consistent `Eq` and `Hash` semantics, regardless of what the closures capture or the data is.
+use ryot_core::prelude::Navigable;
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+type ConditionFn<R, P> = dyn Fn(&R, &dyn Navigable, &P) -> bool + Send + Sync;
+
+/// Represents the navigation condition of a request R, like a [RayCasting](crate::prelude::RayCasting),
+/// evaluated for each point P that a ray goes through. A point that doesn't meet the condition is
+/// considered a collision.
+///
+/// Conditions are closures, so they can capture any data they need, like the team of the
+/// spectator or a set of ignored obstacles. The default condition is always met.
+pub struct NavigationCondition<R, P>(Option<Arc<ConditionFn<R, P>>>);
+
+impl<R, P> NavigationCondition<R, P> {
+    pub fn new(condition: impl Fn(&R, &dyn Navigable, &P) -> bool + Send + Sync + 'static) -> Self {
+        Self(Some(Arc::new(condition)))
+    }
+
+    pub fn check(&self, request: &R, navigable: &dyn Navigable, position: &P) -> bool {
+        self.0
+            .as_ref()
+            .map_or(true, |condition| condition(request, navigable, position))
+    }
+
+    fn address(&self) -> Option<*const ()> {
+        self.0
+            .as_ref()
+            .map(|condition| Arc::as_ptr(condition) as *const ())
+    }
+}
+
+impl<R, P> Default for NavigationCondition<R, P> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<R, P> Clone for NavigationCondition<R, P> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R, P> Debug for NavigationCondition<R, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("NavigationCondition")
+            .field(&self.address())
+            .finish()
+    }
+}
+
+impl<R, P> PartialEq for NavigationCondition<R, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.address() == other.address()
+    }
+}
+
+impl<R, P> Eq for NavigationCondition<R, P> {}
+
+impl<R, P> Hash for NavigationCondition<R, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address().hash(state);
+    }
+}
+
+/// An arbitrary piece of data that travels with a request, available to its condition. It can be
+/// used to parametrize a shared condition per request, e.g. with the faction of the spectator.
+#[derive(Clone, Default)]
+pub struct RequestContext(Option<Arc<dyn Any + Send + Sync>>);
+
+impl RequestContext {
+    pub fn new<C: Any + Send + Sync>(context: C) -> Self {
+        Self(Some(Arc::new(context)))
+    }
+
+    pub fn get<C: Any>(&self) -> Option<&C> {
+        self.0.as_ref()?.downcast_ref::<C>()
+    }
+
+    fn address(&self) -> Option<*const ()> {
+        self.0
+            .as_ref()
+            .map(|context| Arc::as_ptr(context) as *const ())
+    }
+}
+
+impl Debug for RequestContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RequestContext")
+            .field(&self.address())
+            .finish()
+    }
+}
+
+impl PartialEq for RequestContext {
+    fn eq(&self, other: &Self) -> bool {
+        self.address() == other.address()
+    }
+}
+
+impl Eq for RequestContext {}
+
+impl Hash for RequestContext {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address().hash(state);
+    }
+}
@@ -0,0 +1,137 @@
+//! This module introduces the `FogOfWar` component, a memory of the points that a spectator has
+//! already seen through its ray casting requests. While [RayPropagation] only holds the result of
+//! the last execution, `FogOfWar` accumulates every point that was ever part of the propagation,
+//! separating what is currently visible from what was seen before.
+use crate::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_utils::HashSet;
+use ryot_utils::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// The possible visibility states of a point for a given [FogOfWar], ordered from the most to the
+/// least visible one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Component)]
+pub enum FogState {
+    /// The point is part of the latest ray propagation of the spectator.
+    #[default]
+    Visible,
+    /// The point was seen before, but it's not part of the latest ray propagation.
+    Explored,
+    /// The point was never seen by the spectator.
+    Unexplored,
+}
+
+/// Represents the explored-tile memory of a spectator for a given ray casting context T.
+///
+/// This component is attached to the same entity that holds the `RayPropagation<T, P>` and is
+/// updated every time a new propagation is available. The currently visible points are replaced
+/// on every update, while the explored points are only ever extended, so nothing that was seen
+/// before is forgotten.
+///
+/// Only the explored points are serialized, since the visible ones are recomputed from the next
+/// ray casting execution. This allows the explored area to be persisted in save files.
+#[derive(Debug, Clone, Component, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "P: Serialize + Eq + Hash",
+    deserialize = "P: Deserialize<'de> + Eq + Hash"
+))]
+pub struct FogOfWar<T, P> {
+    #[serde(skip)]
+    visible: HashSet<P>,
+    explored: HashSet<P>,
+    #[serde(skip)]
+    marker: PhantomData<T>,
+}
+
+impl<T, P> Default for FogOfWar<T, P> {
+    fn default() -> Self {
+        Self {
+            visible: HashSet::default(),
+            explored: HashSet::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, P: Eq + Hash + Copy> FogOfWar<T, P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a fog of war from previously explored points, e.g. loaded from a save file.
+    pub fn from_explored(explored: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            explored: explored.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Replaces the currently visible points and adds them to the explored ones.
+    pub fn reveal(&mut self, visible: impl IntoIterator<Item = P>) {
+        self.visible.clear();
+        self.visible.extend(visible);
+        self.explored.extend(self.visible.iter().copied());
+    }
+
+    /// Updates the fog of war from a ray propagation. Both the area of interest and the collision
+    /// points are revealed, since the obstacle that stops a ray is also seen by the spectator.
+    pub fn reveal_propagation(&mut self, propagation: &RayPropagation<T, P>) {
+        self.reveal(
+            propagation
+                .area_of_interest
+                .iter()
+                .copied()
+                .chain(propagation.collisions.iter().map(|c| c.position)),
+        );
+    }
+
+    pub fn state(&self, pos: &P) -> FogState {
+        if self.visible.contains(pos) {
+            FogState::Visible
+        } else if self.explored.contains(pos) {
+            FogState::Explored
+        } else {
+            FogState::Unexplored
+        }
+    }
+
+    pub fn is_visible(&self, pos: &P) -> bool {
+        self.visible.contains(pos)
+    }
+
+    pub fn is_explored(&self, pos: &P) -> bool {
+        self.explored.contains(pos)
+    }
+
+    pub fn visible(&self) -> &HashSet<P> {
+        &self.visible
+    }
+
+    pub fn explored(&self) -> &HashSet<P> {
+        &self.explored
+    }
+
+    /// Forgets everything that was seen, including the currently visible points.
+    pub fn clear(&mut self) {
+        self.visible.clear();
+        self.explored.clear();
+    }
+}
+
+/// Updates the `FogOfWar<T, P>` of every entity whose `RayPropagation<T, P>` changed, revealing
+/// the points of the new propagation and keeping the previously explored ones. A `FogOfWar`
+/// added after the propagation was computed is also revealed right away.
+///
+/// Run as part of [`RayCastingSystems::Process`], after the results are shared.
+pub fn update_fog_of_war<T: Copy + ThreadSafe, P: RayCastingPoint>(
+    mut q_fog: Query<
+        (&RayPropagation<T, P>, &mut FogOfWar<T, P>),
+        Or<(Changed<RayPropagation<T, P>>, Added<FogOfWar<T, P>>)>,
+    >,
+) {
+    q_fog.iter_mut().for_each(|(propagation, mut fog)| {
+        fog.reveal_propagation(propagation);
+    });
+}
@@ -6,10 +6,14 @@ use ryot_core::prelude::Point;
 use ryot_utils::prelude::*;
 
 mod app;
+mod condition;
 mod propagation;
 mod request;
 
+pub mod area_shape;
+pub mod fog_of_war;
 pub mod perspective;
+pub mod projectile;
 pub mod radial_area;
 pub mod systems;
 
@@ -24,7 +28,13 @@ pub trait RayCastingPoint = Point + Into<Aabb3d> + ThreadSafe;
 pub mod prelude {
     pub use crate::{
         app::RayCastingApp,
+        area_shape::{AreaShape, Shape, ShapeDirection},
+        condition::{NavigationCondition, RequestContext},
+        fog_of_war::{update_fog_of_war, FogOfWar, FogState},
         perspective::Perspective,
+        projectile::{
+            process_projectiles, visible_projectile, walkable_projectile, Projectile, ProjectileHit,
+        },
         propagation::{Collision, RayPropagation},
         radial_area::RadialArea,
         request::{visible_ray_casting, walkable_ray_casting, ExecutionType, RayCasting},
@@ -0,0 +1,165 @@
+//! This module introduces projectile requests, a single ray traced from a shooter to a target
+//! that stops at the first obstacle (or after piercing N of them), instead of traversing a whole
+//! radial area as a `RayCasting` request does.
+use crate::prelude::*;
+use bevy_ecs::prelude::*;
+use ryot_core::prelude::{Navigable, Point};
+use ryot_utils::prelude::*;
+use std::any::Any;
+use std::marker::PhantomData;
+
+/// Represents a projectile request for a given ray casting context T, fired from the position of
+/// the entity holding it towards the `target` position.
+///
+/// The projectile walks the straight line between the shooter and the target, checking each
+/// position against the `condition`. Positions that don't meet the condition are obstacles: the
+/// projectile pierces up to `max_collisions` of them and stops at the next one. If no obstacle
+/// stops it, the projectile impacts the intended target.
+///
+/// Like [RayCasting], the condition is a closure and the request can carry an extra
+/// [RequestContext], available to the condition through `context`.
+///
+/// A projectile request is executed once and removed from the entity, emitting a [ProjectileHit].
+#[derive(Debug, Clone, Eq, PartialEq, Component)]
+pub struct Projectile<T, P> {
+    pub target: P,
+    pub max_collisions: i32,
+    pub condition: NavigationCondition<Self, P>,
+    pub context: RequestContext,
+    marker: PhantomData<T>,
+}
+
+/// Event emitted when a projectile request is executed, containing where the projectile was
+/// fired from, where it was intended to go and where it actually impacted.
+///
+/// The `pierced` positions are the obstacles the projectile went through before the impact, and
+/// `blocked` indicates whether the impact happened due to an obstacle or because the projectile
+/// reached its target.
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct ProjectileHit<T, P> {
+    pub shooter: Entity,
+    pub origin: P,
+    pub target: P,
+    pub impact: P,
+    pub pierced: Vec<P>,
+    pub blocked: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T, P: Point> Projectile<T, P> {
+    pub fn new(
+        target: P,
+        condition: impl Fn(&Self, &dyn Navigable, &P) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            target,
+            max_collisions: 0,
+            condition: NavigationCondition::new(condition),
+            context: RequestContext::default(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn with_context<C: Any + Send + Sync>(mut self, context: C) -> Self {
+        self.context = RequestContext::new(context);
+        self
+    }
+
+    pub fn context<C: Any>(&self) -> Option<&C> {
+        self.context.get::<C>()
+    }
+
+    pub fn with_max_collisions(mut self, max_collisions: i32) -> Self {
+        self.max_collisions = max_collisions;
+        self
+    }
+
+    pub fn meets_condition<N: Navigable>(&self, flags: &N, position: &P) -> bool {
+        self.condition.check(self, flags, position)
+    }
+
+    /// Traces the projectile trajectory from `from` to the target, returning the resulting hit.
+    /// The origin position is never considered an obstacle, since it's where the shooter stands.
+    pub fn trace<N: Navigable>(
+        &self,
+        shooter: Entity,
+        from: &P,
+        get_nav_for_position: impl Fn(&P) -> N,
+    ) -> ProjectileHit<T, P> {
+        let mut pierced = Vec::new();
+        let mut remaining_collisions = self.max_collisions;
+
+        for pos in from.draw_line_to(self.target).into_iter().skip(1) {
+            if self.meets_condition(&get_nav_for_position(&pos), &pos) {
+                continue;
+            }
+
+            remaining_collisions -= 1;
+
+            if remaining_collisions < 0 {
+                return ProjectileHit::new(shooter, *from, self.target, pos, pierced, true);
+            }
+
+            pierced.push(pos);
+        }
+
+        ProjectileHit::new(shooter, *from, self.target, self.target, pierced, false)
+    }
+}
+
+impl<T, P> ProjectileHit<T, P> {
+    pub fn new(
+        shooter: Entity,
+        origin: P,
+        target: P,
+        impact: P,
+        pierced: Vec<P>,
+        blocked: bool,
+    ) -> Self {
+        Self {
+            shooter,
+            origin,
+            target,
+            impact,
+            pierced,
+            blocked,
+            marker: PhantomData,
+        }
+    }
+}
+
+pub fn visible_projectile<T: 'static, P: Point + 'static>(target: P) -> Projectile<T, P> {
+    Projectile::<T, P>::new(target, |_, flags, _pos| !flags.blocks_sight())
+}
+
+pub fn walkable_projectile<T: 'static, P: Point + 'static>(target: P) -> Projectile<T, P> {
+    Projectile::<T, P>::new(target, |_, flags, _pos| flags.is_walkable())
+}
+
+/// Executes the projectile requests of every entity with a Projectile component, using the
+/// navigation cache to find the obstacles along the trajectory. Each request emits a
+/// [ProjectileHit] event and is removed from the entity afterwards.
+///
+/// Run as part of [`RayCastingSystems::Process`].
+pub fn process_projectiles<
+    T: Copy + ThreadSafe,
+    P: RayCastingPoint + Component,
+    N: Navigable + Copy + Default,
+>(
+    mut commands: Commands,
+    flags_cache: Res<Cache<P, N>>,
+    q_projectiles: Query<(Entity, &P, &Projectile<T, P>)>,
+    mut hits: EventWriter<ProjectileHit<T, P>>,
+) {
+    let Ok(read_guard) = flags_cache.read() else {
+        return;
+    };
+
+    for (entity, from, projectile) in q_projectiles.iter() {
+        hits.send(projectile.trace(entity, from, |pos| {
+            read_guard.get(pos).copied().unwrap_or_default()
+        }));
+
+        commands.entity(entity).remove::<Projectile<T, P>>();
+    }
+}
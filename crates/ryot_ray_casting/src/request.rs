@@ -2,6 +2,7 @@ use crate::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_utils::HashSet;
 use ryot_core::prelude::{Navigable, Point};
+use std::any::Any;
 use std::collections::VecDeque;
 use std::marker::PhantomData;
 use std::time::{Duration, Instant};
@@ -39,6 +40,11 @@ impl ExecutionType {
 ///
 /// A ray casting request can be executed once, being removed from the system after execution, or
 /// executed periodically based on a time interval.
+///
+/// The navigation condition is a closure, so it can capture the data it needs, and the request can
+/// carry an extra [RequestContext] that is available to the condition through `context`. Both are
+/// compared by identity, so two requests are equal only if they share the same condition and
+/// context instances.
 #[derive(Debug, Clone, Eq, PartialEq, Component)]
 pub struct RayCasting<T, P> {
     pub reversed: bool,
@@ -46,7 +52,8 @@ pub struct RayCasting<T, P> {
     pub area: RadialArea<P>,
     pub shared_with: HashSet<Entity>,
     pub execution_type: ExecutionType,
-    pub condition: fn(&Self, &dyn Navigable, &P) -> bool,
+    pub condition: NavigationCondition<Self, P>,
+    pub context: RequestContext,
     last_executed_at: Option<Instant>,
     marker: PhantomData<T>,
 }
@@ -58,7 +65,8 @@ impl<T, P: Point> Default for RayCasting<T, P> {
             max_collisions: 0,
             area: RadialArea::<P>::default(),
             shared_with: HashSet::default(),
-            condition: |_, _, _| true,
+            condition: NavigationCondition::default(),
+            context: RequestContext::default(),
             execution_type: ExecutionType::Once,
             last_executed_at: None,
             marker: PhantomData,
@@ -67,14 +75,26 @@ impl<T, P: Point> Default for RayCasting<T, P> {
 }
 
 impl<T, P: Point> RayCasting<T, P> {
-    pub fn new(area: RadialArea<P>, condition: fn(&Self, &dyn Navigable, &P) -> bool) -> Self {
+    pub fn new(
+        area: RadialArea<P>,
+        condition: impl Fn(&Self, &dyn Navigable, &P) -> bool + Send + Sync + 'static,
+    ) -> Self {
         Self {
             area,
-            condition,
+            condition: NavigationCondition::new(condition),
             ..Default::default()
         }
     }
 
+    pub fn with_context<C: Any + Send + Sync>(mut self, context: C) -> Self {
+        self.context = RequestContext::new(context);
+        self
+    }
+
+    pub fn context<C: Any>(&self) -> Option<&C> {
+        self.context.get::<C>()
+    }
+
     pub fn can_execute(&self) -> bool {
         match self.execution_type {
             ExecutionType::Once => self.last_executed_at.is_none(),
@@ -117,7 +137,7 @@ impl<T, P: Point> RayCasting<T, P> {
 
 impl<T, P: Copy> RayCasting<T, P> {
     pub fn meets_condition<N: Navigable>(&self, flags: &N, position: &P) -> bool {
-        (self.condition)(self, flags, position)
+        self.condition.check(self, flags, position)
     }
 }
 
@@ -202,10 +222,14 @@ impl<T: Copy, P: RayCastingPoint> RayCasting<T, P> {
     }
 }
 
-pub fn visible_ray_casting<T, P: Point>(area: RadialArea<P>) -> RayCasting<T, P> {
+pub fn visible_ray_casting<T: 'static, P: Point + 'static>(
+    area: RadialArea<P>,
+) -> RayCasting<T, P> {
     RayCasting::<T, P>::new(area, |_, flags, _pos| !flags.blocks_sight())
 }
 
-pub fn walkable_ray_casting<T, P: Point>(area: RadialArea<P>) -> RayCasting<T, P> {
+pub fn walkable_ray_casting<T: 'static, P: Point + 'static>(
+    area: RadialArea<P>,
+) -> RayCasting<T, P> {
     RayCasting::<T, P>::new(area, |_, flags, _pos| flags.is_walkable())
 }
@@ -2,10 +2,24 @@ use bevy_ecs::prelude::Component;
 use bevy_math::bounding::Aabb3d;
 use bevy_math::*;
 use ryot_core::prelude::Point;
+use serde::{Deserialize, Serialize};
 
 /// This is an implementation of ryot_core Point for example purpose.
 /// Pos is a simple 3D point with x, y, and z coordinates.
-#[derive(Eq, PartialEq, Ord, PartialOrd, Component, Default, Clone, Copy, Debug, Hash)]
+#[derive(
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Component,
+    Default,
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
 pub struct Pos(i32, i32, i32);
 
 impl From<Pos> for Vec2 {
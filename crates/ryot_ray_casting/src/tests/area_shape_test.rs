@@ -0,0 +1,79 @@
+use crate::prelude::*;
+use crate::stubs::*;
+use rstest::rstest;
+use ryot_core::prelude::*;
+use std::collections::HashSet;
+
+fn tiles(coordinates: Vec<(i32, i32)>) -> HashSet<Pos> {
+    coordinates
+        .into_iter()
+        .map(|(x, y)| Pos::generate(x, y, 0))
+        .collect()
+}
+
+#[rstest]
+#[case(AreaShape::beam(3), vec![(0, 1), (0, 2), (0, 3)])]
+#[case(AreaShape::beam(2).with_direction(ShapeDirection::East), vec![(1, 0), (2, 0)])]
+#[case(AreaShape::beam(2).with_direction(ShapeDirection::South), vec![(0, -1), (0, -2)])]
+#[case(AreaShape::beam(2).with_direction(ShapeDirection::West), vec![(-1, 0), (-2, 0)])]
+#[case(AreaShape::cone(2), vec![(0, 1), (-1, 2), (0, 2), (1, 2)])]
+#[case(AreaShape::wave(3), vec![(0, 1), (-1, 2), (0, 2), (1, 2), (-1, 3), (0, 3), (1, 3)])]
+#[case(
+    AreaShape::ring(1, 1),
+    vec![(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)]
+)]
+#[case(AreaShape::cross(1), vec![(0, 0), (0, 1), (1, 0), (0, -1), (-1, 0)])]
+#[case(
+    AreaShape::pattern(vec![vec![1, 1, 1], vec![0, 1, 0], vec![0, 2, 0]]),
+    vec![(-1, 2), (0, 2), (1, 2), (0, 1)]
+)]
+#[case(
+    AreaShape::pattern(vec![vec![1, 0, 0], vec![0, 3, 0]]).with_direction(ShapeDirection::East),
+    vec![(1, 1), (0, 0)]
+)]
+fn test_area_shape_tiles(#[case] shape: AreaShape<Pos>, #[case] expected: Vec<(i32, i32)>) {
+    assert_eq!(
+        shape.get_tiles().into_iter().collect::<HashSet<_>>(),
+        tiles(expected)
+    );
+}
+
+#[test]
+fn test_area_shape_tiles_are_placed_around_center() {
+    let shape = AreaShape::beam(1).with_center_pos(Pos::generate(5, 5, 1));
+
+    assert_eq!(shape.get_tiles(), vec![Pos::generate(5, 6, 1)]);
+}
+
+#[test]
+fn test_area_shape_propagation_is_filtered_by_line_of_sight() {
+    let wall = Pos::generate(0, 2, 0);
+    let ray_casting = visible_ray_casting::<(), Pos>(RadialArea::default());
+
+    let propagation =
+        AreaShape::beam(4).propagate(&ray_casting, |pos| Flags::new(true, *pos == wall));
+
+    assert_eq!(
+        propagation.area_of_interest.into_iter().collect::<Vec<_>>(),
+        vec![Pos::generate(0, 1, 0)]
+    );
+    assert_eq!(propagation.collisions.len(), 1);
+    assert_eq!(propagation.collisions[0].position, wall);
+    assert!(!propagation.collisions[0].pierced);
+}
+
+#[test]
+fn test_area_shape_propagation_pierces_collisions() {
+    let wall = Pos::generate(0, 2, 0);
+    let ray_casting = visible_ray_casting::<(), Pos>(RadialArea::default()).with_max_collisions(1);
+
+    let propagation =
+        AreaShape::beam(4).propagate(&ray_casting, |pos| Flags::new(true, *pos == wall));
+
+    assert_eq!(propagation.area_of_interest.len(), 4);
+    assert!(propagation.area_of_interest.contains(&wall));
+    assert_eq!(propagation.collisions.len(), 1);
+    assert_eq!(propagation.collisions[0].position, wall);
+    assert!(propagation.collisions[0].pierced);
+    assert!(propagation.get_collisions_last_positions().is_empty());
+}
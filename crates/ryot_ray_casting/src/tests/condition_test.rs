@@ -0,0 +1,52 @@
+use crate::prelude::*;
+use crate::stubs::*;
+use ryot_core::prelude::*;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Team(u8);
+
+#[test]
+fn condition_should_use_captured_data() {
+    let glass_walls = HashSet::from([Pos::generate(1, 0, 0)]);
+    let ray_casting = RayCasting::<(), Pos>::new(RadialArea::default(), move |_, flags, pos| {
+        !flags.blocks_sight() || glass_walls.contains(pos)
+    });
+
+    let wall = Flags::new(false, true);
+
+    assert!(ray_casting.meets_condition(&wall, &Pos::generate(1, 0, 0)));
+    assert!(!ray_casting.meets_condition(&wall, &Pos::generate(2, 0, 0)));
+}
+
+#[test]
+fn condition_should_read_request_context() {
+    let condition = |ray_casting: &RayCasting<(), Pos>, flags: &dyn Navigable, pos: &Pos| {
+        (ray_casting.context::<Team>() == Some(&Team(1)) && pos.x() == 1) || flags.is_walkable()
+    };
+
+    let team_1 = RayCasting::new(RadialArea::default(), condition).with_context(Team(1));
+    let team_2 = RayCasting::new(RadialArea::default(), condition).with_context(Team(2));
+
+    let blocked = Flags::new(false, false);
+
+    assert!(team_1.meets_condition(&blocked, &Pos::generate(1, 0, 0)));
+    assert!(!team_2.meets_condition(&blocked, &Pos::generate(1, 0, 0)));
+    assert_eq!(team_1.context::<Team>(), Some(&Team(1)));
+    assert_eq!(team_1.context::<u32>(), None);
+}
+
+#[test]
+fn requests_should_be_compared_by_condition_and_context_identity() {
+    let ray_casting = visible_ray_casting::<(), Pos>(RadialArea::default()).with_context(Team(1));
+
+    assert_eq!(ray_casting, ray_casting.clone());
+    assert_ne!(
+        ray_casting,
+        visible_ray_casting::<(), Pos>(RadialArea::default()).with_context(Team(1))
+    );
+    assert_eq!(
+        RayCasting::<(), Pos>::default(),
+        RayCasting::<(), Pos>::default()
+    );
+}
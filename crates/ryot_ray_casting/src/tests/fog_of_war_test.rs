@@ -0,0 +1,78 @@
+use crate::prelude::*;
+use crate::stubs::*;
+use bevy_ecs::prelude::*;
+use ryot_core::prelude::*;
+use std::collections::VecDeque;
+
+fn propagation(area_of_interest: Vec<Pos>, collisions: Vec<Pos>) -> RayPropagation<(), Pos> {
+    RayPropagation::new(
+        collisions
+            .into_iter()
+            .map(|pos| Collision::new(pos, 1., pos))
+            .collect(),
+        VecDeque::from(area_of_interest),
+    )
+}
+
+#[test]
+fn fog_of_war_should_remember_explored_positions() {
+    let mut fog = FogOfWar::<(), Pos>::default();
+
+    fog.reveal_propagation(&propagation(
+        vec![Pos::generate(0, 0, 0), Pos::generate(1, 0, 0)],
+        vec![],
+    ));
+    fog.reveal_propagation(&propagation(vec![Pos::generate(0, 1, 0)], vec![]));
+
+    assert_eq!(fog.state(&Pos::generate(0, 1, 0)), FogState::Visible);
+    assert_eq!(fog.state(&Pos::generate(0, 0, 0)), FogState::Explored);
+    assert_eq!(fog.state(&Pos::generate(1, 0, 0)), FogState::Explored);
+    assert_eq!(fog.state(&Pos::generate(5, 5, 0)), FogState::Unexplored);
+    assert_eq!(fog.visible().len(), 1);
+    assert_eq!(fog.explored().len(), 3);
+}
+
+#[test]
+fn fog_of_war_should_reveal_collisions() {
+    let mut fog = FogOfWar::<(), Pos>::default();
+
+    fog.reveal_propagation(&propagation(
+        vec![Pos::generate(0, 0, 0)],
+        vec![Pos::generate(1, 0, 0)],
+    ));
+
+    assert!(fog.is_visible(&Pos::generate(1, 0, 0)));
+    assert!(fog.is_explored(&Pos::generate(1, 0, 0)));
+}
+
+#[test]
+fn fog_of_war_should_serialize_only_explored_positions() {
+    let mut fog = FogOfWar::<(), Pos>::from_explored(vec![Pos::generate(3, 3, 0)]);
+    fog.reveal(vec![Pos::generate(0, 0, 0)]);
+
+    let serialized = serde_json::to_string(&fog).unwrap();
+    let deserialized: FogOfWar<(), Pos> = serde_json::from_str(&serialized).unwrap();
+
+    assert!(deserialized.visible().is_empty());
+    assert_eq!(deserialized.explored(), fog.explored());
+}
+
+#[test]
+fn fog_of_war_added_after_propagation_should_be_revealed() {
+    let mut world = World::new();
+    let mut schedule = Schedule::default();
+    schedule.add_systems(update_fog_of_war::<(), Pos>);
+
+    let spectator = world
+        .spawn(propagation(vec![Pos::generate(0, 0, 0)], vec![]))
+        .id();
+    schedule.run(&mut world);
+
+    world
+        .entity_mut(spectator)
+        .insert(FogOfWar::<(), Pos>::default());
+    schedule.run(&mut world);
+
+    let fog = world.get::<FogOfWar<(), Pos>>(spectator).unwrap();
+    assert!(fog.is_visible(&Pos::generate(0, 0, 0)));
+}
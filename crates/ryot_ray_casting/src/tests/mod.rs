@@ -3,6 +3,10 @@ use crate::stubs::Pos;
 use derive_more::{Deref, DerefMut};
 use ryot_core::prelude::Point;
 
+mod area_shape_test;
+mod condition_test;
+mod fog_of_war_test;
+mod projectile_test;
 mod traversal_test;
 
 impl quickcheck::Arbitrary for RadialArea<Pos> {
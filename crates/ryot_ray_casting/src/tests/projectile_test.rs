@@ -0,0 +1,48 @@
+use crate::prelude::*;
+use crate::stubs::*;
+use bevy_ecs::entity::Entity;
+use rstest::rstest;
+use ryot_core::prelude::*;
+
+fn flags_for(obstacles: &[Pos]) -> impl Fn(&Pos) -> Flags + '_ {
+    |pos| Flags::new(!obstacles.contains(pos), false)
+}
+
+#[rstest]
+#[case(0, vec![], Pos::generate(5, 0, 0), false, vec![])]
+#[case(0, vec![Pos::generate(3, 0, 0)], Pos::generate(3, 0, 0), true, vec![])]
+#[case(
+    1,
+    vec![Pos::generate(2, 0, 0), Pos::generate(4, 0, 0)],
+    Pos::generate(4, 0, 0),
+    true,
+    vec![Pos::generate(2, 0, 0)]
+)]
+#[case(
+    5,
+    vec![Pos::generate(2, 0, 0), Pos::generate(4, 0, 0)],
+    Pos::generate(5, 0, 0),
+    false,
+    vec![Pos::generate(2, 0, 0), Pos::generate(4, 0, 0)]
+)]
+#[case(0, vec![Pos::generate(0, 0, 0)], Pos::generate(5, 0, 0), false, vec![])]
+fn test_projectile_trace(
+    #[case] max_collisions: i32,
+    #[case] obstacles: Vec<Pos>,
+    #[case] expected_impact: Pos,
+    #[case] expected_blocked: bool,
+    #[case] expected_pierced: Vec<Pos>,
+) {
+    let origin = Pos::generate(0, 0, 0);
+    let target = Pos::generate(5, 0, 0);
+
+    let hit = walkable_projectile::<(), Pos>(target)
+        .with_max_collisions(max_collisions)
+        .trace(Entity::PLACEHOLDER, &origin, flags_for(&obstacles));
+
+    assert_eq!(hit.origin, origin);
+    assert_eq!(hit.target, target);
+    assert_eq!(hit.impact, expected_impact);
+    assert_eq!(hit.blocked, expected_blocked);
+    assert_eq!(hit.pierced, expected_pierced);
+}
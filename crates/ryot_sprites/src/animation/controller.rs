@@ -0,0 +1,69 @@
+use bevy_ecs::component::Component;
+
+/// A component that controls the animation of a single entity, allowing gameplay code to pause,
+/// resume, seek, speed up, slow down or reverse it.
+///
+/// Synchronized animations share their state with every other entity of the same appearance,
+/// so a controlled synchronized entity detaches from them into its own state, starting from the
+/// current shared phase.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct AnimationController {
+    pub paused: bool,
+    pub speed: f32,
+    pub reversed: bool,
+    seek: Option<usize>,
+}
+
+impl Default for AnimationController {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            speed: 1.,
+            reversed: false,
+            seek: None,
+        }
+    }
+}
+
+impl AnimationController {
+    pub fn with_speed(self, speed: f32) -> Self {
+        Self { speed, ..self }
+    }
+
+    pub fn with_reversed(self, reversed: bool) -> Self {
+        Self { reversed, ..self }
+    }
+
+    pub fn with_paused(self, paused: bool) -> Self {
+        Self { paused, ..self }
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn reverse(&mut self) {
+        self.reversed = !self.reversed;
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    /// Jumps to the given phase in the next tick, even if the animation is paused.
+    pub fn seek(&mut self, phase: usize) {
+        self.seek = Some(phase);
+    }
+
+    pub(crate) fn has_pending_seek(&self) -> bool {
+        self.seek.is_some()
+    }
+
+    pub(crate) fn take_seek(&mut self) -> Option<usize> {
+        self.seek.take()
+    }
+}
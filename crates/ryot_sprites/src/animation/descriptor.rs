@@ -1,10 +1,12 @@
 use crate::prelude::*;
 use bevy_ecs::component::Component;
+use ryot_core::prelude::LoopType;
 
 #[derive(Debug, Clone)]
 pub struct AnimationDescriptor {
     pub sprites: Vec<LoadedSprite>,
     pub layers: usize,
+    pub pattern: PatternDimensions,
     pub skip: usize,
     pub synchronized: bool,
 }
@@ -23,8 +25,10 @@ pub enum AnimationSprite {
 }
 
 impl AnimationSprite {
+    /// Creates the animation of an appearance. Counted animations are always independent, even if
+    /// synchronized, so each entity plays its own loops and gets its own `AnimationFinished`.
     pub fn from_key_and_descriptor(key: &AnimationKey, descriptor: &AnimationDescriptor) -> Self {
-        if descriptor.synchronized {
+        if descriptor.synchronized && key.loop_type != LoopType::Counted {
             AnimationSprite::Synchronized {
                 key: key.clone(),
                 descriptor: descriptor.clone(),
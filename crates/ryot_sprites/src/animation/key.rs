@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use bevy_time::{Timer, TimerMode};
 use rand::Rng;
-use ryot_core::prelude::Animation;
+use ryot_core::prelude::{Animation, LoopType};
 use std::time::Duration;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -9,6 +9,8 @@ pub struct AnimationKey {
     pub phase_durations: Vec<Duration>,
     pub start_phase: AnimationStartPhase,
     pub total_phases: usize,
+    pub loop_type: LoopType,
+    pub loop_count: u32,
 }
 
 impl AnimationKey {
@@ -23,6 +25,43 @@ impl AnimationKey {
             .unwrap_or(Duration::from_millis(300))
     }
 
+    /// Returns the phase that follows the current one, according to the loop type of the
+    /// animation, or None if the animation is over and must hold its current phase.
+    ///
+    /// A reversed playback mirrors the phases, so it goes from the last phase to the first one.
+    pub(crate) fn next_phase(&self, state: &mut AnimationState, reversed: bool) -> Option<usize> {
+        let last_phase = self.total_phases.saturating_sub(1);
+        let mirror = |phase: usize| {
+            if reversed {
+                last_phase.saturating_sub(phase)
+            } else {
+                phase
+            }
+        };
+        let current_phase = mirror(state.current_phase);
+
+        let next_phase = match self.loop_type {
+            LoopType::Infinite if current_phase >= last_phase => Some(0),
+            LoopType::Counted if current_phase >= last_phase => {
+                state.completed_loops += 1;
+                (state.completed_loops < self.loop_count.max(1)).then_some(0)
+            }
+            LoopType::PingPong if last_phase == 0 => Some(0),
+            LoopType::PingPong if state.bouncing && current_phase == 0 => {
+                state.bouncing = false;
+                Some(1)
+            }
+            LoopType::PingPong if state.bouncing => Some(current_phase - 1),
+            LoopType::PingPong if current_phase >= last_phase => {
+                state.bouncing = true;
+                Some(last_phase - 1)
+            }
+            _ => Some(current_phase + 1),
+        };
+
+        next_phase.map(mirror)
+    }
+
     pub fn default_state(&self) -> AnimationState {
         let current_phase = self.start_phase.get(self.total_phases);
         AnimationState::new(current_phase, self.create_timer(current_phase))
@@ -54,6 +93,8 @@ impl SpriteAnimationExt for Animation {
                 false => AnimationStartPhase::Fixed(self.start_phase as usize),
             },
             total_phases: self.phases.len(),
+            loop_type: self.loop_type,
+            loop_count: self.loop_count,
         }
     }
 }
@@ -1,3 +1,4 @@
+pub mod controller;
 pub mod descriptor;
 pub mod key;
 pub mod state;
@@ -23,7 +23,11 @@ impl AnimationStartPhase {
 pub struct AnimationState {
     pub timer: Timer,
     pub current_phase: usize,
+    pub(crate) bouncing: bool,
+    pub(crate) completed_loops: u32,
     just_finished: bool,
+    finished: bool,
+    just_ended: bool,
 }
 
 impl AnimationState {
@@ -31,25 +35,66 @@ impl AnimationState {
         Self {
             timer,
             current_phase,
+            bouncing: false,
+            completed_loops: 0,
             just_finished: false,
+            finished: false,
+            just_ended: false,
         }
     }
 
     pub(crate) fn tick(&mut self, key: &AnimationKey, delta: Duration) {
-        self.timer.tick(delta);
+        self.advance(key, delta, false);
+    }
+
+    /// Ticks the animation, playing it backwards if reversed.
+    pub(crate) fn advance(&mut self, key: &AnimationKey, delta: Duration, reversed: bool) {
         self.just_finished = false;
+        self.just_ended = false;
+
+        if self.finished {
+            return;
+        }
+
+        self.timer.tick(delta);
         if self.timer.just_finished() {
-            self.current_phase += 1;
-            if self.current_phase >= key.total_phases {
-                self.current_phase = 0;
-            }
+            let Some(next_phase) = key.next_phase(self, reversed) else {
+                self.finished = true;
+                self.just_ended = true;
+                return;
+            };
+
+            self.current_phase = next_phase;
             self.timer.set_duration(key.duration(self.current_phase));
             self.timer.reset();
             self.just_finished = true;
         }
     }
 
+    /// Jumps to the given phase, restarting its timer and the bounce direction of ping-pong loops.
+    /// A finished animation starts playing again.
+    pub(crate) fn seek(&mut self, key: &AnimationKey, phase: usize) {
+        self.current_phase = phase.min(key.total_phases.saturating_sub(1));
+        self.timer.set_duration(key.duration(self.current_phase));
+        self.timer.reset();
+        self.completed_loops = 0;
+        self.bouncing = false;
+        self.finished = false;
+        self.just_finished = true;
+    }
+
+    /// Whether the current phase just changed, and the sprite must be updated.
     pub(crate) fn just_finished(&self) -> bool {
         self.just_finished
     }
+
+    /// Whether the animation is over, holding its last phase. Only counted animations end.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Whether the animation ended in the last tick.
+    pub fn just_ended(&self) -> bool {
+        self.just_ended
+    }
 }
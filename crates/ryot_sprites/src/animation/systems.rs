@@ -1,10 +1,8 @@
 use crate::prelude::*;
-use crate::update::{sprite_material_from_params, ChangingAppearanceFilter};
+use crate::update::{mask_sprite, sprite_material_from_params, ChangingAppearanceFilter};
 use bevy_asset::{Assets, Handle};
-use bevy_ecs::change_detection::{Res, ResMut};
-use bevy_ecs::component::Component;
-use bevy_ecs::entity::Entity;
-use bevy_ecs::prelude::{Commands, Query, Resource, SystemSet};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::DespawnRecursiveExt;
 use bevy_time::Time;
 use bevy_utils::tracing::warn;
 use bevy_utils::HashMap;
@@ -27,15 +25,27 @@ pub struct AnimationDuration(pub Duration);
 #[derive(Resource, Debug, Default, Deref, DerefMut)]
 pub struct SynchronizedAnimationTimers(HashMap<AnimationKey, AnimationState>);
 
+/// Initializes the `AnimationSprite` of the entities whose appearance changed. Entities with an
+/// `AnimationController` keep their current state if the animation is still the same, so a
+/// direction change doesn't throw away a paused or seeked animation.
+#[allow(clippy::type_complexity)]
 pub fn initialize_animation_sprite_system(
     mut commands: Commands,
-    q_maybe_animated: Query<(Entity, &ContentId, Option<&FrameGroup>), ChangingAppearanceFilter>,
+    q_maybe_animated: Query<
+        (
+            Entity,
+            &ContentId,
+            Option<&FrameGroup>,
+            Option<&AnimationSprite>,
+            Has<AnimationController>,
+        ),
+        ChangingAppearanceFilter,
+    >,
     loaded_appereances: Res<LoadedAppearances>,
     mut synced_timers: ResMut<SynchronizedAnimationTimers>,
 ) {
-    q_maybe_animated
-        .iter()
-        .for_each(|(entity, object_id, frame_group)| {
+    q_maybe_animated.iter().for_each(
+        |(entity, object_id, frame_group, current_animation, is_controlled)| {
             let Some(loaded_appearance) =
                 loaded_appereances.get(&(*object_id, frame_group.cloned().unwrap_or_default()))
             else {
@@ -46,32 +56,82 @@ pub fn initialize_animation_sprite_system(
                 commands.entity(entity).remove::<AnimationSprite>();
                 return;
             };
-            commands
-                .entity(entity)
-                .insert(AnimationSprite::from_key_and_descriptor(key, descriptor));
 
-            if descriptor.synchronized {
+            if let (
+                true,
+                Some(AnimationSprite::Independent {
+                    key: current_key,
+                    state,
+                    ..
+                }),
+            ) = (is_controlled, current_animation)
+            {
+                if current_key == key {
+                    commands.entity(entity).insert(AnimationSprite::Independent {
+                        key: key.clone(),
+                        descriptor: descriptor.clone(),
+                        state: state.clone(),
+                    });
+                    return;
+                }
+            }
+
+            let animation = AnimationSprite::from_key_and_descriptor(key, descriptor);
+
+            if matches!(animation, AnimationSprite::Synchronized { .. }) {
                 synced_timers
                     .try_insert(key.clone(), key.default_state())
                     .ok();
             }
-        });
+
+            commands.entity(entity).insert(animation);
+        },
+    );
+}
+
+/// An event sent when the animation of an entity ends, after playing all its counted loops.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnimationFinished(pub Entity);
+
+/// Marks an entity to be despawned once its animation ends, like one-shot effects.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct DespawnOnAnimationFinished;
+
+/// A system that despawns the entities marked with `DespawnOnAnimationFinished` whose
+/// animation just ended.
+pub fn despawn_finished_animations_system(
+    mut commands: Commands,
+    mut finished_events: EventReader<AnimationFinished>,
+    q_despawnable: Query<(), With<DespawnOnAnimationFinished>>,
+) {
+    for AnimationFinished(entity) in finished_events.read() {
+        if q_despawnable.contains(*entity) {
+            commands.entity(*entity).despawn_recursive();
+        }
+    }
 }
 
 /// A system that animates the sprites based on the `AnimationSprite` component.
 /// It's meant to run every frame to update the animation of the entities.
 /// It will only run if the entity has a `TextureAtlas` and an `AnimationSprite` component.
+#[allow(clippy::type_complexity)]
 pub fn tick_animation_system(
     time: Res<Time>,
     mut synced_timers: ResMut<SynchronizedAnimationTimers>,
     mut q_sprites: Query<(
+        Entity,
         &mut Handle<SpriteMaterial>,
         &mut AnimationSprite,
         Option<&Directional>,
+        Option<&SpritePattern>,
         Option<&SpriteParams>,
+        Option<&OutfitColors>,
         Option<&AnimationDuration>,
+        Option<&mut AnimationController>,
+        Has<PlaceholderSprite>,
     )>,
     mut materials: ResMut<Assets<SpriteMaterial>>,
+    mut finished_events: EventWriter<AnimationFinished>,
 ) {
     let delta = time.delta();
     synced_timers
@@ -79,7 +139,33 @@ pub fn tick_animation_system(
         .for_each(|(key, state)| state.tick(key, delta));
 
     q_sprites.iter_mut().for_each(
-        |(mut material, mut anim, direction, sprite_params, duration)| {
+        |(
+            entity,
+            mut material,
+            mut anim,
+            direction,
+            pattern,
+            sprite_params,
+            outfit_colors,
+            duration,
+            mut controller,
+            is_placeholder,
+        )| {
+            if let (AnimationSprite::Synchronized { key, descriptor }, Some(_)) =
+                (anim.as_ref(), &controller)
+            {
+                let state = synced_timers
+                    .get(key)
+                    .cloned()
+                    .unwrap_or_else(|| key.default_state());
+
+                *anim = AnimationSprite::Independent {
+                    key: key.clone(),
+                    descriptor: descriptor.clone(),
+                    state,
+                };
+            }
+
             if let AnimationSprite::Independent { key, state, .. } = &mut *anim {
                 if let Some(duration) = duration {
                     let frame_duration = duration.0 / key.total_phases as u32;
@@ -87,7 +173,25 @@ pub fn tick_animation_system(
                         state.timer.set_duration(frame_duration)
                     }
                 }
-                state.tick(key, delta);
+
+                match controller.as_deref() {
+                    Some(controller) => {
+                        let delta = match controller.paused {
+                            true => Duration::ZERO,
+                            false => delta.mul_f32(controller.speed.max(0.)),
+                        };
+                        state.advance(key, delta, controller.reversed);
+                    }
+                    None => state.tick(key, delta),
+                }
+
+                // Only borrow the controller mutably when there is a seek to take, so it is not
+                // flagged as changed every frame.
+                if controller.as_ref().is_some_and(|c| c.has_pending_seek()) {
+                    if let Some(phase) = controller.as_mut().and_then(|c| c.take_seek()) {
+                        state.seek(key, phase);
+                    }
+                }
             }
 
             let (state, descriptor) = match anim.as_ref() {
@@ -102,18 +206,26 @@ pub fn tick_animation_system(
                 }
             };
 
-            if state.just_finished() {
-                let direction_index = match direction {
-                    Some(dir) => dir.index(),
-                    None => 0,
-                } * descriptor.layers;
-                let Some(sprite) = descriptor
-                    .sprites
-                    .get(direction_index + state.current_phase * descriptor.skip)
-                else {
+            if state.just_ended() {
+                finished_events.send(AnimationFinished(entity));
+            }
+
+            if state.just_finished() && !is_placeholder {
+                let direction_index =
+                    descriptor
+                        .pattern
+                        .sprite_index(descriptor.layers, direction, pattern);
+                let sprite_index = direction_index + state.current_phase * descriptor.skip;
+                let Some(sprite) = descriptor.sprites.get(sprite_index) else {
                     return;
                 };
-                *material = sprite_material_from_params(sprite_params, &mut materials, sprite);
+                *material = sprite_material_from_params(
+                    sprite_params,
+                    outfit_colors,
+                    &mut materials,
+                    sprite,
+                    mask_sprite(&descriptor.sprites, sprite_index, descriptor.layers),
+                );
             }
         },
     );
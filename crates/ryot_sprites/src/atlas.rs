@@ -0,0 +1,465 @@
+//! Runtime texture atlases.
+//!
+//! Sprites are loaded from sprite sheets, each of them a whole PNG with hundreds of sprites,
+//! even though only a few of them are usually drawn. A busy screen therefore binds many sheet
+//! textures, one for each sheet touched by the visible sprites.
+//!
+//! The sprites actually used are copied into a few large atlas pages, and their materials are
+//! rewritten to sample the atlas instead of the sheet. Since the atlas is a grid aligned to the
+//! size of each sprite, the materials keep using the same index and counts representation.
+//! Sprites that are no longer used by any entity are evicted, freeing their space in the atlas.
+use crate::prelude::*;
+use bevy_asset::{AssetId, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_render::prelude::Image;
+use bevy_render::render_asset::RenderAssetUsages;
+use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_render::texture::TextureFormatPixelInfo;
+use bevy_time::{Time, Timer, TimerMode};
+use bevy_utils::tracing::warn;
+use bevy_utils::{HashMap, HashSet};
+use glam::{UVec2, Vec2};
+use ryot_core::prelude::*;
+use ryot_tiled::tile_size;
+use std::time::Duration;
+
+/// A page of the sprite atlas: a texture split in a grid of tile sized cells, where each sprite
+/// takes one or more cells, according to its layout.
+#[derive(Debug, Clone)]
+pub struct AtlasPage {
+    pub texture: Handle<Image>,
+    pub format: TextureFormat,
+    cells: UVec2,
+    occupied: Vec<bool>,
+}
+
+impl AtlasPage {
+    pub fn new(texture: Handle<Image>, format: TextureFormat, cells: UVec2) -> Self {
+        Self {
+            texture,
+            format,
+            cells,
+            occupied: vec![false; (cells.x * cells.y) as usize],
+        }
+    }
+
+    /// Reserves a free region of the given size, in cells, returning the cell of its top left
+    /// corner. Regions are aligned to their own size, so every sprite of the same layout can be
+    /// addressed by a single index within the page.
+    pub fn allocate(&mut self, size: UVec2) -> Option<UVec2> {
+        let cell = (0..self.cells.y / size.y)
+            .flat_map(|row| (0..self.cells.x / size.x).map(move |column| (column, row)))
+            .map(|(column, row)| UVec2::new(column, row) * size)
+            .find(|cell| self.is_free(*cell, size))?;
+
+        self.mark(cell, size, true);
+        Some(cell)
+    }
+
+    /// Releases the region of the given size, in cells, starting at the given cell.
+    pub fn free(&mut self, cell: UVec2, size: UVec2) {
+        self.mark(cell, size, false);
+    }
+
+    /// The number of cells in use.
+    pub fn used_cells(&self) -> usize {
+        self.occupied.iter().filter(|occupied| **occupied).count()
+    }
+
+    fn region(&self, cell: UVec2, size: UVec2) -> impl Iterator<Item = usize> + '_ {
+        (cell.y..cell.y + size.y).flat_map(move |y| {
+            (cell.x..cell.x + size.x).map(move |x| (y * self.cells.x + x) as usize)
+        })
+    }
+
+    fn is_free(&self, cell: UVec2, size: UVec2) -> bool {
+        self.region(cell, size).all(|index| !self.occupied[index])
+    }
+
+    fn mark(&mut self, cell: UVec2, size: UVec2, occupied: bool) {
+        for index in self.region(cell, size).collect::<Vec<_>>() {
+            self.occupied[index] = occupied;
+        }
+    }
+}
+
+/// The position of a sprite within the atlas, along with where it was taken from, so its
+/// materials can be restored to the sprite sheet once it's evicted.
+///
+/// A sprite can be drawn with more than one material, one for each batch of appearances it was
+/// loaded with, so the slot keeps every material it rewrote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtlasSlot {
+    pub page: usize,
+    pub cell: UVec2,
+    pub size: UVec2,
+    pub index: u32,
+    pub counts: Vec2,
+    materials: HashSet<AssetId<SpriteMaterial>>,
+    sheet_texture: Handle<Image>,
+    sheet_index: u32,
+    sheet_counts: Vec2,
+}
+
+impl AtlasSlot {
+    pub fn materials(&self) -> &HashSet<AssetId<SpriteMaterial>> {
+        &self.materials
+    }
+
+    /// Rewrites the material to sample the atlas, unless it already does.
+    fn attach(
+        &mut self,
+        texture: &Handle<Image>,
+        material: &Handle<SpriteMaterial>,
+        materials: &mut Assets<SpriteMaterial>,
+    ) {
+        if !self.materials.insert(material.id()) {
+            return;
+        }
+
+        if let Some(material) = materials.get_mut(material) {
+            material.texture = texture.clone();
+            material.index = self.index;
+            material.counts = self.counts;
+        }
+    }
+}
+
+/// The atlas pages, and the slots of the sprites packed into them.
+///
+/// Sprites are packed as soon as their sprite sheet texture is loaded, while there is room left
+/// in the pages. Once all the pages are full, the remaining sprites keep using their sheets, and
+/// no packing is attempted until an eviction frees some space.
+#[derive(Resource, Debug, Clone)]
+pub struct SpriteAtlases {
+    pub page_size: u32,
+    pub max_pages: usize,
+    pub eviction_timer: Timer,
+    pages: Vec<AtlasPage>,
+    slots: HashMap<u32, AtlasSlot>,
+    unpackable: HashSet<u32>,
+    full: bool,
+}
+
+impl Default for SpriteAtlases {
+    fn default() -> Self {
+        Self {
+            page_size: 2048,
+            max_pages: 8,
+            eviction_timer: Timer::new(Duration::from_secs(5), TimerMode::Repeating),
+            pages: vec![],
+            slots: HashMap::default(),
+            unpackable: HashSet::default(),
+            full: false,
+        }
+    }
+}
+
+impl SpriteAtlases {
+    pub fn with_page_size(self, page_size: u32) -> Self {
+        Self { page_size, ..self }
+    }
+
+    pub fn with_max_pages(self, max_pages: usize) -> Self {
+        Self { max_pages, ..self }
+    }
+
+    pub fn with_eviction_interval(self, interval: Duration) -> Self {
+        Self {
+            eviction_timer: Timer::new(interval, TimerMode::Repeating),
+            ..self
+        }
+    }
+
+    pub fn pages(&self) -> &[AtlasPage] {
+        &self.pages
+    }
+
+    pub fn slot(&self, sprite_id: u32) -> Option<&AtlasSlot> {
+        self.slots.get(&sprite_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Whether a sprite didn't fit in the atlas since the last eviction.
+    pub fn is_full(&self) -> bool {
+        self.full
+    }
+
+    /// The memory used by the atlas pages, in bytes.
+    pub fn memory(&self) -> usize {
+        self.pages
+            .iter()
+            .map(|page| (self.page_size * self.page_size) as usize * page.format.pixel_size())
+            .sum()
+    }
+
+    /// Releases the slots of the sprites that don't match the predicate, without restoring their
+    /// materials. It's meant for sprites that are no longer loaded at all.
+    pub(crate) fn release_unless(&mut self, keep: impl Fn(u32) -> bool) {
+        let released = self
+            .slots
+            .keys()
+            .copied()
+            .filter(|sprite_id| !keep(*sprite_id))
+            .collect::<Vec<_>>();
+
+        for sprite_id in released {
+            if let Some(slot) = self.slots.remove(&sprite_id) {
+                if let Some(page) = self.pages.get_mut(slot.page) {
+                    page.free(slot.cell, slot.size);
+                }
+                self.full = false;
+            }
+        }
+    }
+
+    fn allocate(
+        &mut self,
+        format: TextureFormat,
+        size: UVec2,
+        images: &mut Assets<Image>,
+    ) -> Option<(usize, UVec2)> {
+        let allocated = self
+            .pages
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, page)| page.format == format)
+            .find_map(|(index, page)| page.allocate(size).map(|cell| (index, cell)));
+
+        if allocated.is_some() || self.pages.len() >= self.max_pages {
+            return allocated;
+        }
+
+        let texture = images.add(Image::new_fill(
+            Extent3d {
+                width: self.page_size,
+                height: self.page_size,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &vec![0; format.pixel_size()],
+            format,
+            RenderAssetUsages::default(),
+        ));
+
+        let mut page = AtlasPage::new(texture, format, UVec2::splat(self.page_size) / tile_size());
+        let cell = page.allocate(size)?;
+        self.pages.push(page);
+
+        Some((self.pages.len() - 1, cell))
+    }
+
+    /// Copies the sprite from its sheet into the atlas, rewriting its material to sample the
+    /// atlas. Returns false if the sprite can't be packed, either because its sheet is not
+    /// loaded yet or because the atlas is full.
+    ///
+    /// The space is allocated before the sprite is copied, so nothing is read from the sheet
+    /// once the atlas is full.
+    fn pack(
+        &mut self,
+        sprite: &LoadedSprite,
+        images: &mut Assets<Image>,
+        materials: &mut Assets<SpriteMaterial>,
+    ) -> bool {
+        if self.full {
+            return false;
+        }
+
+        let Some(format) = images
+            .get(&sprite.texture)
+            .map(|sheet| sheet.texture_descriptor.format)
+        else {
+            return false;
+        };
+        let Some(material) = materials.get(&sprite.material) else {
+            return false;
+        };
+
+        let sprite_size = sprite.sprite_sheet.get_tile_size(&tile_size());
+        let sheet_index = material.index;
+        let sheet_counts = material.counts;
+        let source = sprite_cell(sheet_index, sheet_counts) * sprite_size;
+
+        let size = sprite_size / tile_size();
+        let Some((page, cell)) = self.allocate(format, size, images) else {
+            self.full = true;
+            return false;
+        };
+
+        let pixels = images
+            .get(&sprite.texture)
+            .and_then(|sheet| read_pixels(sheet, source, sprite_size));
+        let Some(pixels) = pixels else {
+            warn!("Sprite {} is out of its sprite sheet", sprite.sprite_id);
+            self.unpackable.insert(sprite.sprite_id);
+            self.pages[page].free(cell, size);
+            return false;
+        };
+
+        let Some(atlas) = images.get_mut(&self.pages[page].texture) else {
+            self.pages[page].free(cell, size);
+            return false;
+        };
+
+        write_pixels(atlas, cell * tile_size(), sprite_size, &pixels);
+
+        let counts = (UVec2::splat(self.page_size) / sprite_size).as_vec2();
+        let index = (cell.y / size.y) * counts.x as u32 + cell.x / size.x;
+
+        let mut slot = AtlasSlot {
+            page,
+            cell,
+            size,
+            index,
+            counts,
+            materials: HashSet::default(),
+            sheet_texture: sprite.texture.clone(),
+            sheet_index,
+            sheet_counts,
+        };
+        slot.attach(&self.pages[page].texture, &sprite.material, materials);
+        self.slots.insert(sprite.sprite_id, slot);
+
+        true
+    }
+
+    /// Rewrites the material of an already packed sprite, like the one of a later load of the
+    /// same sprite, to sample the atlas. Returns false if the sprite is not packed.
+    fn attach(&mut self, sprite: &LoadedSprite, materials: &mut Assets<SpriteMaterial>) -> bool {
+        let Some(slot) = self.slots.get_mut(&sprite.sprite_id) else {
+            return false;
+        };
+
+        slot.attach(&self.pages[slot.page].texture, &sprite.material, materials);
+        true
+    }
+
+    /// Releases the slot of the sprite, restoring all its materials to the sprite sheet.
+    fn evict(&mut self, sprite_id: u32, materials: &mut Assets<SpriteMaterial>) {
+        let Some(slot) = self.slots.remove(&sprite_id) else {
+            return;
+        };
+
+        if let Some(page) = self.pages.get_mut(slot.page) {
+            page.free(slot.cell, slot.size);
+        }
+        self.full = false;
+
+        for material in &slot.materials {
+            if let Some(material) = materials.get_mut(*material) {
+                material.texture = slot.sheet_texture.clone();
+                material.index = slot.sheet_index;
+                material.counts = slot.sheet_counts;
+            }
+        }
+    }
+}
+
+fn read_pixels(image: &Image, position: UVec2, size: UVec2) -> Option<Vec<u8>> {
+    let pixel_size = image.texture_descriptor.format.pixel_size();
+    let row_size = image.width() as usize * pixel_size;
+    let sprite_row_size = size.x as usize * pixel_size;
+
+    if position.x + size.x > image.width() || position.y + size.y > image.height() {
+        return None;
+    }
+
+    (position.y..position.y + size.y)
+        .map(|y| {
+            let start = y as usize * row_size + position.x as usize * pixel_size;
+            image.data.get(start..start + sprite_row_size)
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|rows| rows.concat())
+}
+
+fn write_pixels(image: &mut Image, position: UVec2, size: UVec2, pixels: &[u8]) {
+    let pixel_size = image.texture_descriptor.format.pixel_size();
+    let row_size = image.width() as usize * pixel_size;
+    let sprite_row_size = size.x as usize * pixel_size;
+
+    for (row, source) in pixels.chunks_exact(sprite_row_size).enumerate() {
+        let start = (position.y as usize + row) * row_size + position.x as usize * pixel_size;
+        image.data[start..start + sprite_row_size].copy_from_slice(source);
+    }
+}
+
+/// The ids of the sprites of the appearances used by the given entities.
+fn used_sprite_ids(
+    loaded_appearances: &LoadedAppearances,
+    q_contents: &Query<(&ContentId, Option<&FrameGroup>)>,
+) -> HashSet<u32> {
+    q_contents
+        .iter()
+        .map(|(content_id, frame_group)| (*content_id, frame_group.copied().unwrap_or_default()))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .filter_map(|key| loaded_appearances.get(&key))
+        .flat_map(|appearance| appearance.sprites.iter().map(|sprite| sprite.sprite_id))
+        .collect()
+}
+
+/// A system that packs the sprites used by the entities into the atlas, as soon as their sprite
+/// sheets are loaded. Sprites that don't fit in the atlas keep using their sprite sheets.
+///
+/// Only sprites in use are packed, so the ones evicted by [evict_unused_atlas_sprites_system]
+/// are not copied back until an entity uses them again. Every material of a packed sprite is
+/// rewritten, including the ones of appearances loaded after it was packed. While the atlas is
+/// full, only the materials of the sprites already packed are rewritten.
+pub fn pack_sprite_atlas_system(
+    mut atlases: ResMut<SpriteAtlases>,
+    loaded_appearances: Res<LoadedAppearances>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<SpriteMaterial>>,
+    q_contents: Query<(&ContentId, Option<&FrameGroup>)>,
+) {
+    let used = used_sprite_ids(&loaded_appearances, &q_contents);
+
+    let pending = loaded_appearances
+        .values()
+        .flat_map(|appearance| appearance.sprites.iter())
+        .filter(|sprite| {
+            used.contains(&sprite.sprite_id) && !atlases.unpackable.contains(&sprite.sprite_id)
+        })
+        .collect::<Vec<_>>();
+
+    for sprite in pending {
+        if !atlases.attach(sprite, &mut materials) {
+            atlases.pack(sprite, &mut images, &mut materials);
+        }
+    }
+}
+
+/// A system that periodically evicts the sprites that are not used by any entity from the
+/// atlas, so their space can be reused by other sprites.
+pub fn evict_unused_atlas_sprites_system(
+    time: Res<Time>,
+    mut atlases: ResMut<SpriteAtlases>,
+    loaded_appearances: Res<LoadedAppearances>,
+    mut materials: ResMut<Assets<SpriteMaterial>>,
+    q_contents: Query<(&ContentId, Option<&FrameGroup>)>,
+) {
+    if !atlases.eviction_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let used = used_sprite_ids(&loaded_appearances, &q_contents);
+
+    let unused = atlases
+        .slots
+        .keys()
+        .copied()
+        .filter(|sprite_id| !used.contains(sprite_id))
+        .collect::<Vec<_>>();
+
+    for sprite_id in unused {
+        atlases.evict(sprite_id, &mut materials);
+    }
+}
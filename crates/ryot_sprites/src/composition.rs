@@ -0,0 +1,261 @@
+//! Outfit composition: addons and mounts.
+//!
+//! An outfit is drawn with a single sprite, but addons and mounts are separate sprites drawn
+//! together with it. Addons are extra rows of the outfit pattern, drawn on top of the outfit,
+//! while mounts are a different appearance, drawn below it. The outfit itself uses the mounted
+//! depth of its pattern while riding.
+//!
+//! Each addon and mount is composed as a child entity of the outfit, an [OutfitPart], that follows
+//! the direction, frame group, colours and animation phase of its owner.
+use crate::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::prelude::*;
+use bevy_sprite::MaterialMesh2dBundle;
+use bevy_transform::prelude::Transform;
+use bevy_utils::{default, HashSet};
+use glam::{Vec2, Vec3};
+use ryot_core::prelude::*;
+use ryot_tiled::prelude::*;
+use std::ops::BitOr;
+
+/// The distance between the parts of an outfit in the z axis. It's small enough to keep all the
+/// parts within the same layer order of their owner, so they are sorted as a single sprite.
+const OUTFIT_PART_Z_STEP: f32 = 1e-5;
+
+/// The addons worn with an outfit, as a bitmask where each bit enables one of the addon rows of
+/// the outfit pattern: the first bit is the first addon (row 1), the second bit is the second
+/// addon (row 2), and so on.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct OutfitAddons(pub u8);
+
+impl OutfitAddons {
+    pub const NONE: Self = Self(0);
+    pub const FIRST: Self = Self(1);
+    pub const SECOND: Self = Self(1 << 1);
+    pub const ALL: Self = Self(Self::FIRST.0 | Self::SECOND.0);
+
+    pub fn contains(&self, addons: OutfitAddons) -> bool {
+        self.0 & addons.0 == addons.0
+    }
+
+    /// Returns the pattern rows of the worn addons, in the order they are drawn.
+    pub fn rows(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..u8::BITS)
+            .filter(|bit| self.0 & (1 << bit) != 0)
+            .map(|bit| bit + 1)
+    }
+}
+
+impl BitOr for OutfitAddons {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Marks an outfit as riding the mount with the given content id.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mounted(pub ContentId);
+
+/// A sprite composed as part of an outfit, spawned as a child of the outfit entity.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutfitPart {
+    Mount,
+    Addon(u32),
+}
+
+impl OutfitPart {
+    /// The offset of the part relative to its owner in the z axis: the mount is drawn below the
+    /// outfit, and the addons are drawn above it, in the order of their rows.
+    pub fn z_offset(&self) -> f32 {
+        match self {
+            OutfitPart::Mount => -OUTFIT_PART_Z_STEP,
+            OutfitPart::Addon(row) => *row as f32 * OUTFIT_PART_Z_STEP,
+        }
+    }
+}
+
+fn outfit_part_bundle(
+    part: OutfitPart,
+    content_id: ContentId,
+    pattern: SpritePattern,
+) -> impl Bundle {
+    (
+        part,
+        content_id,
+        pattern,
+        Directional::default(),
+        FrameGroup::default(),
+        SpriteLayout::default(),
+        MaterialMesh2dBundle::<SpriteMaterial> {
+            transform: Transform::from_translation(Vec3::Z * part.z_offset()),
+            ..default()
+        },
+    )
+}
+
+/// A system that composes the parts of the outfits whose addons, mount or content changed.
+/// The previous parts are despawned, and the outfit pattern is updated to its mounted state.
+/// Only entities with addons or a mount, or that just lost them, are composed, so the pattern of
+/// other content, like map tiles, is left to [resolve_sprite_variant_system].
+#[allow(clippy::type_complexity)]
+pub fn compose_outfit_system(
+    mut commands: Commands,
+    q_changed: Query<
+        Entity,
+        (
+            With<ContentId>,
+            Without<OutfitPart>,
+            Or<(With<OutfitAddons>, With<Mounted>)>,
+            Or<(Changed<OutfitAddons>, Changed<Mounted>, Changed<ContentId>)>,
+        ),
+    >,
+    q_outfits: Query<
+        (
+            &ContentId,
+            Option<&OutfitAddons>,
+            Option<&Mounted>,
+            Option<&OutfitColors>,
+            Option<&SpritePattern>,
+            Option<&Children>,
+        ),
+        Without<OutfitPart>,
+    >,
+    q_parts: Query<(), With<OutfitPart>>,
+    mut removed_addons: RemovedComponents<OutfitAddons>,
+    mut removed_mounts: RemovedComponents<Mounted>,
+) {
+    let outfits = q_changed
+        .iter()
+        .chain(removed_addons.read())
+        .chain(removed_mounts.read())
+        .collect::<HashSet<_>>();
+
+    for entity in outfits {
+        let Ok((content_id, addons, mounted, colors, pattern, children)) = q_outfits.get(entity)
+        else {
+            continue;
+        };
+
+        children
+            .into_iter()
+            .flatten()
+            .filter(|child| q_parts.contains(**child))
+            .for_each(|child| commands.entity(*child).despawn_recursive());
+
+        let depth = mounted.is_some() as u32;
+
+        if pattern.map_or(0, |pattern| pattern.z) != depth {
+            let pattern = pattern.copied().unwrap_or_default();
+            commands.entity(entity).insert(SpritePattern {
+                z: depth,
+                ..pattern
+            });
+        }
+
+        if addons.is_none() && mounted.is_none() {
+            continue;
+        }
+
+        commands.entity(entity).with_children(|builder| {
+            if let Some(Mounted(mount)) = mounted {
+                builder.spawn(outfit_part_bundle(
+                    OutfitPart::Mount,
+                    *mount,
+                    SpritePattern::default(),
+                ));
+            }
+
+            for row in addons.iter().flat_map(|addons| addons.rows()) {
+                let mut addon = builder.spawn(outfit_part_bundle(
+                    OutfitPart::Addon(row),
+                    *content_id,
+                    SpritePattern::new(0, row, depth),
+                ));
+
+                if let Some(colors) = colors {
+                    addon.insert(*colors);
+                }
+            }
+        });
+    }
+}
+
+/// A system that keeps the parts of the outfits in sync with their owners: they face the same
+/// direction, use the same frame group and colours, share the same animation phase and are
+/// aligned to the owner sprite, even if their layouts are different.
+///
+/// It's meant to run before the sprites are updated and the animations are ticked, so the parts
+/// are updated and animated in the same frame as their owners.
+#[allow(clippy::type_complexity)]
+pub fn sync_outfit_parts_system(
+    mut commands: Commands,
+    q_owners: Query<
+        (
+            Option<&Directional>,
+            Option<&FrameGroup>,
+            Option<&OutfitColors>,
+            Option<&SpriteLayout>,
+            Option<&AnimationSprite>,
+        ),
+        Without<OutfitPart>,
+    >,
+    mut q_parts: Query<(
+        Entity,
+        &OutfitPart,
+        &Parent,
+        &SpriteLayout,
+        Option<&OutfitColors>,
+        &mut Directional,
+        &mut FrameGroup,
+        &mut Transform,
+        Option<&mut AnimationSprite>,
+    )>,
+) {
+    for (
+        entity,
+        part,
+        parent,
+        layout,
+        colors,
+        mut direction,
+        mut frame_group,
+        mut transform,
+        anim,
+    ) in &mut q_parts
+    {
+        let Ok((owner_direction, owner_frame_group, owner_colors, owner_layout, owner_anim)) =
+            q_owners.get(parent.get())
+        else {
+            continue;
+        };
+
+        direction.set_if_neq(owner_direction.copied().unwrap_or_default());
+        frame_group.set_if_neq(owner_frame_group.copied().unwrap_or_default());
+
+        if matches!(part, OutfitPart::Addon(_)) && colors != owner_colors {
+            match owner_colors {
+                Some(owner_colors) => commands.entity(entity).insert(*owner_colors),
+                None => commands.entity(entity).remove::<OutfitColors>(),
+            };
+        }
+
+        let owner_size = owner_layout
+            .copied()
+            .unwrap_or_default()
+            .get_size(&tile_size());
+        let offset =
+            (owner_size.as_vec2() - layout.get_size(&tile_size()).as_vec2()) * Vec2::new(0.5, -0.5);
+        transform.set_if_neq(Transform::from_translation(offset.extend(part.z_offset())));
+
+        if let (Some(AnimationSprite::Independent { state: owner, .. }), Some(mut anim)) =
+            (owner_anim, anim)
+        {
+            if let AnimationSprite::Independent { key, state, .. } = anim.as_mut() {
+                *state = owner.clone();
+                state.current_phase %= key.total_phases.max(1);
+            }
+        }
+    }
+}
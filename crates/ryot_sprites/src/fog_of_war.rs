@@ -0,0 +1,170 @@
+//! Applies the fog of war of the spectators to the rendering of the map sprites.
+use crate::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_render::color::Color;
+use ryot_core::prelude::ContentId;
+use ryot_ray_casting::prelude::{FogOfWar, FogState};
+use ryot_tiled::prelude::*;
+use ryot_utils::prelude::*;
+
+/// Defines how the sprites are rendered according to their fog state.
+/// Remembered (explored) tiles are dimmed using the `explored_tint` and unexplored tiles are
+/// rendered with the `unexplored_alpha`, which hides them by default.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct FogOfWarRendering {
+    pub explored_tint: Color,
+    pub unexplored_alpha: f32,
+}
+
+impl Default for FogOfWarRendering {
+    fn default() -> Self {
+        Self {
+            explored_tint: Color::rgba(0.4, 0.4, 0.4, 1.),
+            unexplored_alpha: 0.,
+        }
+    }
+}
+
+impl FogOfWarRendering {
+    /// Applies the fog state on top of the tint and alpha defined by the user, returning what
+    /// the fog applied so it can be undone later. Visible sprites get their own values back.
+    fn apply(
+        &self,
+        state: FogState,
+        params: &mut SpriteParams,
+        fogged: Option<&FoggedSprite>,
+    ) -> Option<FoggedSprite> {
+        let (tint, alpha) = match fogged {
+            Some(fogged) => fogged.own_values(params),
+            None => (params.tint, params.alpha),
+        };
+
+        match state {
+            FogState::Visible => {
+                params.tint = tint;
+                params.alpha = alpha;
+                return None;
+            }
+            FogState::Explored => {
+                params.tint = Some(tint.map_or(self.explored_tint, |tint| {
+                    multiply_colors(tint, self.explored_tint)
+                }));
+                params.alpha = alpha;
+            }
+            FogState::Unexplored => {
+                params.tint = tint;
+                params.alpha = Some(alpha.unwrap_or(1.) * self.unexplored_alpha);
+            }
+        }
+
+        Some(FoggedSprite {
+            tint,
+            alpha,
+            applied_tint: params.tint,
+            applied_alpha: params.alpha,
+        })
+    }
+}
+
+fn multiply_colors(a: Color, b: Color) -> Color {
+    let [ar, ag, ab, aa] = a.as_rgba_f32();
+    let [br, bg, bb, ba] = b.as_rgba_f32();
+    Color::rgba(ar * br, ag * bg, ab * bb, aa * ba)
+}
+
+/// Remembers the tint and alpha of a sprite before the fog of war was applied to it, together
+/// with the values the fog applied. Once the sprite is visible again, only the values that are
+/// still the ones applied by the fog are restored, so changes made in the meantime are kept.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct FoggedSprite {
+    tint: Option<Color>,
+    alpha: Option<f32>,
+    applied_tint: Option<Color>,
+    applied_alpha: Option<f32>,
+}
+
+impl FoggedSprite {
+    fn own_values(&self, params: &SpriteParams) -> (Option<Color>, Option<f32>) {
+        let tint = if params.tint == self.applied_tint {
+            self.tint
+        } else {
+            params.tint
+        };
+
+        let alpha = if params.alpha == self.applied_alpha {
+            self.alpha
+        } else {
+            params.alpha
+        };
+
+        (tint, alpha)
+    }
+}
+
+/// Dims the sprites of remembered tiles and hides the unseen ones, based on the `FogOfWar` of
+/// all the spectators of a given ray casting context T. A tile is visible if any spectator sees
+/// it, and explored if any spectator has seen it before.
+///
+/// The fog state applied to each entity is tracked by the `FogState` component, so the sprite
+/// params are only touched when the state changes. The tint and alpha set by the user are kept
+/// in `FoggedSprite` while the fog is applied. Hud entities are never affected by the fog.
+pub fn apply_fog_of_war_to_sprites<T: Copy + ThreadSafe>(
+    mut commands: Commands,
+    rendering: Res<FogOfWarRendering>,
+    q_fog: Query<Ref<FogOfWar<T, TilePosition>>>,
+    mut q_sprites: Query<
+        (
+            Entity,
+            Ref<TilePosition>,
+            Option<&Layer>,
+            Option<&FogState>,
+            Option<&FoggedSprite>,
+            Option<&mut SpriteParams>,
+        ),
+        With<ContentId>,
+    >,
+) {
+    if q_fog.is_empty() {
+        return;
+    }
+
+    let fog_changed = q_fog.iter().any(|fog| fog.is_changed());
+
+    for (entity, tile_pos, layer, current_state, fogged, params) in q_sprites.iter_mut() {
+        if !fog_changed && !tile_pos.is_changed() {
+            continue;
+        }
+
+        if matches!(layer, Some(Layer::Hud(_))) {
+            continue;
+        }
+
+        let state = q_fog
+            .iter()
+            .map(|fog| fog.state(&tile_pos))
+            .min()
+            .unwrap_or(FogState::Unexplored);
+
+        if current_state == Some(&state) {
+            continue;
+        }
+
+        let fogged = match params {
+            Some(mut params) => rendering.apply(state, &mut params, fogged),
+            None if state != FogState::Visible => {
+                let mut params = SpriteParams::default();
+                let fogged = rendering.apply(state, &mut params, None);
+                commands.entity(entity).insert(params);
+                fogged
+            }
+            None => None,
+        };
+
+        match fogged {
+            Some(fogged) => commands.entity(entity).insert(fogged),
+            None => commands.entity(entity).remove::<FoggedSprite>(),
+        };
+
+        commands.entity(entity).insert(state);
+    }
+}
@@ -0,0 +1,61 @@
+//! Instanced sprite rendering.
+//!
+//! Every sprite is usually drawn as its own material mesh, which is flexible but doesn't scale to
+//! the hundreds of thousands of tiles of a map level. Entities marked with [InstancedSprite] are
+//! drawn by an instanced renderer instead: their materials are turned into per-instance data and
+//! consecutive sprites sharing the same texture, ideally an atlas page, are drawn with a single
+//! draw call.
+//!
+//! The ECS-facing API is unchanged: instanced entities are still spawned with their `ContentId`,
+//! `TilePosition` and `Layer`, and their sprites, animations and parameters are updated just like
+//! any other sprite. Only their meshes are detached, so they are not drawn twice.
+use crate::prelude::*;
+use bevy_app::App;
+use bevy_asset::{embedded_asset, Handle};
+use bevy_ecs::prelude::*;
+use bevy_sprite::Mesh2dHandle;
+use ryot_core::prelude::*;
+
+pub mod render;
+
+/// Marks an entity to be drawn by the instanced sprite renderer, instead of its own material mesh.
+/// It's meant for static and numerous content, like map tiles.
+///
+/// Instanced sprites are sorted by z and drawn in batches of consecutive sprites sharing the same
+/// texture. A batch is also split wherever a sprite drawn by a material mesh lies in between its
+/// sprites, so the instanced sprites and the material meshes are drawn in back to front order.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct InstancedSprite;
+
+pub fn embed_instanced_sprite_assets(app: &mut App) {
+    embedded_asset!(app, "shaders/instanced_sprite.wgsl");
+}
+
+/// A system that detaches the meshes of the instanced sprites, so they are only drawn by the
+/// instanced renderer. It must run after the sprites are updated, since updating a sprite also
+/// updates its mesh.
+pub fn detach_instanced_sprite_meshes(
+    mut q_instanced: Query<&mut Mesh2dHandle, (With<InstancedSprite>, Changed<Mesh2dHandle>)>,
+) {
+    for mut mesh in &mut q_instanced {
+        mesh.set_if_neq(Mesh2dHandle(Handle::default()));
+    }
+}
+
+/// A system that restores the meshes of the sprites that are no longer instanced, according to
+/// their sprite layout.
+pub fn restore_instanced_sprite_meshes(
+    sprite_meshes: Res<SpriteMeshes>,
+    mut removed: RemovedComponents<InstancedSprite>,
+    mut q_meshes: Query<(&SpriteLayout, &mut Mesh2dHandle), Without<InstancedSprite>>,
+) {
+    for entity in removed.read() {
+        let Ok((layout, mut mesh)) = q_meshes.get_mut(entity) else {
+            continue;
+        };
+
+        if let Some(sprite_mesh) = sprite_meshes.get(layout) {
+            *mesh = Mesh2dHandle(sprite_mesh.clone());
+        }
+    }
+}
@@ -0,0 +1,484 @@
+//! The render world side of the instanced sprite renderer.
+//!
+//! The visible instanced sprites are sorted by z and extracted into batches of consecutive sprites
+//! sharing the same texture. The instances of all the batches are uploaded to a single
+//! per-instance vertex buffer, which unlike storage buffers is also available on WebGL2, and each
+//! batch is drawn on the transparent 2d phase with a single indexed draw call over its range.
+//!
+//! The vertex buffer and the texture bind groups are kept between frames: the buffer is only
+//! written when the instances change, and a bind group is only created when its texture does.
+use crate::prelude::*;
+use bevy_asset::{AssetId, AssetServer, Assets, Handle};
+use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::lifetimeless::SRes;
+use bevy_ecs::system::SystemParamItem;
+use bevy_render::prelude::*;
+use bevy_render::render_asset::RenderAssets;
+use bevy_render::render_phase::{
+    DrawFunctions, PhaseItem, RenderCommand, RenderCommandResult, RenderPhase, SetItemPipeline,
+    TrackedRenderPass,
+};
+use bevy_render::render_resource::binding_types::{sampler, texture_2d};
+use bevy_render::render_resource::*;
+use bevy_render::renderer::{RenderDevice, RenderQueue};
+use bevy_render::texture::BevyDefault;
+use bevy_render::view::{ExtractedView, ViewTarget};
+use bevy_render::Extract;
+use bevy_sprite::{Mesh2dPipeline, Mesh2dPipelineKey, SetMesh2dViewBindGroup};
+use bevy_transform::prelude::GlobalTransform;
+use bevy_utils::{FloatOrd, HashMap, HashSet};
+use bytemuck::{Pod, Zeroable};
+use ryot_core::prelude::*;
+use ryot_tiled::tile_size;
+use std::ops::Range;
+
+const INSTANCED_SPRITE_SHADER: &str =
+    "embedded://ryot_sprites/instancing/shaders/instanced_sprite.wgsl";
+
+/// The data of a single sprite, as read by the instanced sprite shader. It mirrors the uniforms
+/// of the [SpriteMaterial] used by the sprite, along with its position and size.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct SpriteInstance {
+    pub translation: [f32; 3],
+    pub size: [f32; 2],
+    pub index: u32,
+    pub counts: [f32; 2],
+    pub tint: [f32; 4],
+    pub outline_color: [f32; 4],
+    pub alpha: f32,
+    pub outline_thickness: f32,
+}
+
+impl SpriteInstance {
+    /// The instance of a sprite with the given transform, layout and material. Like the material
+    /// meshes, the instance is twice the size of the sprite, to leave room for the outline.
+    pub fn new(
+        transform: &GlobalTransform,
+        layout: &SpriteLayout,
+        material: &SpriteMaterial,
+    ) -> Self {
+        let transform = transform.compute_transform();
+        let size = layout.get_size(&tile_size()).as_vec2() * 2. * transform.scale.truncate();
+
+        Self {
+            translation: transform.translation.to_array(),
+            size: size.to_array(),
+            index: material.index,
+            counts: material.counts.to_array(),
+            tint: material.tint.as_linear_rgba_f32(),
+            outline_color: material.outline_color.as_linear_rgba_f32(),
+            alpha: material.alpha,
+            outline_thickness: material.outline_thickness,
+        }
+    }
+
+    fn vertex_buffer_layout() -> VertexBufferLayout {
+        VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Instance,
+            [
+                VertexFormat::Float32x3,
+                VertexFormat::Float32x2,
+                VertexFormat::Uint32,
+                VertexFormat::Float32x2,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32,
+                VertexFormat::Float32,
+            ],
+        )
+    }
+}
+
+/// Consecutive sprites, in z order, sharing the same texture. Spawned in the render world every
+/// frame.
+#[derive(Component, Debug, Clone)]
+pub struct InstancedSpriteBatch {
+    pub texture: AssetId<Image>,
+    pub instances: Vec<SpriteInstance>,
+}
+
+impl InstancedSpriteBatch {
+    /// The z of the lowest sprite of the batch, used to sort it within the transparent phase.
+    pub fn z(&self) -> f32 {
+        self.instances
+            .first()
+            .map_or(0., |instance| instance.translation[2])
+    }
+}
+
+/// Sorts the instances by z and groups them in batches, starting a new batch whenever the texture
+/// changes or a boundary is crossed.
+///
+/// The boundaries are the z of the sprites drawn outside of the batches, like material meshes.
+/// Since a batch is drawn at once, splitting it at those z keeps every sprite in back to front
+/// order, no matter how the z ranges of the textures and of the other sprites overlap.
+pub fn batch_sprite_instances(
+    instances: impl IntoIterator<Item = (AssetId<Image>, SpriteInstance)>,
+    boundaries: impl IntoIterator<Item = f32>,
+) -> Vec<InstancedSpriteBatch> {
+    let mut instances = instances.into_iter().collect::<Vec<_>>();
+    instances.sort_by_key(|(_, instance)| FloatOrd(instance.translation[2]));
+
+    let mut boundaries = boundaries.into_iter().map(FloatOrd).collect::<Vec<_>>();
+    boundaries.sort();
+    let mut boundaries = boundaries.into_iter().peekable();
+
+    let mut batches: Vec<InstancedSpriteBatch> = vec![];
+
+    for (texture, instance) in instances {
+        let z = FloatOrd(instance.translation[2]);
+        let mut crossed_boundary = false;
+
+        while boundaries.next_if(|boundary| *boundary <= z).is_some() {
+            crossed_boundary = true;
+        }
+
+        match batches.last_mut() {
+            Some(batch) if batch.texture == texture && !crossed_boundary => {
+                batch.instances.push(instance)
+            }
+            _ => batches.push(InstancedSpriteBatch {
+                texture,
+                instances: vec![instance],
+            }),
+        }
+    }
+
+    batches
+}
+
+/// Extracts the visible instanced sprites into batches, split at the z of the visible sprites
+/// drawn as material meshes.
+#[allow(clippy::type_complexity)]
+pub fn extract_instanced_sprites(
+    mut commands: Commands,
+    materials: Extract<Res<Assets<SpriteMaterial>>>,
+    q_instanced: Extract<
+        Query<
+            (
+                &ViewVisibility,
+                &GlobalTransform,
+                &SpriteLayout,
+                &Handle<SpriteMaterial>,
+            ),
+            With<InstancedSprite>,
+        >,
+    >,
+    q_meshes: Extract<
+        Query<
+            (&ViewVisibility, &GlobalTransform),
+            (With<Handle<SpriteMaterial>>, Without<InstancedSprite>),
+        >,
+    >,
+) {
+    let instances = q_instanced
+        .iter()
+        .filter(|(visibility, ..)| visibility.get())
+        .filter_map(|(_, transform, layout, material)| {
+            let material = materials.get(material)?;
+            Some((
+                material.texture.id(),
+                SpriteInstance::new(transform, layout, material),
+            ))
+        });
+
+    let boundaries = q_meshes
+        .iter()
+        .filter(|(visibility, _)| visibility.get())
+        .map(|(_, transform)| transform.translation().z);
+
+    commands.spawn_batch(batch_sprite_instances(instances, boundaries));
+}
+
+/// The pipeline of the instanced sprites: the view bindings of the 2d meshes, the texture of the
+/// batch and a unit quad, drawn once for each instance.
+#[derive(Resource)]
+pub struct InstancedSpritePipeline {
+    view_layout: BindGroupLayout,
+    texture_layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for InstancedSpritePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let texture_layout = render_device.create_bind_group_layout(
+            "instanced_sprite_texture_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        Self {
+            view_layout: world.resource::<Mesh2dPipeline>().view_layout.clone(),
+            texture_layout,
+            shader: world
+                .resource::<AssetServer>()
+                .load(INSTANCED_SPRITE_SHADER),
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for InstancedSpritePipeline {
+    type Key = Mesh2dPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = match key.contains(Mesh2dPipelineKey::HDR) {
+            true => ViewTarget::TEXTURE_FORMAT_HDR,
+            false => TextureFormat::bevy_default(),
+        };
+
+        let quad_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Vertex,
+            [VertexFormat::Float32x2, VertexFormat::Float32x2],
+        );
+        let mut instance_layout = SpriteInstance::vertex_buffer_layout();
+        instance_layout
+            .attributes
+            .iter_mut()
+            .for_each(|attribute| attribute.shader_location += 2);
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                entry_point: "vertex".into(),
+                shader_defs: vec![],
+                buffers: vec![quad_layout, instance_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: vec![self.view_layout.clone(), self.texture_layout.clone()],
+            push_constant_ranges: vec![],
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("instanced_sprite_pipeline".into()),
+        }
+    }
+}
+
+/// The unit quad drawn for every instance, centered at the origin, with the same uvs of the
+/// rectangle meshes used by the material meshes.
+#[derive(Resource)]
+pub struct InstancedSpriteQuad {
+    vertices: Buffer,
+    indices: Buffer,
+}
+
+impl FromWorld for InstancedSpriteQuad {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let vertices: [[f32; 4]; 4] = [
+            [0.5, 0.5, 1., 0.],
+            [-0.5, 0.5, 0., 0.],
+            [-0.5, -0.5, 0., 1.],
+            [0.5, -0.5, 1., 1.],
+        ];
+        let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+        Self {
+            vertices: render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("instanced_sprite_quad_vertices"),
+                contents: bytemuck::cast_slice(&vertices),
+                usage: BufferUsages::VERTEX,
+            }),
+            indices: render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("instanced_sprite_quad_indices"),
+                contents: bytemuck::cast_slice(&indices),
+                usage: BufferUsages::INDEX,
+            }),
+        }
+    }
+}
+
+/// The texture of a batch and the range of its instances in the vertex buffer.
+pub struct PreparedInstancedSpriteBatch {
+    texture: AssetId<Image>,
+    range: Range<u32>,
+}
+
+/// The GPU resources of the instanced sprites, kept between frames.
+///
+/// The instances of all the batches share a single vertex buffer, rewritten only when they
+/// change, and the bind groups are cached by texture until its view or sampler changes.
+#[derive(Resource)]
+pub struct PreparedInstancedSprites {
+    instances: BufferVec<SpriteInstance>,
+    batches: HashMap<Entity, PreparedInstancedSpriteBatch>,
+    bind_groups: HashMap<AssetId<Image>, (TextureViewId, SamplerId, BindGroup)>,
+}
+
+impl Default for PreparedInstancedSprites {
+    fn default() -> Self {
+        let mut instances = BufferVec::new(BufferUsages::VERTEX);
+        instances.set_label(Some("instanced_sprite_instances"));
+
+        Self {
+            instances,
+            batches: HashMap::default(),
+            bind_groups: HashMap::default(),
+        }
+    }
+}
+
+/// Queues a transparent phase item for each batch, in every 2d view.
+pub fn queue_instanced_sprites(
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    pipeline: Res<InstancedSpritePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<InstancedSpritePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    q_batches: Query<(Entity, &InstancedSpriteBatch)>,
+    mut views: Query<(&ExtractedView, &mut RenderPhase<Transparent2d>)>,
+) {
+    if q_batches.is_empty() {
+        return;
+    }
+
+    let draw_function = draw_functions.read().id::<DrawInstancedSprites>();
+
+    for (view, mut phase) in &mut views {
+        let key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
+            | Mesh2dPipelineKey::from_hdr(view.hdr);
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &pipeline, key);
+
+        for (entity, batch) in &q_batches {
+            phase.add(Transparent2d {
+                sort_key: FloatOrd(batch.z()),
+                entity,
+                pipeline: pipeline_id,
+                draw_function,
+                batch_range: 0..1,
+                dynamic_offset: None,
+            });
+        }
+    }
+}
+
+/// Uploads the instances of the batches whose textures are available, and binds their textures.
+/// The vertex buffer is only written if the instances changed since the last frame, and bind
+/// groups are only created for new or changed textures.
+pub fn prepare_instanced_sprites(
+    mut prepared: ResMut<PreparedInstancedSprites>,
+    pipeline: Res<InstancedSpritePipeline>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    images: Res<RenderAssets<Image>>,
+    q_batches: Query<(Entity, &InstancedSpriteBatch)>,
+) {
+    let prepared = prepared.as_mut();
+    prepared.batches.clear();
+
+    let mut instances = Vec::with_capacity(prepared.instances.len());
+    let mut textures = HashSet::new();
+
+    for (entity, batch) in &q_batches {
+        let Some(image) = images.get(batch.texture) else {
+            continue;
+        };
+
+        let up_to_date = prepared
+            .bind_groups
+            .get(&batch.texture)
+            .is_some_and(|(view, sampler, _)| {
+                *view == image.texture_view.id() && *sampler == image.sampler.id()
+            });
+
+        if !up_to_date {
+            let bind_group = render_device.create_bind_group(
+                "instanced_sprite_bind_group",
+                &pipeline.texture_layout,
+                &BindGroupEntries::sequential((&image.texture_view, &image.sampler)),
+            );
+
+            prepared.bind_groups.insert(
+                batch.texture,
+                (image.texture_view.id(), image.sampler.id(), bind_group),
+            );
+        }
+
+        let start = instances.len() as u32;
+        instances.extend_from_slice(&batch.instances);
+        textures.insert(batch.texture);
+
+        prepared.batches.insert(
+            entity,
+            PreparedInstancedSpriteBatch {
+                texture: batch.texture,
+                range: start..instances.len() as u32,
+            },
+        );
+    }
+
+    prepared
+        .bind_groups
+        .retain(|texture, _| textures.contains(texture));
+
+    if *prepared.instances.values() != instances {
+        *prepared.instances.values_mut() = instances;
+        prepared
+            .instances
+            .write_buffer(&render_device, &render_queue);
+    }
+}
+
+pub type DrawInstancedSprites = (
+    SetItemPipeline,
+    SetMesh2dViewBindGroup<0>,
+    DrawSpriteInstances,
+);
+
+/// Draws all the instances of a batch with a single draw call.
+pub struct DrawSpriteInstances;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawSpriteInstances {
+    type Param = (SRes<InstancedSpriteQuad>, SRes<PreparedInstancedSprites>);
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _entity: Option<()>,
+        (quad, prepared): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let quad = quad.into_inner();
+        let prepared = prepared.into_inner();
+        let Some(batch) = prepared.batches.get(&item.entity()) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some((_, _, bind_group)) = prepared.bind_groups.get(&batch.texture) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(instances) = prepared.instances.buffer() else {
+            return RenderCommandResult::Failure;
+        };
+
+        pass.set_bind_group(1, bind_group, &[]);
+        pass.set_vertex_buffer(0, quad.vertices.slice(..));
+        pass.set_vertex_buffer(1, instances.slice(..));
+        pass.set_index_buffer(quad.indices.slice(..), 0, IndexFormat::Uint16);
+        pass.draw_indexed(0..6, 0, batch.range.clone());
+
+        RenderCommandResult::Success
+    }
+}
@@ -0,0 +1,112 @@
+#import bevy_sprite::mesh2d_view_bindings::view
+
+@group(1) @binding(0)
+var texture: texture_2d<f32>;
+@group(1) @binding(1)
+var texture_sampler: sampler;
+
+struct Vertex {
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) translation: vec3<f32>,
+    @location(3) size: vec2<f32>,
+    @location(4) index: u32,
+    @location(5) counts: vec2<f32>,
+    @location(6) tint: vec4<f32>,
+    @location(7) outline_color: vec4<f32>,
+    @location(8) alpha: f32,
+    @location(9) outline_thickness: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) index: u32,
+    @location(2) counts: vec2<f32>,
+    @location(3) tint: vec4<f32>,
+    @location(4) outline_color: vec4<f32>,
+    @location(5) alpha: f32,
+    @location(6) outline_thickness: f32,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    let world_position = vec4<f32>(vertex.translation + vec3<f32>(vertex.position * vertex.size, 0.), 1.);
+
+    var out: VertexOutput;
+    out.clip_position = view.view_proj * world_position;
+    out.uv = vertex.uv;
+    out.index = vertex.index;
+    out.counts = vertex.counts;
+    out.tint = vertex.tint;
+    out.outline_color = vertex.outline_color;
+    out.alpha = vertex.alpha;
+    out.outline_thickness = vertex.outline_thickness;
+    return out;
+}
+
+// Mirrors the fragment of the sprite material, reading the material from the instance.
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let centered_uv = (in.uv - vec2<f32>(0.25, 0.25));
+
+    let uv = vec2<f32>(centered_uv.x * 2.0, centered_uv.y * 2.0);
+    let cuv = vec2(uv.x / in.counts.x, uv.y / in.counts.y);
+    let thickness = in.outline_thickness / 1000.;
+
+    let base = pixel(in, cuv, vec2(0., 0.));
+
+    var outline_alpha : f32 = 0.;
+    outline_alpha += pixel(in, cuv, vec2(thickness, 0.)).a;
+    outline_alpha += pixel(in, cuv, vec2(-thickness, 0.)).a;
+    outline_alpha += pixel(in, cuv, vec2(0., thickness)).a;
+    outline_alpha += pixel(in, cuv, vec2(0., -thickness)).a;
+    outline_alpha += pixel(in, cuv, vec2(thickness, -thickness)).a;
+    outline_alpha += pixel(in, cuv, vec2(-thickness, thickness)).a;
+    outline_alpha += pixel(in, cuv, vec2(thickness, thickness)).a;
+    outline_alpha += pixel(in, cuv, vec2(-thickness, -thickness)).a;
+    outline_alpha = min(outline_alpha, 1.0);
+
+    let outline_color = in.outline_color * vec4<f32>(1.0, 1.0, 1.0, outline_alpha);
+    let lower = vec2<f32>(0.25, 0.25);
+    let upper = vec2<f32>(0.75, 0.75);
+    let lower_thickness = lower - vec2<f32>(thickness, thickness);
+    let upper_thickness = upper + vec2<f32>(thickness, thickness);
+
+    if (in.uv.x < lower_thickness.x || in.uv.x > upper_thickness.x) {
+        discard;
+    }
+
+    if (in.uv.y < lower_thickness.y || in.uv.y > upper_thickness.y) {
+        discard;
+    }
+
+    if (in.uv.x < lower.x || in.uv.x > upper.x) {
+        return outline_color;
+    }
+
+    if (in.uv.y < lower.y || in.uv.y > upper.y) {
+        return outline_color;
+    }
+
+    var outlined = mix(base, outline_color, outline_alpha - base.a);
+    var tinted = mix(outlined, vec4<f32>(outlined.rgb * in.tint.rgb, outlined.a), in.tint.a);
+    tinted.a *= in.alpha;
+    return tinted;
+}
+
+fn pixel(in: VertexOutput, uv: vec2<f32>, adjustment: vec2<f32>) -> vec4<f32> {
+    let sprite_size = vec2<f32>(1.0 / in.counts.x, 1.0 / in.counts.y);
+    let base_uv = vec2<f32>(
+        f32(in.index % u32(in.counts.x)) * sprite_size.x,
+        f32(in.index / u32(in.counts.x)) * sprite_size.y,
+    );
+
+    // Use a small inset to avoid sampling the borders
+    let inset = 0.001;
+    let min_uv = base_uv + vec2<f32>(inset) * sprite_size;
+    let max_uv = base_uv + sprite_size - vec2<f32>(inset) * sprite_size;
+    let clamped_uv = clamp(base_uv + uv + adjustment, min_uv, max_uv);
+
+    return textureSample(texture, texture_sampler, clamped_uv);
+}
@@ -5,8 +5,14 @@
 use bevy_ecs::prelude::SystemSet;
 
 pub mod animation;
+pub mod atlas;
+pub mod composition;
+#[cfg(feature = "ray_casting")]
+pub mod fog_of_war;
+pub mod instancing;
 pub mod loading;
 pub mod material;
+pub mod pattern;
 pub mod sheets;
 pub mod update;
 
@@ -26,29 +32,56 @@ pub enum SpriteSystems {
 pub mod prelude {
     pub use crate::{
         animation::{
+            controller::AnimationController,
             descriptor::{AnimationDescriptor, AnimationSprite},
             key::{AnimationKey, SpriteAnimationExt},
             state::{AnimationStartPhase, AnimationState},
             systems::{
-                initialize_animation_sprite_system, tick_animation_system, AnimationDuration,
-                AnimationSystems, SynchronizedAnimationTimers,
+                despawn_finished_animations_system, initialize_animation_sprite_system,
+                tick_animation_system, AnimationDuration, AnimationFinished, AnimationSystems,
+                DespawnOnAnimationFinished, SynchronizedAnimationTimers,
             },
             toggle::{toggle_sprite_animation, SpriteAnimationEnabled},
         },
+        atlas::{
+            evict_unused_atlas_sprites_system, pack_sprite_atlas_system, AtlasPage, AtlasSlot,
+            SpriteAtlases,
+        },
+        composition::{
+            compose_outfit_system, sync_outfit_parts_system, Mounted, OutfitAddons, OutfitPart,
+        },
         get_decompressed_file_name,
+        instancing::{
+            detach_instanced_sprite_meshes, embed_instanced_sprite_assets,
+            render::{
+                batch_sprite_instances, extract_instanced_sprites, prepare_instanced_sprites,
+                queue_instanced_sprites, DrawInstancedSprites, InstancedSpriteBatch,
+                InstancedSpritePipeline, InstancedSpriteQuad, PreparedInstancedSprites,
+                SpriteInstance,
+            },
+            restore_instanced_sprite_meshes, InstancedSprite,
+        },
         loading::{
-            loaded::{LoadedAppearance, LoadedAppearances, LoadedSprite},
+            cache::{evict_sprite_cache_system, sprite_cache_diagnostics_system, SpriteCache},
+            loaded::{AppearanceLoadState, LoadedAppearance, LoadedAppearances, LoadedSprite},
             systems::{
+                check_appearance_readiness_system, invalidate_changed_appearances_system,
                 load_from_entities_system, load_sprite_system, process_load_events_system,
                 store_loaded_appearances_system,
             },
-            LoadAppearanceEvent,
+            AppearanceReadyEvent, LoadAppearanceEvent,
         },
         material::{
             embed_sprite_assets, initialize_sprite_material,
             meshes::{RectMeshes, SpriteMeshes},
+            outfit::{outfit_color, OutfitColors, OUTFIT_PALETTE_SIZE},
             params::{SpriteOutline, SpriteParams},
-            SpriteMaterial,
+            placeholder::{PlaceholderMaterials, PlaceholderSprite, SpritePlaceholder},
+            sprite_cell, SpriteMaterial,
+        },
+        pattern::{
+            resolve_sprite_variant_system, FluidColor, PatternDimensions, SpritePattern,
+            SpriteVariant,
         },
         sheets::SpriteSheets,
         update::update_sprite_system,
@@ -57,6 +90,9 @@ pub mod prelude {
 
     #[cfg(feature = "debug")]
     pub use crate::loading::debug::debug_sprites;
+
+    #[cfg(feature = "ray_casting")]
+    pub use crate::fog_of_war::{apply_fog_of_war_to_sprites, FogOfWarRendering, FoggedSprite};
 }
 
 #[cfg(test)]
@@ -0,0 +1,200 @@
+//! Eviction of the loaded appearances that are no longer used.
+//!
+//! Loaded appearances hold the materials of their sprites and the textures of their sprite
+//! sheets, so they are kept in memory for as long as they are loaded. The cache tracks how many
+//! live entities reference each appearance, and once the sprite memory goes over its budget, it
+//! unloads the unreferenced appearances, least recently used first. Their sheet textures are
+//! unloaded as soon as no other loaded appearance uses them.
+use crate::prelude::*;
+use bevy_asset::{AssetId, Assets};
+use bevy_diagnostic::{DiagnosticPath, Diagnostics};
+use bevy_ecs::prelude::*;
+use bevy_render::prelude::Image;
+use bevy_time::{Time, Timer, TimerMode};
+use bevy_utils::tracing::debug;
+use bevy_utils::{HashMap, HashSet};
+use ryot_core::prelude::*;
+use std::time::Duration;
+
+/// The references and usage of the loaded appearances, along with the memory budget of the
+/// sprites. Appearances are only evicted while the memory is over the budget.
+#[derive(Resource, Debug, Clone)]
+pub struct SpriteCache {
+    pub budget: usize,
+    pub check_timer: Timer,
+    references: HashMap<(ContentId, FrameGroup), usize>,
+    last_used: HashMap<(ContentId, FrameGroup), Duration>,
+    memory: usize,
+}
+
+impl Default for SpriteCache {
+    fn default() -> Self {
+        Self {
+            budget: 512 * 1024 * 1024,
+            check_timer: Timer::new(Duration::from_secs(1), TimerMode::Repeating),
+            references: HashMap::default(),
+            last_used: HashMap::default(),
+            memory: 0,
+        }
+    }
+}
+
+impl SpriteCache {
+    /// The memory used by the sprite sheets and atlases, in bytes.
+    pub const MEMORY: DiagnosticPath = DiagnosticPath::const_new("ryot/sprites/memory");
+    /// The number of loaded appearances.
+    pub const LOADED_APPEARANCES: DiagnosticPath =
+        DiagnosticPath::const_new("ryot/sprites/loaded_appearances");
+
+    pub fn with_budget(self, budget: usize) -> Self {
+        Self { budget, ..self }
+    }
+
+    pub fn with_check_interval(self, interval: Duration) -> Self {
+        Self {
+            check_timer: Timer::new(interval, TimerMode::Repeating),
+            ..self
+        }
+    }
+
+    /// The number of live entities referencing the appearance, as of the last check.
+    pub fn references(&self, key: &(ContentId, FrameGroup)) -> usize {
+        self.references.get(key).copied().unwrap_or_default()
+    }
+
+    /// The memory used by the sprites, in bytes, as of the last check.
+    pub fn memory(&self) -> usize {
+        self.memory
+    }
+
+    /// Returns the unreferenced appearances, least recently used first.
+    fn eviction_candidates(&self) -> Vec<(ContentId, FrameGroup)> {
+        let mut candidates = self
+            .last_used
+            .iter()
+            .filter(|(key, _)| self.references(key) == 0)
+            .map(|(key, last_used)| (*key, *last_used))
+            .collect::<Vec<_>>();
+
+        candidates.sort_by_key(|(_, last_used)| *last_used);
+        candidates.into_iter().map(|(key, _)| key).collect()
+    }
+}
+
+/// The number of loaded sprites using each sheet texture, along with the size of the texture.
+fn texture_usage(
+    loaded_appearances: &LoadedAppearances,
+    images: &Assets<Image>,
+) -> HashMap<AssetId<Image>, (usize, usize)> {
+    let mut usage = HashMap::<AssetId<Image>, (usize, usize)>::default();
+
+    for sprite in loaded_appearances
+        .values()
+        .flat_map(|appearance| appearance.sprites.iter())
+    {
+        let (count, _) = usage.entry(sprite.texture.id()).or_insert_with(|| {
+            let size = images
+                .get(&sprite.texture)
+                .map_or(0, |image| image.data.len());
+            (0, size)
+        });
+        *count += 1;
+    }
+
+    usage
+}
+
+/// A system that periodically counts the references of the loaded appearances and, while the
+/// sprite memory is over the budget, unloads the unreferenced ones, least recently used first.
+pub fn evict_sprite_cache_system(
+    time: Res<Time>,
+    mut cache: ResMut<SpriteCache>,
+    mut loaded_appearances: ResMut<LoadedAppearances>,
+    mut atlases: Option<ResMut<SpriteAtlases>>,
+    images: Res<Assets<Image>>,
+    q_contents: Query<(&ContentId, Option<&FrameGroup>)>,
+) {
+    if !cache.check_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let now = time.elapsed();
+    let mut references = HashMap::<(ContentId, FrameGroup), usize>::default();
+
+    for (content_id, frame_group) in &q_contents {
+        *references
+            .entry((*content_id, frame_group.copied().unwrap_or_default()))
+            .or_default() += 1;
+    }
+
+    let cache = cache.as_mut();
+    cache
+        .last_used
+        .retain(|key, _| loaded_appearances.contains_key(key));
+
+    for key in loaded_appearances.keys() {
+        match references.contains_key(key) {
+            true => {
+                cache.last_used.insert(*key, now);
+            }
+            false => {
+                cache.last_used.entry(*key).or_insert(now);
+            }
+        }
+    }
+
+    cache.references = references;
+
+    let mut usage = texture_usage(&loaded_appearances, &images);
+    let atlas_memory = atlases.as_ref().map_or(0, |atlases| atlases.memory());
+    let mut memory = atlas_memory + usage.values().map(|(_, size)| size).sum::<usize>();
+
+    if memory > cache.budget {
+        for key in cache.eviction_candidates() {
+            if memory <= cache.budget {
+                break;
+            }
+
+            let Some(appearance) = loaded_appearances.remove(&key) else {
+                continue;
+            };
+            cache.last_used.remove(&key);
+
+            for sprite in &appearance.sprites {
+                let Some((count, size)) = usage.get_mut(&sprite.texture.id()) else {
+                    continue;
+                };
+
+                *count -= 1;
+                if *count == 0 {
+                    memory -= *size;
+                }
+            }
+
+            debug!("Evicted appearance {:?} from the sprite cache", key);
+        }
+
+        if let Some(atlases) = atlases.as_mut() {
+            let loaded_sprites = loaded_appearances
+                .values()
+                .flat_map(|appearance| appearance.sprites.iter().map(|sprite| sprite.sprite_id))
+                .collect::<HashSet<_>>();
+
+            atlases.release_unless(|sprite_id| loaded_sprites.contains(&sprite_id));
+        }
+    }
+
+    cache.memory = memory;
+}
+
+/// A system that reports the sprite memory and the number of loaded appearances.
+pub fn sprite_cache_diagnostics_system(
+    cache: Res<SpriteCache>,
+    loaded_appearances: Res<LoadedAppearances>,
+    mut diagnostics: Diagnostics,
+) {
+    diagnostics.add_measurement(&SpriteCache::MEMORY, || cache.memory() as f64);
+    diagnostics.add_measurement(&SpriteCache::LOADED_APPEARANCES, || {
+        loaded_appearances.len() as f64
+    });
+}
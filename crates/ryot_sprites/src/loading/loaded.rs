@@ -12,7 +12,24 @@ use ryot_core::prelude::{FrameGroup, SpriteSheet};
 pub struct LoadedAppearance {
     pub sprites: Vec<LoadedSprite>,
     pub layers: u32,
+    pub pattern: PatternDimensions,
     pub animation: Option<(AnimationKey, AnimationDescriptor)>,
+    pub state: AppearanceLoadState,
+}
+
+impl LoadedAppearance {
+    pub fn is_ready(&self) -> bool {
+        self.state == AppearanceLoadState::Ready
+    }
+}
+
+/// The loading state of an appearance. Its materials are created right away, but it's only
+/// ready once the sprite sheet textures of all its sprites are loaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AppearanceLoadState {
+    #[default]
+    Loading,
+    Ready,
 }
 
 #[derive(Resource, Default, Deref, DerefMut)]
@@ -2,6 +2,7 @@ use bevy_ecs::event::Event;
 use ryot_core::content::ContentId;
 use ryot_core::prelude::FrameGroup;
 
+pub mod cache;
 #[cfg(feature = "debug")]
 pub mod debug;
 pub mod loaded;
@@ -12,3 +13,10 @@ pub struct LoadAppearanceEvent {
     pub object_id: ContentId,
     pub frame_group: FrameGroup,
 }
+
+/// An event sent once all the sprite sheet textures of a loaded appearance are loaded.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AppearanceReadyEvent {
+    pub object_id: ContentId,
+    pub frame_group: FrameGroup,
+}
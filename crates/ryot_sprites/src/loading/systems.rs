@@ -3,10 +3,10 @@ use crate::animation::key::SpriteAnimationExt;
 use crate::material::meshes::SpriteMeshes;
 use crate::material::SpriteMaterial;
 use crate::prelude::*;
-use bevy_asset::{AssetServer, Assets, Handle};
+use bevy_asset::{AssetEvent, AssetServer, Assets, Handle};
 use bevy_ecs::change_detection::{Res, ResMut};
 use bevy_ecs::event::{EventReader, EventWriter};
-use bevy_ecs::prelude::{Changed, In, Or, Query};
+use bevy_ecs::prelude::{Changed, DetectChanges, DetectChangesMut, In, Or, Query};
 use bevy_render::prelude::Image;
 use bevy_utils::tracing::warn;
 use bevy_utils::{default, HashMap, HashSet};
@@ -201,6 +201,7 @@ pub fn store_loaded_appearances_system(
                     AnimationDescriptor {
                         sprites: sprites.clone(),
                         layers: sprite_info.layers as usize,
+                        pattern: sprite_info.into(),
                         skip: (sprite_info.layers
                             * sprite_info.pattern_width
                             * sprite_info.pattern_height
@@ -213,13 +214,107 @@ pub fn store_loaded_appearances_system(
             let loaded_appearance = LoadedAppearance {
                 sprites: sprites.clone(),
                 layers: sprite_info.layers,
+                pattern: sprite_info.into(),
                 animation: animation_tuple,
+                state: AppearanceLoadState::Loading,
             };
 
             loaded_appearances.insert((*object_id, *frame_group), loaded_appearance);
         });
 }
 
+/// A system that marks the loading appearances as ready once all the sprite sheet textures of
+/// their sprites are loaded, sending an [AppearanceReadyEvent] for each of them.
+pub fn check_appearance_readiness_system(
+    images: Res<Assets<Image>>,
+    mut loaded_appearances: ResMut<LoadedAppearances>,
+    mut ready_events: EventWriter<AppearanceReadyEvent>,
+) {
+    for ((object_id, frame_group), appearance) in loaded_appearances.iter_mut() {
+        if appearance.is_ready()
+            || !appearance
+                .sprites
+                .iter()
+                .all(|sprite| images.contains(&sprite.texture))
+        {
+            continue;
+        }
+
+        appearance.state = AppearanceLoadState::Ready;
+        ready_events.send(AppearanceReadyEvent {
+            object_id: *object_id,
+            frame_group: *frame_group,
+        });
+    }
+}
+
+/// A system that unloads the loaded appearances whose content changed: the ones whose visual
+/// elements were reloaded, the ones whose sprites moved to another sprite sheet and the ones whose
+/// sprite sheet textures were modified. The entities using them are marked as changed, so their
+/// appearances are loaded and drawn again.
+pub fn invalidate_changed_appearances_system(
+    sprite_sheets: Res<SpriteSheets>,
+    mut changed_events: EventReader<VisualElementsChanged>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut loaded_appearances: ResMut<LoadedAppearances>,
+    mut atlases: Option<ResMut<SpriteAtlases>>,
+    mut q_contents: Query<(&mut ContentId, Option<&FrameGroup>)>,
+) {
+    let changed_ids = changed_events
+        .read()
+        .flat_map(|VisualElementsChanged(ids)| ids.iter().copied())
+        .collect::<HashSet<_>>();
+
+    let modified_textures = image_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    let sheets_changed = sprite_sheets.is_changed();
+
+    if changed_ids.is_empty() && modified_textures.is_empty() && !sheets_changed {
+        return;
+    }
+
+    let invalidated = loaded_appearances
+        .iter()
+        .filter(|((object_id, _), appearance)| {
+            changed_ids.contains(object_id)
+                || appearance.sprites.iter().any(|sprite| {
+                    modified_textures.contains(&sprite.texture.id())
+                        || (sheets_changed
+                            && sprite_sheets.get_by_sprite_id(sprite.sprite_id)
+                                != Some(&sprite.sprite_sheet))
+                })
+        })
+        .map(|(key, _)| *key)
+        .collect::<HashSet<_>>();
+
+    if invalidated.is_empty() {
+        return;
+    }
+
+    loaded_appearances.retain(|key, _| !invalidated.contains(key));
+
+    if let Some(atlases) = atlases.as_mut() {
+        let loaded_sprites = loaded_appearances
+            .values()
+            .flat_map(|appearance| appearance.sprites.iter().map(|sprite| sprite.sprite_id))
+            .collect::<HashSet<_>>();
+
+        atlases.release_unless(|sprite_id| loaded_sprites.contains(&sprite_id));
+    }
+
+    for (mut object_id, frame_group) in &mut q_contents {
+        if invalidated.contains(&(*object_id, frame_group.copied().unwrap_or_default())) {
+            object_id.set_changed();
+        }
+    }
+}
+
 fn load_sprite_textures(
     sprite_ids: Vec<u32>,
     asset_server: &Res<AssetServer>,
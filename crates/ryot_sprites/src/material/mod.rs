@@ -7,11 +7,13 @@ use bevy_render::color::Color;
 use bevy_render::prelude::Image;
 use bevy_render::render_resource::{AsBindGroup, ShaderRef};
 use bevy_sprite::{Material2d, MaterialMesh2dBundle};
-use glam::Vec2;
+use glam::{UVec2, Vec2};
 use ryot_core::prelude::{ContentId, SpriteLayout};
 
 pub mod meshes;
+pub mod outfit;
 pub mod params;
+pub mod placeholder;
 
 #[derive(AsBindGroup, TypePath, Asset, Debug, Clone, Default, PartialEq)]
 pub struct SpriteMaterial {
@@ -27,9 +29,37 @@ pub struct SpriteMaterial {
     pub tint: Color,
     #[uniform(0)]
     pub alpha: f32,
+    #[uniform(0)]
+    pub mask_enabled: u32,
+    #[uniform(0)]
+    pub mask_index: u32,
+    #[uniform(0)]
+    pub mask_counts: Vec2,
+    #[uniform(0)]
+    pub head_color: Color,
+    #[uniform(0)]
+    pub body_color: Color,
+    #[uniform(0)]
+    pub legs_color: Color,
+    #[uniform(0)]
+    pub feet_color: Color,
     #[texture(1)]
     #[sampler(2)]
     pub texture: Handle<Image>,
+    #[texture(3)]
+    #[sampler(4)]
+    pub mask_texture: Option<Handle<Image>>,
+}
+
+/// The column and row of the sprite at `index` in a texture split in a grid of `counts` sprites,
+/// matching the lookup of `uv_offset` and `mask_pixel` in `sprite.wgsl`.
+///
+/// Sprites are laid out row by row, so the row is the index divided by the number of columns.
+/// Dividing by the number of rows only works for square grids, while sheets of 2x1 and 1x2
+/// sprites, as well as atlas pages holding them, have a different number of columns and rows.
+pub fn sprite_cell(index: u32, counts: Vec2) -> UVec2 {
+    let columns = (counts.x as u32).max(1);
+    UVec2::new(index % columns, index / columns)
 }
 
 impl Material2d for SpriteMaterial {
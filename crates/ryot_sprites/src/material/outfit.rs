@@ -0,0 +1,109 @@
+use crate::material::SpriteMaterial;
+use bevy_ecs::component::Component;
+use bevy_render::color::Color;
+
+/// The number of hues in the outfit palette, the first of them being the grey scale.
+const PALETTE_HUE_STEPS: u8 = 19;
+
+/// The number of saturation/intensity variations of each hue in the outfit palette.
+const PALETTE_SI_VALUES: u8 = 7;
+
+/// The size of the standard outfit palette: 19 hues times 7 saturation/intensity variations.
+pub const OUTFIT_PALETTE_SIZE: u8 = PALETTE_HUE_STEPS * PALETTE_SI_VALUES;
+
+/// The colours of the four parts of an outfit, as indexes of the standard 133-colour palette.
+///
+/// Outfits have a second sprite layer, the colour mask, where each part of the outfit is painted
+/// with a pure colour: yellow for the head, red for the body, green for the legs and blue for
+/// the feet. The pixels of the base layer covered by the mask are multiplied by the colour of
+/// their part, so the same sprite can be rendered with any combination of colours.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Component)]
+pub struct OutfitColors {
+    pub head: u8,
+    pub body: u8,
+    pub legs: u8,
+    pub feet: u8,
+}
+
+impl OutfitColors {
+    pub fn new(head: u8, body: u8, legs: u8, feet: u8) -> Self {
+        Self {
+            head,
+            body,
+            legs,
+            feet,
+        }
+    }
+
+    /// Applies the outfit colours to the base material, sampling the colour mask from the
+    /// texture and position of the mask sprite, described by its own material.
+    pub fn to_material(&self, base: SpriteMaterial, mask: &SpriteMaterial) -> SpriteMaterial {
+        SpriteMaterial {
+            mask_enabled: 1,
+            mask_index: mask.index,
+            mask_counts: mask.counts,
+            mask_texture: Some(mask.texture.clone()),
+            head_color: outfit_color(self.head),
+            body_color: outfit_color(self.body),
+            legs_color: outfit_color(self.legs),
+            feet_color: outfit_color(self.feet),
+            ..base
+        }
+    }
+}
+
+/// Returns the colour of the standard outfit palette for the given index.
+///
+/// The palette is a grid of 19 hues by 7 saturation/intensity variations, where the first hue of
+/// each row is a shade of grey. Indexes out of the palette fall back to the first colour (white).
+pub fn outfit_color(index: u8) -> Color {
+    let index = if index >= OUTFIT_PALETTE_SIZE {
+        0
+    } else {
+        index
+    };
+
+    let (hue, saturation, intensity) = match index % PALETTE_HUE_STEPS {
+        0 => (
+            0.,
+            0.,
+            1. - index as f32 / PALETTE_HUE_STEPS as f32 / PALETTE_SI_VALUES as f32,
+        ),
+        step => {
+            let (saturation, intensity) = match index / PALETTE_HUE_STEPS {
+                0 => (0.25, 1.),
+                1 => (0.25, 0.75),
+                2 => (0.5, 0.75),
+                3 => (0.667, 0.75),
+                4 => (1., 1.),
+                5 => (1., 0.75),
+                _ => (1., 0.5),
+            };
+
+            (step as f32 / 18., saturation, intensity)
+        }
+    };
+
+    if saturation == 0. {
+        let grey = (intensity * 255.) as u8;
+        return Color::rgb_u8(grey, grey, grey);
+    }
+
+    let min = intensity * (1. - saturation);
+    let ramp = |start: f32, end: f32, sector: f32| start + (end - start) * (6. * hue - sector);
+
+    let (red, green, blue) = match (hue * 6.) as u8 {
+        0 => (intensity, ramp(min, intensity, 0.), min),
+        1 => (ramp(intensity, min, 1.), intensity, min),
+        2 => (min, intensity, ramp(min, intensity, 2.)),
+        3 => (min, ramp(intensity, min, 3.), intensity),
+        4 => (ramp(min, intensity, 4.), min, intensity),
+        _ => (intensity, min, ramp(intensity, min, 5.)),
+    };
+
+    Color::rgb_u8(
+        (red * 255.) as u8,
+        (green * 255.) as u8,
+        (blue * 255.) as u8,
+    )
+}
@@ -0,0 +1,87 @@
+use crate::material::params::SpriteParams;
+use crate::material::SpriteMaterial;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_render::color::Color;
+use bevy_utils::{default, HashMap};
+use glam::Vec2;
+
+/// What is drawn in place of the sprites whose appearance is still loading its textures.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub enum SpritePlaceholder {
+    /// The sprite material is used right away, drawing nothing until its texture is loaded.
+    #[default]
+    Blank,
+    /// A solid rectangle, tinted with the `SpriteParams` tint of the entity or the given colour.
+    Tint(Color),
+    /// A fixed material, like a loading sprite.
+    Sprite(Handle<SpriteMaterial>),
+}
+
+impl SpritePlaceholder {
+    /// Returns the placeholder material for an entity with the given sprite params, if any.
+    pub fn material(
+        &self,
+        sprite_params: Option<&SpriteParams>,
+        cache: &mut PlaceholderMaterials,
+        materials: &mut Assets<SpriteMaterial>,
+    ) -> Option<Handle<SpriteMaterial>> {
+        match self {
+            SpritePlaceholder::Blank => None,
+            SpritePlaceholder::Sprite(material) => Some(material.clone()),
+            SpritePlaceholder::Tint(color) => {
+                let params = sprite_params.cloned().unwrap_or_default();
+                let tint = params.tint.unwrap_or(*color).with_a(1.);
+                let alpha = params.alpha.unwrap_or(1.);
+
+                Some(cache.get_or_add(tint, alpha, materials))
+            }
+        }
+    }
+}
+
+/// The materials drawn by the [SpritePlaceholder::Tint] placeholder, one for each colour, so the
+/// entities sharing a colour also share the same material instead of creating one each.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PlaceholderMaterials(HashMap<[u32; 4], Handle<SpriteMaterial>>);
+
+impl PlaceholderMaterials {
+    fn get_or_add(
+        &mut self,
+        tint: Color,
+        alpha: f32,
+        materials: &mut Assets<SpriteMaterial>,
+    ) -> Handle<SpriteMaterial> {
+        let [r, g, b, _] = tint.as_rgba_f32();
+        let key = [r, g, b, alpha].map(f32::to_bits);
+
+        if let Some(material) = self.0.get(&key) {
+            if materials.contains(material) {
+                return material.clone();
+            }
+        }
+
+        // The default texture is a single white pixel, so the tint is the final colour.
+        let material = materials.add(SpriteMaterial {
+            counts: Vec2::ONE,
+            tint,
+            alpha,
+            ..default()
+        });
+        self.0.insert(key, material.clone());
+
+        material
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Marks an entity drawn with the sprite placeholder, waiting for its appearance to be ready.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PlaceholderSprite;
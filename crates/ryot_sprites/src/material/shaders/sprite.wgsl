@@ -6,6 +6,13 @@ struct SpriteMaterial {
     outline_color: vec4<f32>,
     tint: vec4<f32>,
     alpha: f32,
+    mask_enabled: u32,
+    mask_index: u32,
+    mask_counts: vec2<f32>,
+    head_color: vec4<f32>,
+    body_color: vec4<f32>,
+    legs_color: vec4<f32>,
+    feet_color: vec4<f32>,
 };
 @group(2) @binding(0)
 var<uniform> material: SpriteMaterial;
@@ -13,6 +20,10 @@ var<uniform> material: SpriteMaterial;
 var texture: texture_2d<f32>;
 @group(2) @binding(2)
 var texture_sampler: sampler;
+@group(2) @binding(3)
+var mask_texture: texture_2d<f32>;
+@group(2) @binding(4)
+var mask_sampler: sampler;
 
 
 fn get_sample(
@@ -35,7 +46,11 @@ fn fragment(
     let cuv = vec2(ux, uy);
     let thickness = material.outline_thickness / 1000.;
 
-    let base = pixel(vec2(0., 0.), cuv, vec2(0., 0.));
+    var base = pixel(vec2(0., 0.), cuv, vec2(0., 0.));
+
+    if (material.mask_enabled == 1u) {
+        base = colorize(base, mask_pixel(uv));
+    }
 
     var outline_alpha : f32 = 0.;
     outline_alpha += pixel(vec2(0., 0.), cuv, vec2(thickness, 0.)).a;
@@ -96,8 +111,53 @@ fn pixel(offset: vec2<f32>, uv: vec2<f32>, adjustment: vec2<f32>) -> vec4<f32> {
     return get_sample(clamped_uv);
 }
 
+// Sprites are laid out row by row, so the row is the index divided by the number of columns
+// (counts.x), even when the grid is not square. Keep it in sync with `sprite_cell`.
 fn uv_offset(offset: vec2<f32>, uv: vec2<f32>) -> vec2<f32> {
     let uvx = uv.x + (f32(material.index % u32(material.counts.x)) + offset.x) / material.counts.x;
-    let uvy = uv.y + (f32(material.index / u32(material.counts.y)) + offset.y) / material.counts.y;
+    let uvy = uv.y + (f32(material.index / u32(material.counts.x)) + offset.y) / material.counts.y;
     return vec2(uvx, uvy);
 }
+
+// Multiplies the outfit pixel by the colour of the part painted in the mask:
+// yellow for the head, red for the body, green for the legs and blue for the feet.
+fn colorize(base: vec4<f32>, mask: vec4<f32>) -> vec4<f32> {
+    if (mask.a < 0.1) {
+        return base;
+    }
+
+    if (mask.r > 0.9 && mask.g > 0.9) {
+        return base * material.head_color;
+    }
+
+    if (mask.r > 0.9) {
+        return base * material.body_color;
+    }
+
+    if (mask.g > 0.9) {
+        return base * material.legs_color;
+    }
+
+    if (mask.b > 0.9) {
+        return base * material.feet_color;
+    }
+
+    return base;
+}
+
+// Samples the mask sprite, that has the same layout of the base sprite but can be placed
+// anywhere in its own sprite sheet.
+fn mask_pixel(uv: vec2<f32>) -> vec4<f32> {
+    let sprite_size = vec2<f32>(1.0 / material.mask_counts.x, 1.0 / material.mask_counts.y);
+    let base_uv = vec2<f32>(
+        f32(material.mask_index % u32(material.mask_counts.x)) * sprite_size.x,
+        f32(material.mask_index / u32(material.mask_counts.x)) * sprite_size.y,
+    );
+
+    let inset = 0.001;
+    let min_uv = base_uv + vec2<f32>(inset) * sprite_size;
+    let max_uv = base_uv + sprite_size - vec2<f32>(inset) * sprite_size;
+    let clamped_uv = clamp(base_uv + uv * sprite_size, min_uv, max_uv);
+
+    return textureSample(mask_texture, mask_sampler, clamped_uv);
+}
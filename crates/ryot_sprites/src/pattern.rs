@@ -0,0 +1,241 @@
+//! Appearances can have multiple variations of the same sprite, organized in a pattern grid of
+//! `width` x `height` x `depth` variations. Outfits, for example, use the width for directions,
+//! the height for addons and the depth for the mounted state, while items use the grid to show
+//! the size of a stack, the colour of a fluid or a piece of a large tileable ground.
+use crate::prelude::OutfitPart;
+use bevy_ecs::prelude::*;
+use ryot_core::prelude::*;
+use ryot_tiled::prelude::{Directional, ItemCount, TilePosition};
+
+/// The dimensions of the pattern grid of an appearance, as described in its `SpriteInfo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PatternDimensions {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+}
+
+impl Default for PatternDimensions {
+    fn default() -> Self {
+        Self {
+            width: 1,
+            height: 1,
+            depth: 1,
+        }
+    }
+}
+
+impl From<&SpriteInfo> for PatternDimensions {
+    fn from(sprite_info: &SpriteInfo) -> Self {
+        Self {
+            width: sprite_info.pattern_width.max(1),
+            height: sprite_info.pattern_height.max(1),
+            depth: sprite_info.pattern_depth.max(1),
+        }
+    }
+}
+
+impl PatternDimensions {
+    /// Returns the index of the first layer of the sprite variation selected by the direction and
+    /// the pattern, within a single animation phase of an appearance with the given layers.
+    ///
+    /// The direction selects the pattern column of directional entities, like it always did for
+    /// appearances without patterns. Otherwise, the pattern selects the column, the row and the
+    /// depth, wrapping around the grid.
+    pub fn sprite_index(
+        &self,
+        layers: usize,
+        direction: Option<&Directional>,
+        pattern: Option<&SpritePattern>,
+    ) -> usize {
+        let pattern = pattern.copied().unwrap_or_default();
+        let x = direction.map_or((pattern.x % self.width) as usize, |direction| {
+            direction.index()
+        });
+        let y = (pattern.y % self.height) as usize;
+        let z = (pattern.z % self.depth) as usize;
+
+        ((z * self.height as usize + y) * self.width as usize + x) * layers
+    }
+
+    /// Resolves the pattern position of a sprite variant, following the client rules:
+    /// - stacks with a 4x2 grid use one variation for each of the 1, 2, 3, 4, 5+, 10+, 25+ and
+    ///   50+ counts, while other stacks always use the first variation;
+    /// - fluids are laid out in rows of four colours;
+    /// - tileable content repeats the grid along the map, using the position modulo the grid.
+    pub fn resolve(
+        &self,
+        variant: &SpriteVariant,
+        position: Option<&TilePosition>,
+    ) -> SpritePattern {
+        match variant {
+            SpriteVariant::Count(count) if self.width == 4 && self.height == 2 => match count {
+                0 => SpritePattern::default(),
+                1..=4 => SpritePattern::new(*count as u32 - 1, 0, 0),
+                5..=9 => SpritePattern::new(0, 1, 0),
+                10..=24 => SpritePattern::new(1, 1, 0),
+                25..=49 => SpritePattern::new(2, 1, 0),
+                _ => SpritePattern::new(3, 1, 0),
+            },
+            SpriteVariant::Count(_) => SpritePattern::default(),
+            SpriteVariant::Fluid(color) => {
+                let color = *color as u32;
+                SpritePattern::new((color % 4) % self.width, (color / 4) % self.height, 0)
+            }
+            SpriteVariant::Position => {
+                let position = position.copied().unwrap_or_default();
+                SpritePattern::new(
+                    position.x.rem_euclid(self.width as i32) as u32,
+                    position.y.rem_euclid(self.height as i32) as u32,
+                    position.z.rem_euclid(self.depth as i32) as u32,
+                )
+            }
+        }
+    }
+}
+
+/// Selects the sprite variation of an entity within the pattern grid of its appearance.
+/// Entities without this component use the first variation of the grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Component)]
+pub struct SpritePattern {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+impl SpritePattern {
+    pub fn new(x: u32, y: u32, z: u32) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// The colours of the fluids, in the order they are laid out in the pattern grid of fluid
+/// containers and splashes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum FluidColor {
+    #[default]
+    Transparent = 0,
+    Blue,
+    Red,
+    Brown,
+    Green,
+    Yellow,
+    White,
+    Purple,
+    Black,
+}
+
+impl FluidColor {
+    /// Returns the colour of a fluid type, as stored in the count of fluid containers and
+    /// splashes in the map, following the client colour table.
+    pub fn from_fluid_type(fluid_type: u8) -> Self {
+        match fluid_type {
+            1 => Self::Blue,
+            2 | 10 | 26 => Self::Red,
+            3 | 11 | 19 | 27 | 35 | 43 => Self::Brown,
+            4 | 28 => Self::Green,
+            5 | 13 | 21 => Self::Yellow,
+            6 | 14 => Self::White,
+            7 | 15 => Self::Purple,
+            _ => Self::Transparent,
+        }
+    }
+}
+
+/// Describes how the sprite variation of an item is chosen, resolved into a [SpritePattern] by
+/// [resolve_sprite_variant_system]. Objects without a variant are resolved by their [ItemCount]
+/// when they are stackable or fluids, by their position when they are ground, so large ground
+/// patterns are seamlessly tiled along the map, and use the first variation otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Component)]
+pub enum SpriteVariant {
+    /// Stackable items, like coins, that change with the count of items in the stack.
+    Count(u8),
+    /// Fluid containers and splashes, that change with the colour of the fluid.
+    Fluid(FluidColor),
+    /// Tileable items, like ground patterns, that change with the position of the entity.
+    #[default]
+    Position,
+}
+
+impl SpriteVariant {
+    /// Returns the variant of an object without an explicit one, based on its item properties
+    /// and on the count it has in the map, if any.
+    pub fn for_object(properties: &ItemProperties, count: Option<&ItemCount>) -> Option<Self> {
+        let count = count.map(|count| count.0);
+
+        if properties.is_stackable {
+            Some(Self::Count(count.unwrap_or(1)))
+        } else if properties.is_liquid_container || properties.is_liquid_pool {
+            Some(Self::Fluid(FluidColor::from_fluid_type(
+                count.unwrap_or_default(),
+            )))
+        } else if properties.is_ground {
+            Some(Self::Position)
+        } else {
+            None
+        }
+    }
+}
+
+/// A system that resolves the sprite pattern of the entities whose variant, count, position or
+/// content changed, using the pattern dimensions of their appearance.
+#[allow(clippy::type_complexity)]
+pub fn resolve_sprite_variant_system(
+    mut commands: Commands,
+    visual_elements: Res<VisualElements>,
+    q_variants: Query<
+        (
+            Entity,
+            &ContentId,
+            Option<&FrameGroup>,
+            Option<&SpriteVariant>,
+            Option<&ItemCount>,
+            Option<&TilePosition>,
+            Option<&SpritePattern>,
+        ),
+        (
+            Without<OutfitPart>,
+            Or<(
+                Changed<ContentId>,
+                Changed<FrameGroup>,
+                Changed<SpriteVariant>,
+                Changed<ItemCount>,
+                Changed<TilePosition>,
+            )>,
+        ),
+    >,
+) {
+    for (entity, content_id, frame_group, variant, count, position, pattern) in &q_variants {
+        let Some((group, id)) = content_id.as_group_and_id() else {
+            continue;
+        };
+
+        let Some(element) = visual_elements.get_for_group_and_id(group, id) else {
+            continue;
+        };
+
+        let variant = match (variant, group) {
+            (Some(variant), _) => Some(*variant),
+            (None, ContentType::Object) => {
+                SpriteVariant::for_object(&element.item_properties, count)
+            }
+            _ => continue,
+        };
+
+        let Some(sprite_info) = element
+            .sprites_info
+            .get(frame_group.copied().unwrap_or_default() as usize)
+        else {
+            continue;
+        };
+
+        let resolved = variant.map_or_else(SpritePattern::default, |variant| {
+            PatternDimensions::from(sprite_info).resolve(&variant, position)
+        });
+
+        if pattern.copied().unwrap_or_default() != resolved {
+            commands.entity(entity).insert(resolved);
+        }
+    }
+}
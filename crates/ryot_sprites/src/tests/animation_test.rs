@@ -0,0 +1,304 @@
+use crate::prelude::*;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_time::Time;
+use rstest::rstest;
+use ryot_core::prelude::{ContentId, FrameGroup, LoopType};
+use std::time::Duration;
+
+const PHASE_DURATION: Duration = Duration::from_millis(100);
+
+fn animation_key(loop_type: LoopType, loop_count: u32) -> AnimationKey {
+    AnimationKey {
+        phase_durations: vec![PHASE_DURATION; 3],
+        start_phase: AnimationStartPhase::Fixed(0),
+        total_phases: 3,
+        loop_type,
+        loop_count,
+    }
+}
+
+fn play(key: &AnimationKey, ticks: usize) -> (Vec<usize>, AnimationState) {
+    let mut state = key.default_state();
+    let phases = (0..ticks)
+        .map(|_| {
+            state.tick(key, PHASE_DURATION);
+            state.current_phase
+        })
+        .collect();
+
+    (phases, state)
+}
+
+#[rstest]
+#[case(LoopType::Infinite, 0, vec![1, 2, 0, 1, 2, 0, 1])]
+#[case(LoopType::PingPong, 0, vec![1, 2, 1, 0, 1, 2, 1])]
+#[case(LoopType::Counted, 1, vec![1, 2, 2, 2, 2, 2, 2])]
+#[case(LoopType::Counted, 2, vec![1, 2, 0, 1, 2, 2, 2])]
+fn test_animation_loop(
+    #[case] loop_type: LoopType,
+    #[case] loop_count: u32,
+    #[case] expected: Vec<usize>,
+) {
+    let (phases, _) = play(&animation_key(loop_type, loop_count), expected.len());
+    assert_eq!(phases, expected);
+}
+
+#[rstest]
+#[case(LoopType::Infinite, 0)]
+#[case(LoopType::PingPong, 0)]
+fn test_endless_animation_never_finishes(#[case] loop_type: LoopType, #[case] loop_count: u32) {
+    let (_, state) = play(&animation_key(loop_type, loop_count), 100);
+    assert!(!state.is_finished());
+}
+
+#[test]
+fn test_counted_animation_ends_once() {
+    let key = animation_key(LoopType::Counted, 1);
+    let mut state = key.default_state();
+
+    let ended = (0..10)
+        .filter(|_| {
+            state.tick(&key, PHASE_DURATION);
+            state.just_ended()
+        })
+        .count();
+
+    assert_eq!(ended, 1);
+    assert!(state.is_finished());
+    assert_eq!(state.current_phase, 2);
+}
+
+#[rstest]
+#[case(LoopType::Infinite, 0, vec![1, 0, 2, 1, 0, 2])]
+#[case(LoopType::PingPong, 0, vec![1, 0, 1, 2, 1, 0])]
+#[case(LoopType::Counted, 1, vec![1, 0, 0, 0, 0, 0])]
+fn test_reversed_animation(
+    #[case] loop_type: LoopType,
+    #[case] loop_count: u32,
+    #[case] expected: Vec<usize>,
+) {
+    let key = animation_key(loop_type, loop_count);
+    let mut state = key.default_state();
+    state.seek(&key, 2);
+
+    let phases = (0..expected.len())
+        .map(|_| {
+            state.advance(&key, PHASE_DURATION, true);
+            state.current_phase
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(phases, expected);
+}
+
+#[test]
+fn test_seek_restarts_finished_animation() {
+    let key = animation_key(LoopType::Counted, 1);
+    let (_, mut state) = play(&key, 5);
+    assert!(state.is_finished());
+
+    state.seek(&key, 1);
+    assert!(!state.is_finished());
+    assert_eq!(state.current_phase, 1);
+
+    state.tick(&key, PHASE_DURATION);
+    assert_eq!(state.current_phase, 2);
+}
+
+#[test]
+fn test_controlled_synchronized_animation_detaches() {
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    world.init_resource::<Assets<SpriteMaterial>>();
+    world.init_resource::<Events<AnimationFinished>>();
+    world.init_resource::<SynchronizedAnimationTimers>();
+
+    let key = animation_key(LoopType::Infinite, 0);
+    let descriptor = AnimationDescriptor {
+        sprites: vec![],
+        layers: 1,
+        pattern: PatternDimensions::default(),
+        skip: 1,
+        synchronized: true,
+    };
+    let synchronized = world
+        .spawn((
+            Handle::<SpriteMaterial>::default(),
+            AnimationSprite::from_key_and_descriptor(&key, &descriptor),
+        ))
+        .id();
+    let controlled = world
+        .spawn((
+            Handle::<SpriteMaterial>::default(),
+            AnimationSprite::from_key_and_descriptor(&key, &descriptor),
+            AnimationController::default().with_paused(true),
+        ))
+        .id();
+
+    world.run_system_once(tick_animation_system);
+
+    assert!(matches!(
+        world.get::<AnimationSprite>(synchronized),
+        Some(AnimationSprite::Synchronized { .. })
+    ));
+    assert!(matches!(
+        world.get::<AnimationSprite>(controlled),
+        Some(AnimationSprite::Independent { .. })
+    ));
+}
+
+#[test]
+fn test_seek_resets_ping_pong_bounce() {
+    let key = animation_key(LoopType::PingPong, 0);
+    let (phases, mut state) = play(&key, 3);
+    assert_eq!(phases, vec![1, 2, 1]);
+
+    state.seek(&key, 1);
+    state.tick(&key, PHASE_DURATION);
+    assert_eq!(state.current_phase, 2);
+}
+
+fn independent_descriptor() -> AnimationDescriptor {
+    AnimationDescriptor {
+        sprites: vec![],
+        layers: 1,
+        pattern: PatternDimensions::default(),
+        skip: 1,
+        synchronized: false,
+    }
+}
+
+#[test]
+fn test_controller_is_only_changed_by_seek() {
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    world.init_resource::<Assets<SpriteMaterial>>();
+    world.init_resource::<Events<AnimationFinished>>();
+    world.init_resource::<SynchronizedAnimationTimers>();
+
+    let key = animation_key(LoopType::Infinite, 0);
+    let controlled = world
+        .spawn((
+            Handle::<SpriteMaterial>::default(),
+            AnimationSprite::from_key_and_descriptor(&key, &independent_descriptor()),
+            AnimationController::default().with_paused(true),
+        ))
+        .id();
+    let changed_tick = |world: &World| {
+        world
+            .entity(controlled)
+            .get_change_ticks::<AnimationController>()
+            .unwrap()
+            .last_changed_tick()
+    };
+
+    let spawned_at = changed_tick(&world);
+    world.run_system_once(tick_animation_system);
+    assert_eq!(changed_tick(&world), spawned_at);
+
+    world
+        .get_mut::<AnimationController>(controlled)
+        .unwrap()
+        .seek(2);
+    world.run_system_once(tick_animation_system);
+
+    let Some(AnimationSprite::Independent { state, .. }) =
+        world.get::<AnimationSprite>(controlled)
+    else {
+        panic!("Controlled animation must be independent");
+    };
+    assert_eq!(state.current_phase, 2);
+}
+
+#[test]
+fn test_appearance_change_keeps_controlled_animation_state() {
+    let mut world = World::new();
+    world.init_resource::<LoadedAppearances>();
+    world.init_resource::<SynchronizedAnimationTimers>();
+
+    let key = animation_key(LoopType::Infinite, 0);
+    let descriptor = independent_descriptor();
+    world.resource_mut::<LoadedAppearances>().insert(
+        (ContentId::Object(1), FrameGroup::default()),
+        LoadedAppearance {
+            sprites: vec![],
+            layers: 1,
+            pattern: PatternDimensions::default(),
+            animation: Some((key.clone(), descriptor.clone())),
+            state: AppearanceLoadState::Ready,
+        },
+    );
+
+    let mut seeked = key.default_state();
+    seeked.seek(&key, 2);
+    let animation = AnimationSprite::Independent {
+        key: key.clone(),
+        descriptor,
+        state: seeked,
+    };
+
+    let controlled = world
+        .spawn((
+            ContentId::Object(1),
+            animation.clone(),
+            AnimationController::default().with_paused(true),
+        ))
+        .id();
+    let uncontrolled = world.spawn((ContentId::Object(1), animation)).id();
+
+    world.run_system_once(initialize_animation_sprite_system);
+
+    let phase = |entity: Entity| match world.get::<AnimationSprite>(entity) {
+        Some(AnimationSprite::Independent { state, .. }) => Some(state.current_phase),
+        _ => None,
+    };
+    assert_eq!(phase(controlled), Some(2));
+    assert_eq!(phase(uncontrolled), Some(0));
+}
+
+#[test]
+fn test_synchronized_one_shot_effects_finish_on_their_own() {
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    world.init_resource::<Assets<SpriteMaterial>>();
+    world.init_resource::<Events<AnimationFinished>>();
+    world.init_resource::<SynchronizedAnimationTimers>();
+
+    let key = animation_key(LoopType::Counted, 1);
+    let descriptor = AnimationDescriptor {
+        synchronized: true,
+        ..independent_descriptor()
+    };
+    let spawn_effect = |world: &mut World| {
+        world
+            .spawn((
+                Handle::<SpriteMaterial>::default(),
+                AnimationSprite::from_key_and_descriptor(&key, &descriptor),
+            ))
+            .id()
+    };
+    let play_effects = |world: &mut World| {
+        (0..4)
+            .flat_map(|_| {
+                world.resource_mut::<Time>().advance_by(PHASE_DURATION);
+                world.run_system_once(tick_animation_system);
+                world
+                    .resource_mut::<Events<AnimationFinished>>()
+                    .drain()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let first = spawn_effect(&mut world);
+    assert!(matches!(
+        world.get::<AnimationSprite>(first),
+        Some(AnimationSprite::Independent { .. })
+    ));
+    assert_eq!(play_effects(&mut world), vec![AnimationFinished(first)]);
+
+    let second = spawn_effect(&mut world);
+    assert_eq!(play_effects(&mut world), vec![AnimationFinished(second)]);
+}
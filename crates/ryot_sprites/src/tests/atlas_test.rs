@@ -0,0 +1,211 @@
+use crate::prelude::*;
+use crate::tests::{insert_appearance, sheet_image, sprite_sheet};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_render::prelude::Image;
+use bevy_render::render_resource::TextureFormat;
+use bevy_time::Time;
+use bevy_utils::default;
+use glam::{UVec2, Vec2};
+use ryot_core::prelude::*;
+use std::time::Duration;
+
+#[test]
+fn test_atlas_page_allocation() {
+    let mut page = AtlasPage::new(
+        Handle::default(),
+        TextureFormat::Rgba8UnormSrgb,
+        UVec2::new(4, 4),
+    );
+
+    assert_eq!(page.allocate(UVec2::new(1, 1)), Some(UVec2::new(0, 0)));
+    assert_eq!(page.allocate(UVec2::new(2, 2)), Some(UVec2::new(2, 0)));
+    assert_eq!(page.allocate(UVec2::new(1, 2)), Some(UVec2::new(1, 0)));
+    assert_eq!(page.allocate(UVec2::new(2, 1)), Some(UVec2::new(0, 2)));
+    assert_eq!(page.used_cells(), 9);
+
+    page.free(UVec2::new(2, 0), UVec2::new(2, 2));
+    assert_eq!(page.used_cells(), 5);
+    assert_eq!(page.allocate(UVec2::new(2, 2)), Some(UVec2::new(2, 0)));
+}
+
+#[test]
+fn test_sprite_cell_in_non_square_grid() {
+    assert_eq!(sprite_cell(3, Vec2::new(2., 2.)), UVec2::new(1, 1));
+    assert_eq!(sprite_cell(1, Vec2::new(1., 2.)), UVec2::new(0, 1));
+    assert_eq!(sprite_cell(5, Vec2::new(4., 2.)), UVec2::new(1, 1));
+    assert_eq!(sprite_cell(6, Vec2::new(2., 4.)), UVec2::new(0, 3));
+}
+
+/// A 64x64 sprite sheet with four 32x32 sprites, each of them filled with its own sprite index.
+fn sprite_sheet_image() -> Image {
+    let mut image = sheet_image(64, [0, 0, 0, 255]);
+
+    for (pixel, color) in image.data.chunks_exact_mut(4).enumerate() {
+        let (x, y) = (pixel % 64, pixel / 64);
+        color[0] = ((y / 32) * 2 + x / 32) as u8;
+    }
+
+    image
+}
+
+/// A world with atlases of a single page of the given size, evicting on every check, and the
+/// sprite sheet of [sprite_sheet_image].
+fn setup(page_size: u32) -> (World, Handle<Image>) {
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    world.init_resource::<LoadedAppearances>();
+    world.init_resource::<Assets<SpriteMaterial>>();
+    world.insert_resource(
+        SpriteAtlases::default()
+            .with_page_size(page_size)
+            .with_max_pages(1)
+            .with_eviction_interval(Duration::ZERO),
+    );
+
+    let sheet = world
+        .get_resource_or_insert_with(Assets::<Image>::default)
+        .add(sprite_sheet_image());
+
+    (world, sheet)
+}
+
+/// Loads an object with the given sprite of the sheet, returning the material created for it.
+fn load_sprite(
+    world: &mut World,
+    sheet: &Handle<Image>,
+    content_id: u32,
+    sprite_id: u32,
+) -> Handle<SpriteMaterial> {
+    insert_appearance(
+        world,
+        ContentId::Object(content_id),
+        sprite_id,
+        sprite_sheet(10, 13, 64),
+        sheet.clone(),
+        SpriteMaterial {
+            texture: sheet.clone(),
+            index: sprite_id - 10,
+            counts: Vec2::new(2., 2.),
+            ..default()
+        },
+        AppearanceLoadState::Ready,
+    )
+}
+
+#[test]
+fn test_pack_and_evict_sprite() {
+    let (mut world, sheet) = setup(64);
+    let material = load_sprite(&mut world, &sheet, 100, 11);
+
+    let entity = world.spawn(ContentId::Object(100)).id();
+    world.run_system_once(pack_sprite_atlas_system);
+
+    let atlases = world.resource::<SpriteAtlases>();
+    let slot = atlases.slot(11).cloned().expect("Sprite must be packed");
+    let page = atlases.pages()[slot.page].texture.clone();
+    assert_eq!(slot.cell, UVec2::ZERO);
+    assert_eq!(slot.counts, Vec2::new(2., 2.));
+
+    let packed = world.resource::<Assets<SpriteMaterial>>().get(&material);
+    assert_eq!(packed.map(|material| &material.texture), Some(&page));
+
+    let atlas = world.resource::<Assets<Image>>().get(&page).unwrap();
+    assert!(atlas
+        .data
+        .chunks_exact(4)
+        .take(32)
+        .all(|pixel| pixel[0] == 1));
+
+    world.run_system_once(evict_unused_atlas_sprites_system);
+    assert!(world.resource::<SpriteAtlases>().slot(11).is_some());
+
+    world.despawn(entity);
+    world.run_system_once(evict_unused_atlas_sprites_system);
+    assert!(world.resource::<SpriteAtlases>().is_empty());
+
+    world.run_system_once(pack_sprite_atlas_system);
+    assert!(world.resource::<SpriteAtlases>().is_empty());
+
+    let restored = world
+        .resource::<Assets<SpriteMaterial>>()
+        .get(&material)
+        .unwrap();
+    assert_eq!(restored.texture, sheet);
+    assert_eq!(restored.index, 1);
+    assert_eq!(restored.counts, Vec2::new(2., 2.));
+}
+
+#[test]
+fn test_pack_and_evict_every_material_of_a_sprite() {
+    let (mut world, sheet) = setup(64);
+
+    // Each load batch creates its own material, even for the same sprite.
+    let materials = [
+        load_sprite(&mut world, &sheet, 100, 11),
+        load_sprite(&mut world, &sheet, 101, 11),
+    ];
+
+    let first = world.spawn(ContentId::Object(100)).id();
+    let second = world.spawn(ContentId::Object(101)).id();
+    world.run_system_once(pack_sprite_atlas_system);
+
+    let atlases = world.resource::<SpriteAtlases>();
+    assert_eq!(atlases.len(), 1);
+    assert_eq!(atlases.pages()[0].used_cells(), 1);
+    let page = atlases.pages()[0].texture.clone();
+
+    let textures = |world: &World| {
+        materials
+            .iter()
+            .map(|material| {
+                world
+                    .resource::<Assets<SpriteMaterial>>()
+                    .get(material)
+                    .map(|material| material.texture.clone())
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(textures(&world), vec![Some(page.clone()), Some(page)]);
+
+    world.despawn(first);
+    world.despawn(second);
+    world.run_system_once(evict_unused_atlas_sprites_system);
+
+    assert!(world.resource::<SpriteAtlases>().is_empty());
+    assert_eq!(
+        textures(&world),
+        vec![Some(sheet.clone()), Some(sheet.clone())]
+    );
+}
+
+#[test]
+fn test_full_atlas_waits_for_an_eviction() {
+    let (mut world, sheet) = setup(32);
+    load_sprite(&mut world, &sheet, 100, 10);
+    load_sprite(&mut world, &sheet, 101, 11);
+
+    let first = world.spawn(ContentId::Object(100)).id();
+    world.run_system_once(pack_sprite_atlas_system);
+    assert!(world.resource::<SpriteAtlases>().slot(10).is_some());
+
+    world.spawn(ContentId::Object(101));
+    world.run_system_once(pack_sprite_atlas_system);
+
+    let atlases = world.resource::<SpriteAtlases>();
+    assert_eq!(atlases.len(), 1);
+    assert!(atlases.is_full());
+
+    world.run_system_once(pack_sprite_atlas_system);
+    assert_eq!(world.resource::<SpriteAtlases>().len(), 1);
+
+    world.despawn(first);
+    world.run_system_once(evict_unused_atlas_sprites_system);
+    assert!(!world.resource::<SpriteAtlases>().is_full());
+
+    world.run_system_once(pack_sprite_atlas_system);
+    let atlases = world.resource::<SpriteAtlases>();
+    assert!(atlases.slot(10).is_none());
+    assert!(atlases.slot(11).is_some());
+}
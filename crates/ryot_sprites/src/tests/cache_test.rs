@@ -0,0 +1,85 @@
+use crate::prelude::*;
+use crate::tests::{insert_appearance, sheet_image, sprite_sheet};
+use bevy_asset::Assets;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_render::prelude::Image;
+use bevy_time::Time;
+use ryot_core::prelude::*;
+use std::time::Duration;
+
+/// The size of the 32x32 RGBA sheets used by the appearances of the tests.
+const SHEET_SIZE: usize = 32 * 32 * 4;
+
+fn load_appearance(world: &mut World, content_id: ContentId) {
+    let texture = world
+        .get_resource_or_insert_with(Assets::<Image>::default)
+        .add(sheet_image(32, [0, 0, 0, 0]));
+
+    insert_appearance(
+        world,
+        content_id,
+        1,
+        sprite_sheet(1, 1, 32),
+        texture,
+        SpriteMaterial::default(),
+        AppearanceLoadState::Ready,
+    );
+}
+
+fn is_loaded(world: &World, content_id: ContentId) -> bool {
+    world
+        .resource::<LoadedAppearances>()
+        .contains_key(&(content_id, FrameGroup::default()))
+}
+
+fn check_cache(world: &mut World) {
+    world
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs(1));
+    world.run_system_once(evict_sprite_cache_system);
+}
+
+#[test]
+fn test_evict_least_recently_used_appearances() {
+    let (first, second, third) = (
+        ContentId::Object(1),
+        ContentId::Object(2),
+        ContentId::Object(3),
+    );
+
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    world.init_resource::<LoadedAppearances>();
+    world.insert_resource(SpriteCache::default().with_check_interval(Duration::ZERO));
+
+    for content_id in [first, second, third] {
+        load_appearance(&mut world, content_id);
+    }
+    check_cache(&mut world);
+
+    world.spawn(first);
+    let second_entity = world.spawn(second).id();
+    check_cache(&mut world);
+
+    let cache = world.resource::<SpriteCache>();
+    assert_eq!(cache.memory(), 3 * SHEET_SIZE);
+    assert_eq!(cache.references(&(second, FrameGroup::default())), 1);
+    assert_eq!(cache.references(&(third, FrameGroup::default())), 0);
+
+    world.despawn(second_entity);
+    world.resource_mut::<SpriteCache>().budget = 2 * SHEET_SIZE;
+    check_cache(&mut world);
+
+    assert!(is_loaded(&world, first));
+    assert!(is_loaded(&world, second));
+    assert!(!is_loaded(&world, third));
+    assert_eq!(world.resource::<SpriteCache>().memory(), 2 * SHEET_SIZE);
+
+    world.resource_mut::<SpriteCache>().budget = 0;
+    check_cache(&mut world);
+
+    assert!(is_loaded(&world, first));
+    assert!(!is_loaded(&world, second));
+    assert_eq!(world.resource::<SpriteCache>().memory(), SHEET_SIZE);
+}
@@ -0,0 +1,124 @@
+use crate::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_hierarchy::prelude::*;
+use ryot_core::prelude::ContentId;
+
+#[test]
+fn test_outfit_addons_rows() {
+    assert_eq!(OutfitAddons::NONE.rows().count(), 0);
+    assert_eq!(OutfitAddons::FIRST.rows().collect::<Vec<_>>(), vec![1]);
+    assert_eq!(OutfitAddons::SECOND.rows().collect::<Vec<_>>(), vec![2]);
+    assert_eq!(OutfitAddons::ALL.rows().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(
+        OutfitAddons::FIRST | OutfitAddons::SECOND,
+        OutfitAddons::ALL
+    );
+    assert!(OutfitAddons::ALL.contains(OutfitAddons::SECOND));
+    assert!(!OutfitAddons::FIRST.contains(OutfitAddons::SECOND));
+}
+
+fn get_parts(world: &mut World, outfit: Entity) -> Vec<(OutfitPart, ContentId, SpritePattern)> {
+    let children = world
+        .get::<Children>(outfit)
+        .map(|children| children.to_vec())
+        .unwrap_or_default();
+
+    children
+        .into_iter()
+        .filter_map(|child| {
+            let entity = world.get_entity(child)?;
+            Some((
+                *entity.get::<OutfitPart>()?,
+                *entity.get::<ContentId>()?,
+                *entity.get::<SpritePattern>()?,
+            ))
+        })
+        .collect()
+}
+
+#[test]
+fn test_compose_outfit() {
+    let mut world = World::new();
+    let outfit = world
+        .spawn((
+            ContentId::Outfit(128),
+            OutfitAddons::ALL,
+            Mounted(ContentId::Outfit(368)),
+        ))
+        .id();
+
+    world.run_system_once(compose_outfit_system);
+
+    assert_eq!(
+        world.get::<SpritePattern>(outfit),
+        Some(&SpritePattern::new(0, 0, 1))
+    );
+
+    let parts = get_parts(&mut world, outfit);
+    assert_eq!(parts.len(), 3);
+    assert!(parts.contains(&(
+        OutfitPart::Mount,
+        ContentId::Outfit(368),
+        SpritePattern::default()
+    )));
+    assert!(parts.contains(&(
+        OutfitPart::Addon(1),
+        ContentId::Outfit(128),
+        SpritePattern::new(0, 1, 1)
+    )));
+    assert!(parts.contains(&(
+        OutfitPart::Addon(2),
+        ContentId::Outfit(128),
+        SpritePattern::new(0, 2, 1)
+    )));
+    assert!(OutfitPart::Mount.z_offset() < 0.);
+    assert!(OutfitPart::Addon(1).z_offset() < OutfitPart::Addon(2).z_offset());
+}
+
+#[test]
+fn test_recompose_outfit() {
+    let mut world = World::new();
+    let outfit = world
+        .spawn((
+            ContentId::Outfit(128),
+            OutfitAddons::ALL,
+            Mounted(ContentId::Outfit(368)),
+        ))
+        .id();
+
+    world.run_system_once(compose_outfit_system);
+
+    world.entity_mut(outfit).remove::<Mounted>();
+    world.entity_mut(outfit).insert(OutfitAddons::FIRST);
+    world.run_system_once(compose_outfit_system);
+
+    assert_eq!(
+        world.get::<SpritePattern>(outfit),
+        Some(&SpritePattern::default())
+    );
+    assert_eq!(
+        get_parts(&mut world, outfit),
+        vec![(
+            OutfitPart::Addon(1),
+            ContentId::Outfit(128),
+            SpritePattern::new(0, 1, 0)
+        )]
+    );
+}
+
+#[test]
+fn test_compose_ignores_content_without_addons_or_mount() {
+    let mut world = World::new();
+    let tile = world
+        .spawn((ContentId::Object(100), SpritePattern::new(1, 0, 2)))
+        .id();
+
+    world.run_system_once(compose_outfit_system);
+
+    assert_eq!(
+        world.get::<SpritePattern>(tile),
+        Some(&SpritePattern::new(1, 0, 2))
+    );
+    assert!(world.get::<Children>(tile).is_none());
+}
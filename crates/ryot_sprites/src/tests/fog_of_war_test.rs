@@ -0,0 +1,62 @@
+use crate::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_render::color::Color;
+use ryot_core::prelude::ContentId;
+use ryot_ray_casting::prelude::{FogOfWar, FogState};
+use ryot_tiled::prelude::*;
+
+#[test]
+fn test_fog_of_war_keeps_sprite_tint_and_alpha() {
+    let tint = Color::rgba(1., 0.5, 0.5, 1.);
+    let tile = TilePosition::new(0, 0, 0);
+
+    let mut world = World::new();
+    world.init_resource::<FogOfWarRendering>();
+
+    let mut schedule = Schedule::default();
+    schedule.add_systems(apply_fog_of_war_to_sprites::<()>);
+
+    let spectator = world.spawn(FogOfWar::<(), TilePosition>::default()).id();
+    let sprite = world
+        .spawn((
+            ContentId::Object(1),
+            tile,
+            SpriteParams::default().with_tint(tint).with_alpha(0.8),
+        ))
+        .id();
+
+    schedule.run(&mut world);
+    assert_eq!(world.get::<FogState>(sprite), Some(&FogState::Unexplored));
+    assert_eq!(world.get::<SpriteParams>(sprite).unwrap().alpha, Some(0.));
+
+    world
+        .get_mut::<FogOfWar<(), TilePosition>>(spectator)
+        .unwrap()
+        .reveal(vec![tile]);
+    schedule.run(&mut world);
+    assert_eq!(world.get::<FogState>(sprite), Some(&FogState::Visible));
+    let params = world.get::<SpriteParams>(sprite).unwrap();
+    assert_eq!(params.tint, Some(tint));
+    assert_eq!(params.alpha, Some(0.8));
+
+    world
+        .get_mut::<FogOfWar<(), TilePosition>>(spectator)
+        .unwrap()
+        .reveal(vec![]);
+    schedule.run(&mut world);
+    assert_eq!(world.get::<FogState>(sprite), Some(&FogState::Explored));
+    let params = world.get::<SpriteParams>(sprite).unwrap();
+    assert_eq!(params.tint, Some(Color::rgba(0.4, 0.2, 0.2, 1.)));
+    assert_eq!(params.alpha, Some(0.8));
+
+    world.get_mut::<SpriteParams>(sprite).unwrap().alpha = Some(0.5);
+    world
+        .get_mut::<FogOfWar<(), TilePosition>>(spectator)
+        .unwrap()
+        .reveal(vec![tile]);
+    schedule.run(&mut world);
+    let params = world.get::<SpriteParams>(sprite).unwrap();
+    assert_eq!(params.tint, Some(tint));
+    assert_eq!(params.alpha, Some(0.5));
+    assert!(world.get::<FoggedSprite>(sprite).is_none());
+}
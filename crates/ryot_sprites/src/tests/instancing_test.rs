@@ -0,0 +1,121 @@
+use crate::prelude::*;
+use bevy_asset::{AssetId, Handle};
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_render::prelude::Image;
+use bevy_sprite::Mesh2dHandle;
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use bevy_utils::Uuid;
+use glam::Vec3;
+use ryot_core::prelude::*;
+
+fn instance(z: f32) -> SpriteInstance {
+    SpriteInstance::new(
+        &GlobalTransform::from(Transform::from_translation(Vec3::new(0., 0., z))),
+        &SpriteLayout::OneByOne,
+        &SpriteMaterial::default(),
+    )
+}
+
+fn page(id: u128) -> AssetId<Image> {
+    AssetId::<Image>::Uuid {
+        uuid: Uuid::from_u128(id),
+    }
+}
+
+fn batch_summary(batches: &[InstancedSpriteBatch]) -> Vec<(AssetId<Image>, Vec<f32>)> {
+    batches
+        .iter()
+        .map(|batch| {
+            (
+                batch.texture,
+                batch
+                    .instances
+                    .iter()
+                    .map(|instance| instance.translation[2])
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_batch_sprite_instances() {
+    let (first_page, second_page) = (page(1), page(2));
+
+    let batches = batch_sprite_instances(
+        [
+            (first_page, instance(4.)),
+            (second_page, instance(2.)),
+            (first_page, instance(1.)),
+            (first_page, instance(3.)),
+        ],
+        [],
+    );
+
+    assert_eq!(
+        batch_summary(&batches),
+        vec![
+            (first_page, vec![1.]),
+            (second_page, vec![2.]),
+            (first_page, vec![3., 4.]),
+        ]
+    );
+    assert_eq!(
+        batches.iter().map(|batch| batch.z()).collect::<Vec<_>>(),
+        vec![1., 2., 3.]
+    );
+}
+
+#[test]
+fn test_batch_sprite_instances_split_at_boundaries() {
+    let first_page = page(1);
+    let instances = || {
+        [
+            (first_page, instance(1.)),
+            (first_page, instance(2.)),
+            (first_page, instance(3.)),
+        ]
+    };
+
+    assert_eq!(
+        batch_summary(&batch_sprite_instances(instances(), [2.5, 0.5])),
+        vec![(first_page, vec![1., 2.]), (first_page, vec![3.])]
+    );
+    assert_eq!(
+        batch_summary(&batch_sprite_instances(instances(), [5., 1.])),
+        vec![(first_page, vec![1., 2., 3.])]
+    );
+}
+
+#[test]
+fn test_instanced_sprite_meshes() {
+    let mut world = World::new();
+    let sprite_mesh = Handle::weak_from_u128(42);
+    world.insert_resource(SpriteMeshes(
+        [(SpriteLayout::OneByOne, sprite_mesh.clone())]
+            .into_iter()
+            .collect(),
+    ));
+
+    let entity = world
+        .spawn((
+            InstancedSprite,
+            SpriteLayout::OneByOne,
+            Mesh2dHandle(sprite_mesh.clone()),
+        ))
+        .id();
+
+    world.run_system_once(detach_instanced_sprite_meshes);
+    assert_eq!(
+        world.get::<Mesh2dHandle>(entity).map(|mesh| mesh.0.clone()),
+        Some(Handle::default())
+    );
+
+    world.entity_mut(entity).remove::<InstancedSprite>();
+    world.run_system_once(restore_instanced_sprite_meshes);
+    assert_eq!(
+        world.get::<Mesh2dHandle>(entity).map(|mesh| mesh.0.clone()),
+        Some(sprite_mesh)
+    );
+}
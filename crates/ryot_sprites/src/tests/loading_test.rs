@@ -0,0 +1,218 @@
+use crate::prelude::*;
+use crate::tests::{insert_appearance, sprite_sheet};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_render::color::Color;
+use bevy_render::prelude::Image;
+use bevy_sprite::Mesh2dHandle;
+use bevy_utils::default;
+use ryot_core::prelude::*;
+
+const CONTENT_ID: ContentId = ContentId::Object(100);
+
+fn setup(placeholder: SpritePlaceholder) -> (World, Handle<Image>, Handle<SpriteMaterial>) {
+    let mut world = World::new();
+    world.init_resource::<Assets<Image>>();
+    world.init_resource::<LoadedAppearances>();
+    world.init_resource::<Events<AppearanceReadyEvent>>();
+    world.insert_resource(placeholder);
+    world.init_resource::<PlaceholderMaterials>();
+
+    let texture = Handle::<Image>::weak_from_u128(7);
+    let material = insert_appearance(
+        &mut world,
+        CONTENT_ID,
+        1,
+        sprite_sheet(1, 1, 32),
+        texture.clone(),
+        SpriteMaterial {
+            texture: texture.clone(),
+            ..default()
+        },
+        AppearanceLoadState::Loading,
+    );
+
+    (world, texture, material)
+}
+
+fn spawn_sprite(world: &mut World) -> Entity {
+    world
+        .spawn((
+            CONTENT_ID,
+            SpriteLayout::default(),
+            Mesh2dHandle::default(),
+            Handle::<SpriteMaterial>::default(),
+            SpriteParams::default().with_tint(Color::RED),
+        ))
+        .id()
+}
+
+fn is_ready(world: &World) -> bool {
+    world
+        .resource::<LoadedAppearances>()
+        .get(&(CONTENT_ID, FrameGroup::default()))
+        .is_some_and(|appearance| appearance.is_ready())
+}
+
+#[test]
+fn test_appearance_is_ready_once_textures_are_loaded() {
+    let (mut world, texture, _) = setup(SpritePlaceholder::Blank);
+
+    world.run_system_once(check_appearance_readiness_system);
+    assert!(!is_ready(&world));
+    assert!(world.resource::<Events<AppearanceReadyEvent>>().is_empty());
+
+    world
+        .resource_mut::<Assets<Image>>()
+        .insert(&texture, Image::default());
+    world.run_system_once(check_appearance_readiness_system);
+    assert!(is_ready(&world));
+
+    world.run_system_once(check_appearance_readiness_system);
+    assert_eq!(world.resource::<Events<AppearanceReadyEvent>>().len(), 1);
+}
+
+#[test]
+fn test_placeholder_while_loading() {
+    let (mut world, texture, material) = setup(SpritePlaceholder::Tint(Color::GRAY));
+    let entity = spawn_sprite(&mut world);
+
+    world.run_system_once(update_sprite_system);
+
+    let placeholder = world.get::<Handle<SpriteMaterial>>(entity).unwrap().clone();
+    assert_ne!(placeholder, material);
+    assert!(world.get::<PlaceholderSprite>(entity).is_some());
+    assert_eq!(
+        world
+            .resource::<Assets<SpriteMaterial>>()
+            .get(&placeholder)
+            .map(|material| material.tint),
+        Some(Color::RED)
+    );
+
+    world
+        .resource_mut::<Assets<Image>>()
+        .insert(&texture, Image::default());
+    world.run_system_once(check_appearance_readiness_system);
+    world.run_system_once(update_sprite_system);
+
+    let base = world.resource::<Assets<SpriteMaterial>>().get(&material);
+    let updated = world.get::<Handle<SpriteMaterial>>(entity).unwrap();
+    let updated = world.resource::<Assets<SpriteMaterial>>().get(updated);
+    assert_eq!(
+        updated.map(|material| &material.texture),
+        base.map(|material| &material.texture)
+    );
+    assert!(world.get::<PlaceholderSprite>(entity).is_none());
+}
+
+#[test]
+fn test_placeholder_material_is_shared_by_colour() {
+    let (mut world, _, _) = setup(SpritePlaceholder::Tint(Color::GRAY));
+    let first = spawn_sprite(&mut world);
+    let second = spawn_sprite(&mut world);
+    let other = spawn_sprite(&mut world);
+    world
+        .entity_mut(other)
+        .insert(SpriteParams::default().with_tint(Color::BLUE));
+
+    world.run_system_once(update_sprite_system);
+    let materials = world.resource::<Assets<SpriteMaterial>>().len();
+
+    assert_eq!(
+        world.get::<Handle<SpriteMaterial>>(first),
+        world.get::<Handle<SpriteMaterial>>(second)
+    );
+    assert_ne!(
+        world.get::<Handle<SpriteMaterial>>(first),
+        world.get::<Handle<SpriteMaterial>>(other)
+    );
+    assert_eq!(world.resource::<PlaceholderMaterials>().len(), 2);
+
+    world
+        .entity_mut(first)
+        .insert(SpriteParams::default().with_tint(Color::RED));
+    world.run_system_once(update_sprite_system);
+
+    assert_eq!(world.resource::<Assets<SpriteMaterial>>().len(), materials);
+}
+
+#[test]
+fn test_blank_placeholder_uses_sprite_material() {
+    let (mut world, _, material) = setup(SpritePlaceholder::Blank);
+    let entity = spawn_sprite(&mut world);
+    world.entity_mut(entity).remove::<SpriteParams>();
+
+    world.run_system_once(update_sprite_system);
+
+    assert_eq!(world.get::<Handle<SpriteMaterial>>(entity), Some(&material));
+    assert!(world.get::<PlaceholderSprite>(entity).is_none());
+}
+
+fn setup_invalidation() -> (World, Handle<Image>, Entity) {
+    let (mut world, texture, _) = setup(SpritePlaceholder::Blank);
+    world.init_resource::<Events<VisualElementsChanged>>();
+    world.init_resource::<Events<AssetEvent<Image>>>();
+    world.insert_resource(SpriteSheets::from(vec![sprite_sheet(1, 1, 32)]));
+    let entity = spawn_sprite(&mut world);
+    world.increment_change_tick();
+
+    (world, texture, entity)
+}
+
+fn invalidate(world: &mut World, entity: Entity) -> bool {
+    let changed = |world: &World| {
+        world
+            .entity(entity)
+            .get_change_ticks::<ContentId>()
+            .unwrap()
+            .last_changed_tick()
+    };
+
+    let before = changed(world);
+    world.run_system_once(invalidate_changed_appearances_system);
+    let invalidated = changed(world) != before;
+
+    assert_eq!(invalidated, !is_loaded(world));
+    invalidated
+}
+
+fn is_loaded(world: &World) -> bool {
+    world
+        .resource::<LoadedAppearances>()
+        .contains_key(&(CONTENT_ID, FrameGroup::default()))
+}
+
+#[test]
+fn test_invalidate_changed_visual_elements() {
+    let (mut world, _, entity) = setup_invalidation();
+    assert!(!invalidate(&mut world, entity));
+
+    world.send_event(VisualElementsChanged(vec![ContentId::Object(101)]));
+    assert!(!invalidate(&mut world, entity));
+
+    world.send_event(VisualElementsChanged(vec![CONTENT_ID]));
+    assert!(invalidate(&mut world, entity));
+}
+
+#[test]
+fn test_invalidate_moved_sprites() {
+    let (mut world, _, entity) = setup_invalidation();
+    world.insert_resource(SpriteSheets::from(vec![sprite_sheet(1, 2, 32)]));
+
+    assert!(invalidate(&mut world, entity));
+}
+
+#[test]
+fn test_invalidate_modified_sprite_sheet_textures() {
+    let (mut world, texture, entity) = setup_invalidation();
+
+    world.send_event(AssetEvent::<Image>::Modified {
+        id: Handle::<Image>::weak_from_u128(8).id(),
+    });
+    assert!(!invalidate(&mut world, entity));
+
+    world.send_event(AssetEvent::<Image>::Modified { id: texture.id() });
+    assert!(invalidate(&mut world, entity));
+}
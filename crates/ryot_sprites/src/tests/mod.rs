@@ -1 +1,85 @@
+use crate::prelude::*;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_render::prelude::Image;
+use bevy_render::render_asset::RenderAssetUsages;
+use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_utils::default;
+use ryot_core::prelude::*;
+
+mod animation_test;
+mod atlas_test;
+mod cache_test;
+mod composition_test;
+#[cfg(feature = "ray_casting")]
+mod fog_of_war_test;
+mod instancing_test;
+mod loading_test;
+mod outfit_test;
+mod pattern_test;
 mod sprite_sheets_test;
+
+/// A square RGBA sprite sheet texture of the given size, filled with the given pixel.
+fn sheet_image(size: u32, pixel: [u8; 4]) -> Image {
+    Image::new_fill(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &pixel,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// A sprite sheet of one by one sprites, named after the sprites it contains.
+fn sprite_sheet(first_sprite_id: u32, last_sprite_id: u32, area: u32) -> SpriteSheet {
+    SpriteSheet {
+        file: format!("sprites-{}-{}.bmp.lzma", first_sprite_id, last_sprite_id),
+        layout: SpriteLayout::OneByOne,
+        first_sprite_id,
+        last_sprite_id,
+        area,
+    }
+}
+
+/// Inserts a still appearance with a single object sprite, returning its material.
+fn insert_appearance(
+    world: &mut World,
+    content_id: ContentId,
+    sprite_id: u32,
+    sprite_sheet: SpriteSheet,
+    texture: Handle<Image>,
+    material: SpriteMaterial,
+    state: AppearanceLoadState,
+) -> Handle<SpriteMaterial> {
+    let material = world
+        .get_resource_or_insert_with(Assets::<SpriteMaterial>::default)
+        .add(material);
+
+    let sprite = LoadedSprite {
+        sprite_id,
+        group: ContentType::Object,
+        sprite_sheet,
+        texture,
+        material: material.clone(),
+        mesh: default(),
+    };
+
+    world
+        .get_resource_or_insert_with(LoadedAppearances::default)
+        .insert(
+            (content_id, FrameGroup::default()),
+            LoadedAppearance {
+                sprites: vec![sprite],
+                layers: 1,
+                pattern: PatternDimensions::default(),
+                animation: None,
+                state,
+            },
+        );
+
+    material
+}
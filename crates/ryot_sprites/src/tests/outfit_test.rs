@@ -0,0 +1,48 @@
+use crate::prelude::*;
+use bevy_render::color::Color;
+use rstest::rstest;
+
+#[rstest]
+#[case(0, Color::rgb_u8(255, 255, 255))]
+#[case(1, Color::rgb_u8(255, 212, 191))]
+#[case(19, Color::rgb_u8(218, 218, 218))]
+#[case(82, Color::rgb_u8(0, 255, 0))]
+#[case(88, Color::rgb_u8(0, 0, 255))]
+#[case(94, Color::rgb_u8(255, 0, 0))]
+#[case(114, Color::rgb_u8(36, 36, 36))]
+fn test_outfit_color(#[case] index: u8, #[case] expected: Color) {
+    assert_eq!(outfit_color(index), expected);
+}
+
+#[test]
+fn test_outfit_color_out_of_palette() {
+    assert_eq!(outfit_color(OUTFIT_PALETTE_SIZE), outfit_color(0));
+    assert_eq!(outfit_color(u8::MAX), outfit_color(0));
+}
+
+#[test]
+fn test_outfit_colors_to_material() {
+    let base = SpriteMaterial {
+        index: 3,
+        alpha: 1.,
+        ..Default::default()
+    };
+    let mask = SpriteMaterial {
+        index: 4,
+        counts: glam::Vec2::new(12., 12.),
+        ..Default::default()
+    };
+
+    let material = OutfitColors::new(0, 94, 82, 88).to_material(base, &mask);
+
+    assert_eq!(material.index, 3);
+    assert_eq!(material.alpha, 1.);
+    assert_eq!(material.mask_enabled, 1);
+    assert_eq!(material.mask_index, 4);
+    assert_eq!(material.mask_counts, mask.counts);
+    assert_eq!(material.mask_texture, Some(mask.texture));
+    assert_eq!(material.head_color, outfit_color(0));
+    assert_eq!(material.body_color, outfit_color(94));
+    assert_eq!(material.legs_color, outfit_color(82));
+    assert_eq!(material.feet_color, outfit_color(88));
+}
@@ -0,0 +1,209 @@
+use crate::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use rstest::rstest;
+use ryot_core::prelude::*;
+use ryot_tiled::prelude::{CardinalDirection, Directional, ItemCount, TilePosition};
+
+fn outfit_pattern() -> PatternDimensions {
+    PatternDimensions {
+        width: 4,
+        height: 3,
+        depth: 2,
+    }
+}
+
+#[rstest]
+#[case(None, None, 0)]
+#[case(Some(CardinalDirection::South), None, 4)]
+#[case(Some(CardinalDirection::East), Some(SpritePattern::new(0, 1, 0)), 10)]
+#[case(Some(CardinalDirection::West), Some(SpritePattern::new(0, 2, 1)), 46)]
+#[case(None, Some(SpritePattern::new(0, 3, 2)), 0)]
+fn test_sprite_index(
+    #[case] direction: Option<CardinalDirection>,
+    #[case] pattern: Option<SpritePattern>,
+    #[case] expected: usize,
+) {
+    let direction = direction.map(Directional::Cardinal);
+
+    assert_eq!(
+        outfit_pattern().sprite_index(2, direction.as_ref(), pattern.as_ref()),
+        expected
+    );
+}
+
+#[test]
+fn test_sprite_index_without_pattern() {
+    let direction = Directional::Cardinal(CardinalDirection::West);
+
+    assert_eq!(
+        PatternDimensions::default().sprite_index(1, Some(&direction), None),
+        3
+    );
+}
+
+#[rstest]
+#[case(0, SpritePattern::new(0, 0, 0))]
+#[case(1, SpritePattern::new(0, 0, 0))]
+#[case(4, SpritePattern::new(3, 0, 0))]
+#[case(5, SpritePattern::new(0, 1, 0))]
+#[case(24, SpritePattern::new(1, 1, 0))]
+#[case(25, SpritePattern::new(2, 1, 0))]
+#[case(100, SpritePattern::new(3, 1, 0))]
+fn test_resolve_count(#[case] count: u8, #[case] expected: SpritePattern) {
+    let stack = PatternDimensions {
+        width: 4,
+        height: 2,
+        depth: 1,
+    };
+
+    assert_eq!(stack.resolve(&SpriteVariant::Count(count), None), expected);
+    assert_eq!(
+        PatternDimensions::default().resolve(&SpriteVariant::Count(count), None),
+        SpritePattern::default()
+    );
+}
+
+#[rstest]
+#[case(FluidColor::Transparent, SpritePattern::new(0, 0, 0))]
+#[case(FluidColor::Red, SpritePattern::new(2, 0, 0))]
+#[case(FluidColor::Green, SpritePattern::new(0, 1, 0))]
+#[case(FluidColor::Purple, SpritePattern::new(3, 1, 0))]
+#[case(FluidColor::Black, SpritePattern::new(0, 0, 0))]
+fn test_resolve_fluid(#[case] color: FluidColor, #[case] expected: SpritePattern) {
+    let fluid = PatternDimensions {
+        width: 4,
+        height: 2,
+        depth: 1,
+    };
+
+    assert_eq!(fluid.resolve(&SpriteVariant::Fluid(color), None), expected);
+}
+
+#[rstest]
+#[case(TilePosition::new(0, 0, 0), SpritePattern::new(0, 0, 0))]
+#[case(TilePosition::new(5, 6, 7), SpritePattern::new(1, 0, 0))]
+#[case(TilePosition::new(-1, -2, 0), SpritePattern::new(3, 1, 0))]
+fn test_resolve_position(#[case] position: TilePosition, #[case] expected: SpritePattern) {
+    let ground = PatternDimensions {
+        width: 4,
+        height: 3,
+        depth: 1,
+    };
+
+    assert_eq!(
+        ground.resolve(&SpriteVariant::Position, Some(&position)),
+        expected
+    );
+}
+
+#[rstest]
+#[case(1, FluidColor::Blue)]
+#[case(2, FluidColor::Red)]
+#[case(7, FluidColor::Purple)]
+#[case(43, FluidColor::Brown)]
+#[case(0, FluidColor::Transparent)]
+#[case(200, FluidColor::Transparent)]
+fn test_fluid_color_from_fluid_type(#[case] fluid_type: u8, #[case] expected: FluidColor) {
+    assert_eq!(FluidColor::from_fluid_type(fluid_type), expected);
+}
+
+#[test]
+fn test_sprite_variant_for_object() {
+    let stackable = ItemProperties {
+        is_stackable: true,
+        ..Default::default()
+    };
+    let fluid = ItemProperties {
+        is_liquid_container: true,
+        ..Default::default()
+    };
+    let ground = ItemProperties {
+        is_ground: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        SpriteVariant::for_object(&stackable, Some(&ItemCount(7))),
+        Some(SpriteVariant::Count(7))
+    );
+    assert_eq!(
+        SpriteVariant::for_object(&stackable, None),
+        Some(SpriteVariant::Count(1))
+    );
+    assert_eq!(
+        SpriteVariant::for_object(&fluid, Some(&ItemCount(2))),
+        Some(SpriteVariant::Fluid(FluidColor::Red))
+    );
+    assert_eq!(
+        SpriteVariant::for_object(&ground, None),
+        Some(SpriteVariant::Position)
+    );
+    assert_eq!(
+        SpriteVariant::for_object(&ItemProperties::default(), Some(&ItemCount(3))),
+        None
+    );
+}
+
+fn object_with_pattern(id: u32, item_properties: ItemProperties) -> VisualElement {
+    VisualElement {
+        id,
+        sprites_info: vec![SpriteInfo {
+            pattern_width: 4,
+            pattern_height: 2,
+            pattern_depth: 1,
+            ..Default::default()
+        }],
+        item_properties,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_resolve_sprite_variant_system() {
+    let mut visual_elements = VisualElements::default();
+    for element in [
+        object_with_pattern(
+            1,
+            ItemProperties {
+                is_stackable: true,
+                ..Default::default()
+            },
+        ),
+        object_with_pattern(
+            2,
+            ItemProperties {
+                is_ground: true,
+                ..Default::default()
+            },
+        ),
+        object_with_pattern(3, ItemProperties::default()),
+    ] {
+        visual_elements
+            .entry(ContentType::Object)
+            .or_default()
+            .insert(element.id, element);
+    }
+
+    let mut world = World::new();
+    world.insert_resource(visual_elements);
+
+    let position = TilePosition::new(5, 3, 0);
+    let coins = world
+        .spawn((ContentId::Object(1), ItemCount(30), position))
+        .id();
+    let ground = world.spawn((ContentId::Object(2), position)).id();
+    let chest = world.spawn((ContentId::Object(3), position)).id();
+
+    world.run_system_once(resolve_sprite_variant_system);
+
+    assert_eq!(
+        world.get::<SpritePattern>(coins),
+        Some(&SpritePattern::new(2, 1, 0))
+    );
+    assert_eq!(
+        world.get::<SpritePattern>(ground),
+        Some(&SpritePattern::new(1, 1, 0))
+    );
+    assert_eq!(world.get::<SpritePattern>(chest), None);
+}
@@ -3,6 +3,7 @@ use crate::animation::descriptor::AnimationSprite;
 use crate::prelude::*;
 use bevy_asset::*;
 use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryItem;
 use bevy_sprite::Mesh2dHandle;
 use bevy_utils::tracing::warn;
 use ryot_core::prelude::*;
@@ -12,72 +13,188 @@ pub(crate) type ChangingAppearanceFilter = Or<(
     Changed<ContentId>,
     Changed<FrameGroup>,
     Changed<Directional>,
+    Changed<SpritePattern>,
     (Without<AnimationSprite>, Changed<SpriteParams>),
+    (Without<AnimationSprite>, Changed<OutfitColors>),
 )>;
 
+type SpriteUpdateQueryData<'a> = (
+    Entity,
+    &'a ContentId,
+    Option<&'a FrameGroup>,
+    Option<&'a Directional>,
+    Option<&'a SpritePattern>,
+    Option<&'a SpriteParams>,
+    Option<&'a OutfitColors>,
+    Has<PlaceholderSprite>,
+    &'a mut SpriteLayout,
+    &'a mut Mesh2dHandle,
+    &'a mut Handle<SpriteMaterial>,
+);
+
+/// A system that updates the sprites whose appearance changed, and the ones drawn with the sprite
+/// placeholder once their appearance is ready. While an appearance is loading, its entities are
+/// drawn with the placeholder, if any.
 pub fn update_sprite_system(
-    mut q_updated: Query<
-        (
-            &ContentId,
-            Option<&FrameGroup>,
-            Option<&Directional>,
-            Option<&SpriteParams>,
-            &mut SpriteLayout,
-            &mut Mesh2dHandle,
-            &mut Handle<SpriteMaterial>,
-        ),
-        ChangingAppearanceFilter,
-    >,
+    mut commands: Commands,
+    mut q_sprites: ParamSet<(
+        Query<SpriteUpdateQueryData, ChangingAppearanceFilter>,
+        Query<SpriteUpdateQueryData, With<PlaceholderSprite>>,
+    )>,
+    mut ready_events: EventReader<AppearanceReadyEvent>,
     mut materials: ResMut<Assets<SpriteMaterial>>,
+    placeholder: Res<SpritePlaceholder>,
+    mut placeholder_materials: ResMut<PlaceholderMaterials>,
     loaded_appereances: Res<LoadedAppearances>,
 ) {
-    q_updated.iter_mut().for_each(
-        |(object_id, frame_group, direction, sprite_params, mut layout, mut mesh, mut material)| {
-            if object_id.is_none() {
-                return;
-            }
-            let Some(loaded_appearance) =
-                loaded_appereances.get(&(*object_id, frame_group.cloned().unwrap_or_default()))
-            else {
-                warn!("BUG: Loaded appearance for {:?} not found.", object_id);
-                return;
-            };
+    for sprite in q_sprites.p0().iter_mut() {
+        update_sprite(
+            sprite,
+            false,
+            &mut commands,
+            &mut materials,
+            &placeholder,
+            &mut placeholder_materials,
+            &loaded_appereances,
+        );
+    }
 
-            let direction_index = match direction {
-                Some(dir) => dir.index(),
-                None => 0,
-            } * loaded_appearance.layers as usize;
+    if ready_events.read().count() > 0 {
+        for sprite in q_sprites.p1().iter_mut() {
+            update_sprite(
+                sprite,
+                true,
+                &mut commands,
+                &mut materials,
+                &placeholder,
+                &mut placeholder_materials,
+                &loaded_appereances,
+            );
+        }
+    }
+}
+
+fn update_sprite(
+    (
+        entity,
+        object_id,
+        frame_group,
+        direction,
+        pattern,
+        sprite_params,
+        outfit_colors,
+        is_placeholder,
+        mut layout,
+        mut mesh,
+        mut material,
+    ): QueryItem<SpriteUpdateQueryData>,
+    only_ready: bool,
+    commands: &mut Commands,
+    materials: &mut ResMut<Assets<SpriteMaterial>>,
+    placeholder: &SpritePlaceholder,
+    placeholder_materials: &mut PlaceholderMaterials,
+    loaded_appereances: &LoadedAppearances,
+) {
+    if object_id.is_none() {
+        return;
+    }
+    let Some(loaded_appearance) =
+        loaded_appereances.get(&(*object_id, frame_group.cloned().unwrap_or_default()))
+    else {
+        warn!("BUG: Loaded appearance for {:?} not found.", object_id);
+        return;
+    };
 
-            let Some(sprite) = loaded_appearance.sprites.get(direction_index) else {
-                warn!(
-                    "Sprite for appearance {:?} not found for direction {:?}",
-                    object_id, direction
-                );
-                return;
-            };
-            *layout = sprite.sprite_sheet.layout;
-            *mesh = Mesh2dHandle(sprite.mesh.clone());
-            *material = sprite_material_from_params(sprite_params, &mut materials, sprite);
-        },
+    if only_ready && !loaded_appearance.is_ready() {
+        return;
+    }
+
+    let direction_index = loaded_appearance.pattern.sprite_index(
+        loaded_appearance.layers as usize,
+        direction,
+        pattern,
     );
+
+    let Some(sprite) = loaded_appearance.sprites.get(direction_index) else {
+        warn!(
+            "Sprite for appearance {:?} not found for direction {:?}",
+            object_id, direction
+        );
+        return;
+    };
+    *layout = sprite.sprite_sheet.layout;
+    *mesh = Mesh2dHandle(sprite.mesh.clone());
+
+    let placeholder_material = match loaded_appearance.is_ready() {
+        true => None,
+        false => placeholder.material(sprite_params, placeholder_materials, materials),
+    };
+
+    match (placeholder_material, is_placeholder) {
+        (Some(placeholder_material), _) => {
+            *material = placeholder_material;
+            commands.entity(entity).insert(PlaceholderSprite);
+            return;
+        }
+        (None, true) => {
+            commands.entity(entity).remove::<PlaceholderSprite>();
+        }
+        (None, false) => (),
+    }
+
+    *material = sprite_material_from_params(
+        sprite_params,
+        outfit_colors,
+        materials,
+        sprite,
+        mask_sprite(
+            &loaded_appearance.sprites,
+            direction_index,
+            loaded_appearance.layers as usize,
+        ),
+    );
+}
+
+/// Returns the colour mask of the sprite at the given index, which is the next layer of the
+/// sprite, if the appearance has more than one layer.
+pub(crate) fn mask_sprite(
+    sprites: &[LoadedSprite],
+    index: usize,
+    layers: usize,
+) -> Option<&LoadedSprite> {
+    if layers > 1 {
+        sprites.get(index + 1)
+    } else {
+        None
+    }
 }
 
 pub(crate) fn sprite_material_from_params(
     sprite_params: Option<&SpriteParams>,
+    outfit_colors: Option<&OutfitColors>,
     materials: &mut ResMut<'_, Assets<SpriteMaterial>>,
     sprite: &LoadedSprite,
+    mask: Option<&LoadedSprite>,
 ) -> Handle<SpriteMaterial> {
-    sprite_params
-        .map(|params| {
-            if params.has_any() {
-                materials
-                    .get(sprite.material.id())
-                    .map(|base| params.to_material(base.clone()))
-                    .map(|material| materials.add(material))
-                    .unwrap_or_else(|| sprite.material.clone())
-            } else {
-                sprite.material.clone()
-            }
+    let sprite_params = sprite_params.filter(|params| params.has_any());
+    let outfit =
+        outfit_colors.zip(mask.and_then(|mask| materials.get(mask.material.id()).cloned()));
+
+    if sprite_params.is_none() && outfit.is_none() {
+        return sprite.material.clone();
+    }
+
+    materials
+        .get(sprite.material.id())
+        .cloned()
+        .map(|base| match outfit {
+            Some((colors, mask)) => colors.to_material(base, &mask),
+            None => base,
+        })
+        .map(|base| match sprite_params {
+            Some(params) => params.to_material(base),
+            None => base,
         })
+        .map(|material| materials.add(material))
         .unwrap_or_else(|| sprite.material.clone())
 }
@@ -14,11 +14,20 @@
 //! - Handling of nullable types and ensuring sensible defaults for optional data from `tibia`.
 //! - Mapping complex flags and properties from `tibia` types into simplified, application-specific flags
 //!   and categories.
+//! - Preserving the full set of item flags in `ItemProperties`, for game and editor code that needs
+//!   more than the simplified flags.
 //!
 //! These conversions play a crucial role in separating external data dependencies from the core logic
 //! of the application, thus maintaining a clean architecture and ensuring that changes in external
 //! data structures have minimal impact on internal business logic.
 //!
+//! ## Writing
+//!
+//! The reverse conversions, from Ryot visual elements back into `tibia` types, are also provided,
+//! so edited content can be written back into a client-compatible `appearances.dat` with
+//! `ryot_tibia::to_bytes`. Descriptions, special ids and per-direction bounding boxes are not kept
+//! by the Ryot types, so they are not written back.
+//!
 //! ## Custom Traits and Macros
 //!
 //! The file also includes a macro `option_flag_to_element!` that generates default implementations for
@@ -78,6 +87,9 @@ impl From<tibia::VisualElement> for VisualElement {
         let flags: Flags = from_flags(&visual_element.flags);
         let category: Category = from_flags(&visual_element.flags);
         let properties: Properties = from_flags(&visual_element.flags);
+        // Missing flags mean a plain item, which can still be moved around.
+        let item_properties: ItemProperties =
+            visual_element.flags.clone().unwrap_or_default().into();
         let sprites_info: Vec<SpriteInfo> = visual_element
             .frames
             .iter()
@@ -96,6 +108,7 @@ impl From<tibia::VisualElement> for VisualElement {
             flags,
             category,
             properties,
+            item_properties,
         }
     }
 }
@@ -107,6 +120,8 @@ impl From<tibia::SpriteInfo> for SpriteInfo {
         let pattern_width = sprite_info.pattern_width();
         let pattern_height = sprite_info.pattern_height();
         let pattern_depth = sprite_info.pattern_depth();
+        let bounding_square = sprite_info.bounding_square();
+        let is_opaque = sprite_info.is_opaque();
         let animation = sprite_info.animation.map(|a| a.into());
 
         SpriteInfo {
@@ -116,6 +131,8 @@ impl From<tibia::SpriteInfo> for SpriteInfo {
             pattern_height,
             pattern_depth,
             animation,
+            bounding_square,
+            is_opaque,
         }
     }
 }
@@ -125,6 +142,8 @@ impl From<tibia::Animation> for Animation {
         let start_phase = animation.start_phase();
         let synchronized = animation.synchronized();
         let is_start_random = animation.is_start_random();
+        let loop_type = animation.loop_type().into();
+        let loop_count = animation.loop_count();
         let phases = animation
             .phases
             .iter()
@@ -135,11 +154,23 @@ impl From<tibia::Animation> for Animation {
             start_phase,
             synchronized,
             is_start_random,
+            loop_type,
+            loop_count,
             phases,
         }
     }
 }
 
+impl From<tibia::LoopType> for LoopType {
+    fn from(loop_type: tibia::LoopType) -> Self {
+        match loop_type {
+            tibia::LoopType::PingPong => LoopType::PingPong,
+            tibia::LoopType::Infinite => LoopType::Infinite,
+            tibia::LoopType::Counted => LoopType::Counted,
+        }
+    }
+}
+
 impl From<tibia::Flags> for Flags {
     fn from(flags: tibia::Flags) -> Self {
         Flags::new(!flags.is_not_walkable(), flags.blocks_sight())
@@ -148,8 +179,164 @@ impl From<tibia::Flags> for Flags {
 
 impl From<tibia::Flags> for Properties {
     fn from(flags: tibia::Flags) -> Self {
+        let displacement = flags.displacement.clone().unwrap_or_default();
+
         Properties {
             elevation: flags.elevation.clone().unwrap_or_default().height().into(),
+            displacement: (displacement.x(), displacement.y()).into(),
+            lying_object: flags.lying_object(),
+            has_top_effect: flags.has_top_effect(),
+        }
+    }
+}
+
+impl From<tibia::Flags> for ItemProperties {
+    fn from(flags: tibia::Flags) -> Self {
+        let write = match (&flags.write_info, &flags.write_once_info) {
+            (Some(info), _) => Some(WriteInfo {
+                max_length: info.max_length(),
+                once: false,
+            }),
+            (None, Some(info)) => Some(WriteInfo {
+                max_length: info.max_length(),
+                once: true,
+            }),
+            (None, None) => None,
+        };
+
+        let hook = flags
+            .hook_info
+            .as_ref()
+            .map(|hook| HookInfo {
+                south: hook.south.is_some(),
+                east: hook.east.is_some(),
+            })
+            .unwrap_or_default();
+
+        let default_action = flags
+            .default_action
+            .as_ref()
+            .and_then(|action| action.action().into());
+
+        ItemProperties {
+            ground_speed: flags.ground.as_ref().map(|ground| ground.speed()),
+            is_ground: flags.is_ground(),
+            is_edge: flags.is_edge(),
+            is_bottom: flags.is_bottom(),
+            is_top: flags.is_top(),
+            is_container: flags.is_container(),
+            is_stackable: flags.is_cumulative(),
+            is_usable: flags.is_usable(),
+            can_force_use: flags.can_force_use(),
+            is_multi_use: flags.is_multi_use(),
+            write,
+            is_liquid_pool: flags.is_liquid_pool(),
+            is_liquid_container: flags.is_liquid_container(),
+            is_not_movable: flags.is_not_movable(),
+            is_avoidable: flags.is_avoidable(),
+            has_no_movement_animation: flags.has_no_movement_animation(),
+            is_pickupable: flags.can_be_taken(),
+            is_hangable: flags.can_be_hanged(),
+            hook,
+            is_rotatable: flags.can_rotate(),
+            light: flags.light.as_ref().map(|light| Light {
+                brightness: light.brightness(),
+                color: light.color(),
+            }),
+            cannot_hide: flags.cannot_hide(),
+            is_translucent: flags.is_translucent(),
+            should_always_animate: flags.should_always_animate(),
+            minimap_color: flags.minimap.as_ref().map(|minimap| minimap.color()),
+            lens_help: flags.lens_help.as_ref().map(|lens_help| lens_help.id()),
+            should_ignore_look: flags.should_ignore_look(),
+            slot: flags.slot.as_ref().map(|slot| slot.id()),
+            default_action,
+            market: flags.market_info.clone().map(Into::into),
+            is_wrappable: flags.wrap(),
+            is_unwrappable: flags.unwrap(),
+            npc_sales: flags
+                .npc_sales_info
+                .iter()
+                .cloned()
+                .map(Into::into)
+                .collect(),
+            changed_to_expire: flags
+                .changed_to_expire
+                .as_ref()
+                .map(|expire| expire.former_object_type_id()),
+            is_corpse: flags.is_corpse(),
+            is_player_corpse: flags.is_player_corpse(),
+            cyclopedia_id: flags.cyclopedia.as_ref().map(|cyclopedia| cyclopedia.id()),
+            is_ammo: flags.is_ammo(),
+            should_show_off_socket: flags.should_show_off_socket(),
+            is_reportable: flags.is_reportable(),
+            upgrade_classification: flags
+                .upgrade_classification
+                .as_ref()
+                .map(|classification| classification.value()),
+            reverse_addons: ReverseAddons {
+                east: flags.reverse_addons_east(),
+                west: flags.reverse_addons_west(),
+                south: flags.reverse_addons_south(),
+                north: flags.reverse_addons_north(),
+            },
+            wears_out: flags.wears_out(),
+            clock_expires: flags.clock_expires(),
+            expires: flags.expires(),
+            expire_stops: flags.expirestop(),
+            is_decoration_kit: flags.is_decoration_kit(),
+        }
+    }
+}
+
+impl From<tibia::Action> for Option<ItemAction> {
+    fn from(action: tibia::Action) -> Self {
+        match action {
+            tibia::Action::None => None,
+            tibia::Action::Look => Some(ItemAction::Look),
+            tibia::Action::Use => Some(ItemAction::Use),
+            tibia::Action::Open => Some(ItemAction::Open),
+            tibia::Action::AutoWalk => Some(ItemAction::AutoWalk),
+        }
+    }
+}
+
+impl From<tibia::MarketInfo> for MarketInfo {
+    fn from(market: tibia::MarketInfo) -> Self {
+        MarketInfo {
+            category: market.category,
+            trade_as_object_id: market.trade_as_object_id,
+            show_as_object_id: market.show_as_object_id,
+            restrict_to_vocations: market.restrict_to_profession().map(Into::into).collect(),
+            minimum_level: market.minimum_level,
+            name: market.name,
+        }
+    }
+}
+
+impl From<tibia::Profession> for Vocation {
+    fn from(profession: tibia::Profession) -> Self {
+        match profession {
+            tibia::Profession::Any => Vocation::Any,
+            tibia::Profession::None => Vocation::None,
+            tibia::Profession::Knight => Vocation::Knight,
+            tibia::Profession::Paladin => Vocation::Paladin,
+            tibia::Profession::Sorcerer => Vocation::Sorcerer,
+            tibia::Profession::Druid => Vocation::Druid,
+            tibia::Profession::Promoted => Vocation::Promoted,
+        }
+    }
+}
+
+impl From<tibia::NpcSalesInfo> for NpcSale {
+    fn from(sale: tibia::NpcSalesInfo) -> Self {
+        NpcSale {
+            sale_price: sale.sale_price(),
+            buy_price: sale.buy_price(),
+            currency_id: sale.currency_id(),
+            name: sale.name.unwrap_or_default(),
+            location: sale.location.unwrap_or_default(),
+            currency_quest_flag_display_name: sale.currency_quest_flag_display_name,
         }
     }
 }
@@ -180,3 +367,338 @@ impl From<tibia::Flags> for Category {
         }
     }
 }
+
+impl From<VisualElements> for tibia::VisualElements {
+    fn from(visual_elements: VisualElements) -> Self {
+        fn process_items(
+            visual_elements: &VisualElements,
+            entity_type: ContentType,
+        ) -> Vec<tibia::VisualElement> {
+            let Some(items) = visual_elements.get_all_for_group(entity_type) else {
+                return Vec::new();
+            };
+
+            let mut items = items
+                .values()
+                .map(|item| {
+                    let mut visual_element: tibia::VisualElement = item.clone().into();
+
+                    // Only outfits have idle and moving frames, other contents have a single
+                    // initial frame per frame group.
+                    if entity_type != ContentType::Outfit {
+                        for frame in visual_element.frames.iter_mut() {
+                            frame.set_type(tibia::FrameType::ObjectInitial);
+                        }
+                    }
+
+                    visual_element
+                })
+                .collect::<Vec<_>>();
+
+            items.sort_by_key(|item| item.id());
+            items
+        }
+
+        tibia::VisualElements {
+            objects: process_items(&visual_elements, ContentType::Object),
+            outfits: process_items(&visual_elements, ContentType::Outfit),
+            effects: process_items(&visual_elements, ContentType::Effect),
+            missiles: process_items(&visual_elements, ContentType::Missile),
+            special_ids: None,
+        }
+    }
+}
+
+impl From<VisualElement> for tibia::VisualElement {
+    fn from(visual_element: VisualElement) -> Self {
+        let mut flags: tibia::Flags = visual_element.item_properties.into();
+
+        flags.is_not_walkable = (!visual_element.flags.is_walkable).then_some(true);
+        flags.blocks_sight = visual_element.flags.blocks_sight.then_some(true);
+        apply_properties(&mut flags, visual_element.properties);
+        apply_category(&mut flags, visual_element.category);
+
+        let frames = visual_element
+            .sprites_info
+            .into_iter()
+            .enumerate()
+            .map(|(index, sprite_info)| {
+                let frame_type = match index {
+                    0 => tibia::FrameType::OutfitIdle,
+                    1 => tibia::FrameType::OutfitMoving,
+                    _ => tibia::FrameType::ObjectInitial,
+                };
+
+                tibia::Frame {
+                    r#type: Some(frame_type as i32),
+                    id: Some(index as u32),
+                    sprite_info: Some(sprite_info.into()),
+                }
+            })
+            .collect();
+
+        tibia::VisualElement {
+            id: Some(visual_element.id),
+            frames,
+            flags: Some(flags),
+            name: Some(visual_element.name),
+            description: None,
+        }
+    }
+}
+
+fn apply_properties(flags: &mut tibia::Flags, properties: Properties) {
+    if *properties.elevation > 0. {
+        flags.elevation = Some(tibia::Elevation {
+            height: Some(*properties.elevation as u32),
+        });
+    }
+
+    if *properties.displacement != Default::default() {
+        flags.displacement = Some(tibia::Displacement {
+            x: Some(properties.displacement.x as u32),
+            y: Some(properties.displacement.y as u32),
+        });
+    }
+
+    flags.lying_object = properties.lying_object.then_some(true);
+    flags.has_top_effect = properties.has_top_effect.then_some(true);
+}
+
+/// Sets the flags that the category is derived from, so the category is kept when converting back.
+///
+/// Nothing is written when the item properties already lead to the same category, so they are
+/// kept as they are. Otherwise, e.g. when a content pack overrides the category, the category wins
+/// and the item properties read back also include its flags, like `is_ground` for ground.
+fn apply_category(flags: &mut tibia::Flags, category: Category) {
+    if Category::from(flags.clone()) == category {
+        return;
+    }
+
+    match category {
+        Category::Bottom => flags.is_bottom = Some(true),
+        Category::Containers => flags.is_container = Some(true),
+        Category::Corpses => flags.is_corpse = Some(true),
+        Category::Edges => flags.is_edge = Some(true),
+        Category::Ground => flags.is_ground = Some(true),
+        Category::Top => flags.is_top = Some(true),
+        Category::Decor if flags.hook_info.is_none() => {
+            flags.hook_info = Some(tibia::HookInfo::default());
+        }
+        Category::Wearable if flags.slot.is_none() => {
+            flags.slot = Some(tibia::Slot::default());
+        }
+        Category::Custom(category) => {
+            flags
+                .market_info
+                .get_or_insert_with(Default::default)
+                .category = Some(category);
+        }
+        _ => (),
+    }
+}
+
+impl From<SpriteInfo> for tibia::SpriteInfo {
+    fn from(sprite_info: SpriteInfo) -> Self {
+        tibia::SpriteInfo {
+            pattern_width: Some(sprite_info.pattern_width),
+            pattern_height: Some(sprite_info.pattern_height),
+            pattern_depth: Some(sprite_info.pattern_depth),
+            layers: Some(sprite_info.layers),
+            sprite_ids: sprite_info.ids,
+            bounding_square: Some(sprite_info.bounding_square),
+            animation: sprite_info.animation.map(Into::into),
+            is_opaque: Some(sprite_info.is_opaque),
+            bounding_box_per_direction: Vec::new(),
+        }
+    }
+}
+
+impl From<Animation> for tibia::Animation {
+    fn from(animation: Animation) -> Self {
+        tibia::Animation {
+            start_phase: Some(animation.start_phase),
+            synchronized: Some(animation.synchronized),
+            is_start_random: Some(animation.is_start_random),
+            loop_type: Some(tibia::LoopType::from(animation.loop_type) as i32),
+            loop_count: Some(animation.loop_count),
+            phases: animation
+                .phases
+                .into_iter()
+                .map(|(min, max)| tibia::Duration {
+                    min: Some(min),
+                    max: Some(max),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<LoopType> for tibia::LoopType {
+    fn from(loop_type: LoopType) -> Self {
+        match loop_type {
+            LoopType::PingPong => tibia::LoopType::PingPong,
+            LoopType::Infinite => tibia::LoopType::Infinite,
+            LoopType::Counted => tibia::LoopType::Counted,
+        }
+    }
+}
+
+impl From<ItemProperties> for tibia::Flags {
+    fn from(properties: ItemProperties) -> Self {
+        let (write_info, write_once_info) = match properties.write {
+            Some(write) if write.once => (
+                None,
+                Some(tibia::WriteOnceInfo {
+                    max_length: Some(write.max_length),
+                }),
+            ),
+            Some(write) => (
+                Some(tibia::WriteInfo {
+                    max_length: Some(write.max_length),
+                }),
+                None,
+            ),
+            None => (None, None),
+        };
+
+        let hook = properties.hook;
+        let hook_info = (hook.south || hook.east).then(|| tibia::HookInfo {
+            south: hook.south.then_some(tibia::HookType::South as i32),
+            east: hook.east.then_some(tibia::HookType::East as i32),
+        });
+
+        let reverse_addons = properties.reverse_addons;
+
+        tibia::Flags {
+            ground: properties
+                .ground_speed
+                .map(|speed| tibia::Ground { speed: Some(speed) }),
+            is_edge: properties.is_edge.then_some(true),
+            is_bottom: properties.is_bottom.then_some(true),
+            is_top: properties.is_top.then_some(true),
+            is_container: properties.is_container.then_some(true),
+            is_cumulative: properties.is_stackable.then_some(true),
+            is_usable: properties.is_usable.then_some(true),
+            can_force_use: properties.can_force_use.then_some(true),
+            is_multi_use: properties.is_multi_use.then_some(true),
+            write_info,
+            write_once_info,
+            is_liquid_pool: properties.is_liquid_pool.then_some(true),
+            is_not_movable: properties.is_not_movable.then_some(true),
+            is_avoidable: properties.is_avoidable.then_some(true),
+            has_no_movement_animation: properties.has_no_movement_animation.then_some(true),
+            can_be_taken: properties.is_pickupable.then_some(true),
+            is_liquid_container: properties.is_liquid_container.then_some(true),
+            can_be_hanged: properties.is_hangable.then_some(true),
+            hook_info,
+            can_rotate: properties.is_rotatable.then_some(true),
+            light: properties.light.map(|light| tibia::Light {
+                brightness: Some(light.brightness),
+                color: Some(light.color),
+            }),
+            cannot_hide: properties.cannot_hide.then_some(true),
+            is_translucent: properties.is_translucent.then_some(true),
+            should_always_animate: properties.should_always_animate.then_some(true),
+            minimap: properties
+                .minimap_color
+                .map(|color| tibia::Minimap { color: Some(color) }),
+            lens_help: properties
+                .lens_help
+                .map(|id| tibia::LensHelp { id: Some(id) }),
+            is_ground: properties.is_ground.then_some(true),
+            should_ignore_look: properties.should_ignore_look.then_some(true),
+            slot: properties.slot.map(|id| tibia::Slot { id: Some(id) }),
+            default_action: properties
+                .default_action
+                .map(|action| tibia::DefaultAction {
+                    action: Some(tibia::Action::from(action) as i32),
+                }),
+            market_info: properties.market.map(Into::into),
+            wrap: properties.is_wrappable.then_some(true),
+            unwrap: properties.is_unwrappable.then_some(true),
+            npc_sales_info: properties.npc_sales.into_iter().map(Into::into).collect(),
+            changed_to_expire: properties
+                .changed_to_expire
+                .map(|id| tibia::ChangedToExpire {
+                    former_object_type_id: Some(id),
+                }),
+            is_corpse: properties.is_corpse.then_some(true),
+            is_player_corpse: properties.is_player_corpse.then_some(true),
+            cyclopedia: properties
+                .cyclopedia_id
+                .map(|id| tibia::Cyclopedia { id: Some(id) }),
+            is_ammo: properties.is_ammo.then_some(true),
+            should_show_off_socket: properties.should_show_off_socket.then_some(true),
+            is_reportable: properties.is_reportable.then_some(true),
+            upgrade_classification: properties
+                .upgrade_classification
+                .map(|value| tibia::UpgradeClassification { value: Some(value) }),
+            reverse_addons_east: reverse_addons.east.then_some(true),
+            reverse_addons_west: reverse_addons.west.then_some(true),
+            reverse_addons_south: reverse_addons.south.then_some(true),
+            reverse_addons_north: reverse_addons.north.then_some(true),
+            wears_out: properties.wears_out.then_some(true),
+            clock_expires: properties.clock_expires.then_some(true),
+            expires: properties.expires.then_some(true),
+            expirestop: properties.expire_stops.then_some(true),
+            is_decoration_kit: properties.is_decoration_kit.then_some(true),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ItemAction> for tibia::Action {
+    fn from(action: ItemAction) -> Self {
+        match action {
+            ItemAction::Look => tibia::Action::Look,
+            ItemAction::Use => tibia::Action::Use,
+            ItemAction::Open => tibia::Action::Open,
+            ItemAction::AutoWalk => tibia::Action::AutoWalk,
+        }
+    }
+}
+
+impl From<MarketInfo> for tibia::MarketInfo {
+    fn from(market: MarketInfo) -> Self {
+        tibia::MarketInfo {
+            category: market.category,
+            trade_as_object_id: market.trade_as_object_id,
+            show_as_object_id: market.show_as_object_id,
+            name: market.name,
+            restrict_to_profession: market
+                .restrict_to_vocations
+                .into_iter()
+                .map(|vocation| tibia::Profession::from(vocation) as i32)
+                .collect(),
+            minimum_level: market.minimum_level,
+        }
+    }
+}
+
+impl From<Vocation> for tibia::Profession {
+    fn from(vocation: Vocation) -> Self {
+        match vocation {
+            Vocation::Any => tibia::Profession::Any,
+            Vocation::None => tibia::Profession::None,
+            Vocation::Knight => tibia::Profession::Knight,
+            Vocation::Paladin => tibia::Profession::Paladin,
+            Vocation::Sorcerer => tibia::Profession::Sorcerer,
+            Vocation::Druid => tibia::Profession::Druid,
+            Vocation::Promoted => tibia::Profession::Promoted,
+        }
+    }
+}
+
+impl From<NpcSale> for tibia::NpcSalesInfo {
+    fn from(sale: NpcSale) -> Self {
+        tibia::NpcSalesInfo {
+            name: Some(sale.name),
+            location: Some(sale.location),
+            sale_price: Some(sale.sale_price),
+            buy_price: Some(sale.buy_price),
+            currency_id: Some(sale.currency_id),
+            currency_quest_flag_display_name: sale.currency_quest_flag_display_name,
+        }
+    }
+}
@@ -15,6 +15,23 @@ pub fn from_bytes(bytes: &[u8]) -> Result<ryot::VisualElements, DecodeError> {
     Ok(visual_elements.into())
 }
 
+/// Encodes the visual elements into the protobuf format of `appearances.dat`, the reverse of
+/// [from_bytes].
+pub fn to_bytes(visual_elements: &ryot::VisualElements) -> Vec<u8> {
+    VisualElements::from(visual_elements.clone()).encode_to_vec()
+}
+
+/// Decodes two versions of `appearances.dat` and reports what changed from the old to the new one.
+pub fn diff(old: &[u8], new: &[u8]) -> Result<ryot::ContentDiff, DecodeError> {
+    Ok(ryot::ContentDiff::between(
+        &from_bytes(old)?,
+        &from_bytes(new)?,
+    ))
+}
+
 pub mod prelude {
     pub use crate::{asset_loader::TibiaAssetsPlugin, conversions, *};
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,339 @@
+use crate as tibia;
+use ryot_core::prelude::*;
+
+fn visual_element(flags: tibia::Flags) -> tibia::VisualElement {
+    tibia::VisualElement {
+        id: Some(1),
+        flags: Some(flags),
+        frames: vec![tibia::Frame {
+            sprite_info: Some(tibia::SpriteInfo {
+                sprite_ids: vec![1],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_item_properties_from_flags() {
+    let flags = tibia::Flags {
+        is_cumulative: Some(true),
+        can_be_taken: Some(true),
+        is_not_movable: Some(false),
+        write_once_info: Some(tibia::WriteOnceInfo {
+            max_length: Some(120),
+        }),
+        hook_info: Some(tibia::HookInfo {
+            south: Some(tibia::HookType::South as i32),
+            east: None,
+        }),
+        light: Some(tibia::Light {
+            brightness: Some(3),
+            color: Some(215),
+        }),
+        minimap: Some(tibia::Minimap { color: Some(86) }),
+        default_action: Some(tibia::DefaultAction {
+            action: Some(tibia::Action::Use as i32),
+        }),
+        market_info: Some(tibia::MarketInfo {
+            category: Some(6),
+            name: Some("ham".to_string()),
+            restrict_to_profession: vec![
+                tibia::Profession::Knight as i32,
+                tibia::Profession::Druid as i32,
+            ],
+            ..Default::default()
+        }),
+        npc_sales_info: vec![tibia::NpcSalesInfo {
+            name: Some("Willard".to_string()),
+            sale_price: Some(8),
+            ..Default::default()
+        }],
+        reverse_addons_west: Some(true),
+        ..Default::default()
+    };
+
+    let element: VisualElement = visual_element(flags).into();
+    let properties = element.item_properties;
+
+    assert!(properties.is_stackable);
+    assert!(properties.is_pickupable);
+    assert!(properties.is_movable());
+    assert!(!properties.is_container);
+    assert_eq!(
+        properties.write,
+        Some(WriteInfo {
+            max_length: 120,
+            once: true
+        })
+    );
+    assert_eq!(
+        properties.hook,
+        HookInfo {
+            south: true,
+            east: false
+        }
+    );
+    assert_eq!(
+        properties.light,
+        Some(Light {
+            brightness: 3,
+            color: 215
+        })
+    );
+    assert_eq!(properties.minimap_color, Some(86));
+    assert_eq!(properties.default_action, Some(ItemAction::Use));
+
+    let market = properties.market.unwrap();
+    assert_eq!(market.category, Some(6));
+    assert_eq!(market.name.as_deref(), Some("ham"));
+    assert_eq!(
+        market.restrict_to_vocations,
+        vec![Vocation::Knight, Vocation::Druid]
+    );
+
+    assert_eq!(properties.npc_sales.len(), 1);
+    assert_eq!(properties.npc_sales[0].name, "Willard");
+    assert_eq!(properties.npc_sales[0].sale_price, 8);
+    assert!(properties.reverse_addons.west);
+    assert!(!properties.reverse_addons.east);
+}
+
+#[test]
+fn test_item_properties_without_flags_are_movable() {
+    let element: VisualElement = tibia::VisualElement {
+        flags: None,
+        ..visual_element(Default::default())
+    }
+    .into();
+
+    let properties = element.item_properties;
+    assert!(properties.is_movable());
+    assert!(!properties.is_pickupable);
+    assert_eq!(properties.market, None);
+    assert_eq!(properties.default_action, None);
+    assert_eq!(properties, ItemProperties::default());
+}
+
+fn sprite_info(ids: Vec<u32>) -> SpriteInfo {
+    SpriteInfo {
+        ids,
+        layers: 1,
+        pattern_width: 1,
+        pattern_height: 1,
+        pattern_depth: 1,
+        bounding_square: 32,
+        ..Default::default()
+    }
+}
+
+fn visual_elements() -> VisualElements {
+    let torch = VisualElement {
+        id: 100,
+        name: "torch".to_string(),
+        main_sprite_id: Some(10),
+        sprites_info: vec![SpriteInfo {
+            animation: Some(Animation {
+                start_phase: 1,
+                synchronized: true,
+                loop_type: LoopType::PingPong,
+                phases: vec![(100, 100), (200, 300)],
+                ..Default::default()
+            }),
+            ..sprite_info(vec![10, 11])
+        }],
+        flags: Flags::new(true, false),
+        category: Category::Decor,
+        properties: Properties {
+            elevation: 8.into(),
+            displacement: (8, 8).into(),
+            ..Default::default()
+        },
+        item_properties: ItemProperties {
+            is_pickupable: true,
+            is_hangable: true,
+            hook: HookInfo {
+                south: true,
+                east: false,
+            },
+            light: Some(Light {
+                brightness: 3,
+                color: 206,
+            }),
+            default_action: Some(ItemAction::Use),
+            npc_sales: vec![NpcSale {
+                name: "Willard".to_string(),
+                location: "Edron".to_string(),
+                buy_price: 2,
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    };
+
+    let wall = VisualElement {
+        id: 101,
+        name: "wall".to_string(),
+        main_sprite_id: Some(12),
+        sprites_info: vec![sprite_info(vec![12])],
+        flags: Flags::new(false, true),
+        category: Category::Custom(5),
+        item_properties: ItemProperties {
+            market: Some(MarketInfo {
+                category: Some(5),
+                restrict_to_vocations: vec![Vocation::Paladin],
+                ..Default::default()
+            }),
+            write: Some(WriteInfo {
+                max_length: 50,
+                once: true,
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let outfit = VisualElement {
+        id: 128,
+        name: "citizen".to_string(),
+        main_sprite_id: Some(20),
+        sprites_info: vec![sprite_info(vec![20]), sprite_info(vec![21, 22])],
+        ..Default::default()
+    };
+
+    let mut visual_elements = VisualElements::default();
+    for (group, element) in [
+        (ContentType::Object, torch),
+        (ContentType::Object, wall),
+        (ContentType::Outfit, outfit),
+    ] {
+        visual_elements
+            .entry(group)
+            .or_default()
+            .insert(element.id, element);
+    }
+
+    visual_elements
+}
+
+#[test]
+fn test_visual_elements_bytes_round_trip() {
+    let visual_elements = visual_elements();
+
+    let bytes = tibia::to_bytes(&visual_elements);
+    assert_eq!(tibia::from_bytes(&bytes).unwrap(), visual_elements);
+}
+
+#[test]
+fn test_tibia_visual_element_round_trip() {
+    let tibia_element = visual_element(tibia::Flags {
+        is_cumulative: Some(true),
+        can_be_taken: Some(true),
+        is_container: Some(true),
+        light: Some(tibia::Light {
+            brightness: Some(2),
+            color: Some(215),
+        }),
+        elevation: Some(tibia::Elevation { height: Some(16) }),
+        reverse_addons_north: Some(true),
+        ..Default::default()
+    });
+
+    let element: VisualElement = tibia_element.into();
+    let converted: VisualElement = tibia::VisualElement::from(element.clone()).into();
+
+    assert_eq!(converted, element);
+    assert_eq!(converted.category, Category::Containers);
+}
+
+#[test]
+fn test_category_override_wins_over_item_properties() {
+    let element = VisualElement {
+        id: 1,
+        name: "grass".to_string(),
+        sprites_info: vec![sprite_info(vec![1])],
+        category: Category::Ground,
+        ..Default::default()
+    };
+
+    let converted: VisualElement = tibia::VisualElement::from(element.clone()).into();
+
+    assert_eq!(converted.category, Category::Ground);
+    assert_eq!(
+        converted.item_properties,
+        ItemProperties {
+            is_ground: true,
+            ..element.item_properties
+        }
+    );
+}
+
+#[test]
+fn test_frame_types_by_content_type() {
+    let tibia_elements: tibia::VisualElements = visual_elements().into();
+
+    let frame_types = |element: &tibia::VisualElement| {
+        element
+            .frames
+            .iter()
+            .map(|frame| frame.r#type())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        tibia_elements
+            .objects
+            .iter()
+            .map(|o| o.id())
+            .collect::<Vec<_>>(),
+        vec![100, 101]
+    );
+    assert_eq!(
+        frame_types(&tibia_elements.objects[0]),
+        vec![tibia::FrameType::ObjectInitial]
+    );
+    assert_eq!(
+        frame_types(&tibia_elements.outfits[0]),
+        vec![tibia::FrameType::OutfitIdle, tibia::FrameType::OutfitMoving]
+    );
+}
+
+#[test]
+fn test_diff_appearances() {
+    let old = visual_elements();
+    let mut new = old.clone();
+
+    let objects = new.get_mut(&ContentType::Object).unwrap();
+    objects.remove(&101);
+    objects.insert(
+        102,
+        VisualElement {
+            id: 102,
+            sprites_info: vec![sprite_info(vec![30])],
+            ..Default::default()
+        },
+    );
+
+    let torch = objects.get_mut(&100).unwrap();
+    torch.flags = Flags::new(false, false);
+    torch.sprites_info[0].ids = vec![10, 12];
+
+    let diff = tibia::diff(&tibia::to_bytes(&old), &tibia::to_bytes(&new)).unwrap();
+
+    assert_eq!(diff.objects.added, vec![102]);
+    assert_eq!(diff.objects.removed, vec![101]);
+    assert_eq!(diff.objects.changed.len(), 1);
+    assert_eq!(
+        diff.objects.changed[0].flags,
+        Some(Change {
+            old: Flags::new(true, false),
+            new: Flags::new(false, false),
+        })
+    );
+    assert!(diff.outfits.is_empty());
+    assert!(tibia::diff(&tibia::to_bytes(&old), &tibia::to_bytes(&old))
+        .unwrap()
+        .is_empty());
+}
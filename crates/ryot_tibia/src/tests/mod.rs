@@ -0,0 +1 @@
+mod conversions_test;
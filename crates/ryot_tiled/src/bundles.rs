@@ -1,5 +1,6 @@
 use crate::prelude::{Layer, TilePosition};
 use bevy_ecs::bundle::Bundle;
+use bevy_ecs::component::Component;
 use bevy_ecs::event::Event;
 use bevy_utils::default;
 use derive_more::*;
@@ -11,6 +12,8 @@ pub struct TiledContentBundle {
     pub position: TilePosition,
     pub elevation: Elevation,
     pub layer: Layer,
+    #[bundle(ignore)]
+    pub count: Option<ItemCount>,
 }
 
 impl TiledContentBundle {
@@ -20,9 +23,22 @@ impl TiledContentBundle {
             position,
             layer,
             elevation: default(),
+            count: None,
+        }
+    }
+
+    pub fn with_count(self, count: Option<u8>) -> Self {
+        Self {
+            count: count.map(ItemCount),
+            ..self
         }
     }
 }
 
+/// The count of a stackable item, or the fluid type of a fluid container or splash, as stored
+/// in the map. It's used to select the sprite variation of the item.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Deref)]
+pub struct ItemCount(pub u8);
+
 #[derive(Event, Clone, Debug, Deref, DerefMut)]
 pub struct LoadObjects(pub Vec<TiledContentBundle>);
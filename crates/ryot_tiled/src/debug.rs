@@ -0,0 +1,256 @@
+//! Debug overlays that show the results of ray casting and pathfinding requests on the map.
+//!
+//! Tiles are highlighted with coloured sprites drawn on a [Layer::Hud] layer, so they are always
+//! on top of the map content, while rays and paths are drawn as gizmo lines between tile centers.
+use crate::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_render::color::Color;
+use std::marker::PhantomData;
+
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+use bevy_gizmos::prelude::Gizmos;
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+use bevy_sprite::{Sprite, SpriteBundle};
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+use bevy_transform::prelude::Transform;
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+use bevy_utils::default;
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+use glam::Vec3;
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+use ryot_core::prelude::{Displacement, Elevation, SpriteLayout};
+
+#[cfg(feature = "pathfinding")]
+use crate::pathfinding::TiledPath;
+#[cfg(feature = "ray_casting")]
+use crate::ray_casting::{TiledRayCasting, TiledRayPropagation};
+#[cfg(feature = "ray_casting")]
+use bevy_utils::HashSet;
+#[cfg(feature = "ray_casting")]
+use ryot_core::prelude::Point;
+#[cfg(feature = "ray_casting")]
+use ryot_utils::prelude::ThreadSafe;
+
+/// The layer in which the debug overlays are drawn, above the grid and the map content.
+pub static DEBUG_OVERLAY_LAYER: Layer = Layer::Hud(Order::MAX);
+
+/// Marks a tile highlight spawned by the debug overlay systems, pointing to the entity whose
+/// debugged component C, like a ray propagation or a path, it represents.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebugOverlay<C>(pub Entity, PhantomData<C>);
+
+impl<C> DebugOverlay<C> {
+    pub fn new(owner: Entity) -> Self {
+        Self(owner, PhantomData)
+    }
+}
+
+/// The colours used to draw the debug overlays.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DebugOverlayColors {
+    pub area_of_interest: Color,
+    pub collision: Color,
+    pub pierced_collision: Color,
+    pub path: Color,
+    pub ray: Color,
+}
+
+impl Default for DebugOverlayColors {
+    fn default() -> Self {
+        Self {
+            area_of_interest: Color::GREEN.with_a(0.3),
+            collision: Color::RED.with_a(0.5),
+            pierced_collision: Color::ORANGE.with_a(0.5),
+            path: Color::BLUE.with_a(0.4),
+            ray: Color::YELLOW,
+        }
+    }
+}
+
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+fn tile_center(tile_pos: &TilePosition) -> Vec3 {
+    elevate_position(
+        tile_pos,
+        SpriteLayout::OneByOne,
+        DEBUG_OVERLAY_LAYER,
+        Elevation::default(),
+        Displacement::default(),
+    )
+}
+
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+fn spawn_tile_overlay<C: Component>(
+    commands: &mut Commands,
+    owner: Entity,
+    tile_pos: TilePosition,
+    color: Color,
+) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(tile_size().as_vec2()),
+                ..default()
+            },
+            transform: Transform::from_translation(tile_center(&tile_pos)),
+            ..default()
+        },
+        tile_pos,
+        DEBUG_OVERLAY_LAYER,
+        DebugOverlay::<C>::new(owner),
+    ));
+}
+
+#[cfg(any(feature = "ray_casting", feature = "pathfinding"))]
+fn despawn_overlays_of<C: Component>(
+    commands: &mut Commands,
+    q_overlays: &Query<(Entity, &DebugOverlay<C>)>,
+    owner: Entity,
+) {
+    q_overlays
+        .iter()
+        .filter(|(_, overlay)| overlay.0 == owner)
+        .for_each(|(entity, _)| commands.entity(entity).despawn());
+}
+
+/// Highlights the area of interest and the collisions of every changed `RayPropagation` of the
+/// ray casting context Marker. Pierced and unpierced collisions are drawn with different colours.
+#[cfg(feature = "ray_casting")]
+pub fn draw_ray_propagation_overlay<Marker: Copy + ThreadSafe>(
+    mut commands: Commands,
+    colors: Res<DebugOverlayColors>,
+    q_propagation: Query<
+        (Entity, &TiledRayPropagation<Marker>),
+        Changed<TiledRayPropagation<Marker>>,
+    >,
+    q_overlays: Query<(Entity, &DebugOverlay<TiledRayPropagation<Marker>>)>,
+) {
+    for (owner, propagation) in &q_propagation {
+        despawn_overlays_of(&mut commands, &q_overlays, owner);
+
+        for tile_pos in &propagation.area_of_interest {
+            spawn_tile_overlay::<TiledRayPropagation<Marker>>(
+                &mut commands,
+                owner,
+                *tile_pos,
+                colors.area_of_interest,
+            );
+        }
+
+        for collision in &propagation.collisions {
+            let color = if collision.pierced {
+                colors.pierced_collision
+            } else {
+                colors.collision
+            };
+
+            spawn_tile_overlay::<TiledRayPropagation<Marker>>(
+                &mut commands,
+                owner,
+                collision.position,
+                color,
+            );
+        }
+    }
+}
+
+/// Draws the rays of every `RayPropagation` of the ray casting context Marker as gizmo lines,
+/// going from the spectator to each collision point. While the request is alive, its rays are
+/// also drawn to the edge of its radial area, stopping at the first unpierced collision.
+#[cfg(feature = "ray_casting")]
+pub fn draw_ray_casting_gizmos<Marker: Copy + ThreadSafe>(
+    mut gizmos: Gizmos,
+    colors: Res<DebugOverlayColors>,
+    q_propagation: Query<(
+        &TilePosition,
+        &TiledRayPropagation<Marker>,
+        Option<&TiledRayCasting<Marker>>,
+    )>,
+) {
+    for (origin, propagation, ray_casting) in &q_propagation {
+        let start = tile_center(origin).truncate();
+
+        if let Some(ray_casting) = ray_casting {
+            let blocked_at: HashSet<TilePosition> = propagation
+                .collisions
+                .iter()
+                .filter(|collision| !collision.pierced)
+                .map(|collision| collision.position)
+                .collect();
+
+            let area = &ray_casting.area;
+            let (start_angle, end_angle) = area.angle_range;
+
+            for edge in origin.tiles_on_arc_circumference(
+                area.range,
+                start_angle,
+                end_angle,
+                area.angle_step,
+            ) {
+                let end = origin
+                    .draw_line_to(edge)
+                    .into_iter()
+                    .find(|pos| blocked_at.contains(pos))
+                    .unwrap_or(edge);
+
+                gizmos.line_2d(start, tile_center(&end).truncate(), colors.ray);
+            }
+        }
+
+        for collision in &propagation.collisions {
+            let color = if collision.pierced {
+                colors.pierced_collision
+            } else {
+                colors.ray
+            };
+
+            gizmos.line_2d(start, tile_center(&collision.position).truncate(), color);
+        }
+    }
+}
+
+/// Highlights the tiles of every changed `Path` of the pathfinding requests.
+#[cfg(feature = "pathfinding")]
+pub fn draw_path_overlay(
+    mut commands: Commands,
+    colors: Res<DebugOverlayColors>,
+    q_path: Query<(Entity, &TiledPath), Changed<TiledPath>>,
+    q_overlays: Query<(Entity, &DebugOverlay<TiledPath>)>,
+) {
+    for (owner, path) in &q_path {
+        despawn_overlays_of(&mut commands, &q_overlays, owner);
+
+        for tile_pos in path.iter() {
+            spawn_tile_overlay::<TiledPath>(&mut commands, owner, *tile_pos, colors.path);
+        }
+    }
+}
+
+/// Draws the remaining steps of every `Path` as gizmo lines, starting from the walker position.
+#[cfg(feature = "pathfinding")]
+pub fn draw_path_gizmos(
+    mut gizmos: Gizmos,
+    colors: Res<DebugOverlayColors>,
+    q_path: Query<(&TilePosition, &TiledPath)>,
+) {
+    for (origin, path) in &q_path {
+        gizmos.linestrip_2d(
+            std::iter::once(origin)
+                .chain(path.iter())
+                .map(|tile_pos| tile_center(tile_pos).truncate()),
+            colors.ray,
+        );
+    }
+}
+
+/// Removes the overlays whose owner no longer exists or no longer holds the debugged component.
+pub fn remove_stale_debug_overlays<C: Component>(
+    mut commands: Commands,
+    q_owners: Query<(), With<C>>,
+    q_overlays: Query<(Entity, &DebugOverlay<C>)>,
+) {
+    q_overlays
+        .iter()
+        .filter(|(_, overlay)| !q_owners.contains(overlay.0))
+        .for_each(|(entity, _)| commands.entity(entity).despawn());
+}
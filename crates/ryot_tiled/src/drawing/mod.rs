@@ -9,6 +9,7 @@ use bevy_utils::*;
 use glam::Vec3;
 use ryot_core::prelude::ContentId;
 use ryot_core::prelude::FrameGroup;
+use ryot_core::prelude::VisualElements;
 
 pub mod brushes;
 
@@ -285,11 +286,37 @@ impl DetailLevel {
     }
 }
 
+/// The size, in pixels, of the square that bounds the sprites of the given content.
+fn bounding_square(
+    visual_elements: &VisualElements,
+    object_id: &ContentId,
+    frame_group: Option<&FrameGroup>,
+) -> Option<u32> {
+    let (group, id) = object_id.as_group_and_id()?;
+
+    visual_elements
+        .get_for_group_and_id(group, id)?
+        .sprites_info
+        .get(frame_group.copied().unwrap_or_default() as usize)
+        .map(|sprite_info| sprite_info.bounding_square)
+}
+
+/// A system that hides the entities that are not drawn in the current detail level, or whose
+/// bounding square lies outside the sector seen by the camera. Sprites bigger than a tile are
+/// drawn up and to the left of their tile, so they are kept visible as long as any part of their
+/// bounding square is in the sector.
 #[allow(clippy::type_complexity)]
 pub fn apply_detail_level_to_visibility(
+    visual_elements: Res<VisualElements>,
     mut q_visible_entities: Query<(&mut VisibleEntities, &Sector), With<Camera>>,
     mut q_all_entities: Query<
-        (&mut ViewVisibility, Option<&Layer>),
+        (
+            &mut ViewVisibility,
+            Option<&Layer>,
+            Option<&TilePosition>,
+            Option<&ContentId>,
+            Option<&FrameGroup>,
+        ),
         (Without<Deletion>, With<TileComponent>),
     >,
 ) {
@@ -300,7 +327,9 @@ pub fn apply_detail_level_to_visibility(
             .entities
             .iter()
             .filter_map(|entity| {
-                let Ok((mut view_visibility, layer)) = q_all_entities.get_mut(*entity) else {
+                let Ok((mut view_visibility, layer, tile_pos, object_id, frame_group)) =
+                    q_all_entities.get_mut(*entity)
+                else {
                     // If no tile is found we cannot infer anything about the detail level, so we
                     // just keep the entity visible.
                     return Some(*entity);
@@ -313,6 +342,16 @@ pub fn apply_detail_level_to_visibility(
                     }
                 }
 
+                if let (Some(tile_pos), Some(object_id)) = (tile_pos, object_id) {
+                    let size = bounding_square(&visual_elements, object_id, frame_group)
+                        .unwrap_or_default();
+
+                    if !sector.intersects(&Sector::from_bounding_square(*tile_pos, size)) {
+                        *view_visibility = ViewVisibility::HIDDEN;
+                        return None;
+                    }
+                }
+
                 view_visibility.get().then_some(*entity)
             })
             .collect::<Vec<_>>();
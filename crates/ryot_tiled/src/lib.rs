@@ -11,6 +11,8 @@ use std::sync::OnceLock;
 pub mod bundles;
 #[cfg(feature = "bevy")]
 pub mod camera;
+#[cfg(all(feature = "bevy", feature = "debug"))]
+pub mod debug;
 #[cfg(feature = "bevy")]
 pub mod drawing;
 #[cfg(feature = "bevy")]
@@ -39,7 +41,7 @@ pub mod prelude {
 
     #[cfg(feature = "bevy")]
     pub use crate::{
-        bundles::{LoadObjects, TiledContentBundle},
+        bundles::{ItemCount, LoadObjects, TiledContentBundle},
         camera::{
             cursor::{
                 cursor_sliding_camera, draw_cursor_system, move_to_cursor, update_cursor_pos,
@@ -59,7 +61,9 @@ pub mod prelude {
             },
         },
         flags::update_tile_flag_cache,
-        map::elevation::{apply_elevation, elevate_position, initialize_elevation},
+        map::elevation::{
+            apply_displacement, apply_elevation, elevate_position, initialize_elevation,
+        },
         map::grid::{spawn_grid, GridView},
         map::position::{
             systems::{
@@ -74,6 +78,17 @@ pub mod prelude {
         debug_sprite_position, debug_y_offset, PositionDebugText,
     };
 
+    #[cfg(all(feature = "bevy", feature = "debug"))]
+    pub use crate::debug::{
+        remove_stale_debug_overlays, DebugOverlay, DebugOverlayColors, DEBUG_OVERLAY_LAYER,
+    };
+
+    #[cfg(all(feature = "debug", feature = "ray_casting"))]
+    pub use crate::debug::{draw_ray_casting_gizmos, draw_ray_propagation_overlay};
+
+    #[cfg(all(feature = "debug", feature = "pathfinding"))]
+    pub use crate::debug::{draw_path_gizmos, draw_path_overlay};
+
     #[cfg(feature = "egui")]
     pub use crate::include_svg;
 
@@ -88,8 +103,10 @@ pub mod prelude {
 
     #[cfg(feature = "ray_casting")]
     pub use crate::ray_casting::{
-        tiled_ray_casting, tiled_visible_ray_casting, tiled_walkable_ray_casting, TiledRadialArea,
-        TiledRayCasting, TiledRayCastingApp, TiledRayPropagation,
+        spawn_projectile_missiles, tiled_ray_casting, tiled_visible_ray_casting,
+        tiled_walkable_ray_casting, ProjectileMissile, TiledAreaShape, TiledFogOfWar,
+        TiledProjectile, TiledProjectileHit, TiledRadialArea, TiledRayCasting, TiledRayCastingApp,
+        TiledRayPropagation,
     };
 
     #[cfg(feature = "pathfinding")]
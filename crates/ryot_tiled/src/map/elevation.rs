@@ -4,7 +4,9 @@ use bevy_ecs::prelude::*;
 use bevy_render::prelude::Visibility;
 use glam::{UVec2, Vec2, Vec3};
 use itertools::Itertools;
-use ryot_core::prelude::{ContentId, ContentType, Elevation, SpriteLayout, VisualElements};
+use ryot_core::prelude::{
+    ContentId, ContentType, Displacement, Elevation, SpriteLayout, VisualElements,
+};
 
 use crate::prelude::{Layer, MapTiles, TilePosition};
 use crate::tile_size;
@@ -20,16 +22,41 @@ pub fn initialize_elevation(
     });
 }
 
+/// A system that keeps the displacement of the entities in sync with the properties of their
+/// content, so their sprites are drawn with the right offset.
+pub fn apply_displacement(
+    mut commands: Commands,
+    visual_elements: Res<VisualElements>,
+    q_entities: Query<(Entity, &ContentId, Option<&Displacement>), Changed<ContentId>>,
+) {
+    for (entity, object_id, displacement) in &q_entities {
+        let new_displacement = object_id
+            .as_group_and_id()
+            .and_then(|(group, id)| visual_elements.get_for_group_and_id(group, id))
+            .map(|element| element.properties.displacement)
+            .unwrap_or_default();
+
+        if displacement.copied().unwrap_or_default() != new_displacement {
+            commands.entity(entity).insert(new_displacement);
+        }
+    }
+}
+
 pub fn elevate_position(
     position: &TilePosition,
     layout: SpriteLayout,
     layer: Layer,
     elevation: Elevation,
+    displacement: Displacement,
 ) -> Vec3 {
     let elevation = elevation.clamp(0.0, 1.0);
     let anchor = Vec2::new(elevation, -elevation);
+    let base_size = SpriteLayout::OneByOne.get_size(&tile_size()).as_vec2();
+    let displacement = *displacement / SPRITE_BASE_SIZE.as_vec2() * Vec2::new(1., -1.);
+
     position.to_vec3(&layer)
-        - (SpriteLayout::OneByOne.get_size(&tile_size()).as_vec2() * anchor).extend(0.)
+        - (base_size * anchor).extend(0.)
+        - (base_size * displacement).extend(0.)
         - (layout.get_size(&tile_size()).as_vec2() * Vec2::new(0.5, -0.5)).extend(0.)
 }
 
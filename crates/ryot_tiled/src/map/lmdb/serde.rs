@@ -116,6 +116,18 @@ pub struct Item {
     pub attributes: Vec<ItemAttribute>,
 }
 
+impl Item {
+    /// Returns the count of a stackable item, or the subtype of a fluid, if it has one.
+    pub fn count(&self) -> Option<u8> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                ItemAttribute::Count(count) => Some(*count),
+                _ => None,
+            })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ItemAttribute {
     Count(u8),
@@ -144,11 +144,14 @@ pub fn load_area(
                         }
                     }
 
-                    bundles.push(TiledContentBundle::new(
-                        ContentId::Object(item.id as u32),
-                        tile.position,
-                        layer,
-                    ));
+                    bundles.push(
+                        TiledContentBundle::new(
+                            ContentId::Object(item.id as u32),
+                            tile.position,
+                            layer,
+                        )
+                        .with_count(item.count()),
+                    );
                 }
             }
 
@@ -5,7 +5,7 @@ use bevy_transform::prelude::*;
 
 #[cfg(feature = "debug")]
 use bevy_stroked_text::StrokedText;
-use ryot_core::prelude::Elevation;
+use ryot_core::prelude::{Displacement, Elevation};
 
 use crate::prelude::*;
 use ryot_core::prelude::SpriteLayout;
@@ -34,7 +34,12 @@ pub fn debug_y_offset(layer: &Layer) -> f32 {
 
 type PositionChangedFilter = (
     With<Transform>,
-    Or<(Added<Transform>, Changed<TilePosition>, Changed<Elevation>)>,
+    Or<(
+        Added<Transform>,
+        Changed<TilePosition>,
+        Changed<Elevation>,
+        Changed<Displacement>,
+    )>,
 );
 
 /// This system syncs the sprite position with the TilePosition.
@@ -51,17 +56,24 @@ pub fn update_sprite_position(
             &SpriteLayout,
             &TilePosition,
             &Elevation,
+            Option<&Displacement>,
             &Layer,
             &mut Transform,
         ),
         (PositionChangedFilter, Without<SpriteMovement>),
     >,
 ) {
-    query
-        .par_iter_mut()
-        .for_each(|(layout, tile_pos, elevation, layer, mut transform)| {
-            transform.translation = elevate_position(tile_pos, *layout, *layer, *elevation);
-        });
+    query.par_iter_mut().for_each(
+        |(layout, tile_pos, elevation, displacement, layer, mut transform)| {
+            transform.translation = elevate_position(
+                tile_pos,
+                *layout,
+                *layer,
+                *elevation,
+                displacement.copied().unwrap_or_default(),
+            );
+        },
+    );
 }
 
 #[cfg(feature = "debug")]
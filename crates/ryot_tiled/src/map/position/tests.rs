@@ -96,3 +96,33 @@ fn test_is_directly_connected(
 ) {
     assert_eq!(from.is_directly_connected(to, positions), expected);
 }
+
+#[cfg(feature = "bevy")]
+#[rstest]
+#[case((0, 0), (0., 0.))]
+#[case((8, 0), (-8., 0.))]
+#[case((8, 8), (-8., 8.))]
+#[case((16, 24), (-16., 24.))]
+fn test_elevate_position_with_displacement(
+    #[case] displacement: (u32, u32),
+    #[case] expected_offset: (f32, f32),
+) {
+    use ryot_core::prelude::{Displacement, Elevation, SpriteLayout};
+
+    let position = TilePosition::new(3, 4, 0);
+    let elevate = |displacement: Displacement| {
+        elevate_position(
+            &position,
+            SpriteLayout::OneByOne,
+            Layer::Ground,
+            Elevation::default(),
+            displacement,
+        )
+    };
+
+    let offset = elevate(displacement.into()) - elevate(Displacement::default());
+    assert_eq!(
+        offset.truncate(),
+        Vec2::new(expected_offset.0, expected_offset.1)
+    );
+}
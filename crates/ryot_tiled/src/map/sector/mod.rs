@@ -1,4 +1,4 @@
-use crate::prelude::TilePosition;
+use crate::prelude::{tile_size, TilePosition};
 use glam::{IVec2, IVec3, Vec2};
 use std::fmt;
 use std::fmt::Formatter;
@@ -14,6 +14,9 @@ use serde::{Deserialize, Serialize};
 
 mod operations;
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Hash, Eq, PartialEq, Default, Clone, Copy, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "bevy", derive(Component))]
 pub struct Sector {
@@ -75,6 +78,25 @@ impl Sector {
         (self.size().x * self.size().y).unsigned_abs()
     }
 
+    /// The sector covered by a bounding square of the given size, in pixels, anchored at the
+    /// bottom right tile of the square, where sprites bigger than a tile are drawn from.
+    pub fn from_bounding_square(anchor: TilePosition, size: u32) -> Self {
+        let tiles = size.div_ceil(tile_size().x).max(1) as i32 - 1;
+
+        Self {
+            min: TilePosition::new(anchor.x - tiles, anchor.y, anchor.z),
+            max: TilePosition::new(anchor.x, anchor.y + tiles, anchor.z),
+        }
+    }
+
+    /// Whether the two sectors overlap on the x and y axes, regardless of their floor.
+    pub fn intersects(&self, other: &Sector) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+    }
+
     pub fn contains(&self, pos: TilePosition) -> bool {
         pos.x >= self.min.x
             && pos.x <= self.max.x
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+use rstest::rstest;
+
+#[rstest]
+#[case(0, (0, 0), (0, 0))]
+#[case(32, (0, 0), (0, 0))]
+#[case(33, (-1, 0), (0, 1))]
+#[case(64, (-1, 0), (0, 1))]
+#[case(96, (-2, 0), (0, 2))]
+fn test_sector_from_bounding_square(
+    #[case] size: u32,
+    #[case] min: (i32, i32),
+    #[case] max: (i32, i32),
+) {
+    let sector = Sector::from_bounding_square(TilePosition::new(0, 0, 0), size);
+    assert_eq!(sector.min, TilePosition::new(min.0, min.1, 0));
+    assert_eq!(sector.max, TilePosition::new(max.0, max.1, 0));
+}
+
+#[rstest]
+#[case((-1, 0, 0, 1), true)]
+#[case((10, 10, 11, 11), true)]
+#[case((11, 0, 12, 1), false)]
+#[case((-2, -2, -1, -1), false)]
+#[case((-2, -2, 0, 0), true)]
+fn test_sector_intersects(#[case] other: (i32, i32, i32, i32), #[case] expected: bool) {
+    let sector = Sector::new(TilePosition::new(0, 0, 0), TilePosition::new(10, 10, 0));
+    let other = Sector::new(
+        TilePosition::new(other.0, other.1, 0),
+        TilePosition::new(other.2, other.3, 0),
+    );
+    assert_eq!(sector.intersects(&other), expected);
+    assert_eq!(other.intersects(&sector), expected);
+}
@@ -1,40 +1,131 @@
-use crate::prelude::TilePosition;
-use bevy_app::App;
+use crate::prelude::{CardinalDirection, Layer, MovementBundle, OrdinalDirection, TilePosition};
+use bevy_app::{App, Update};
+use bevy_ecs::prelude::*;
 use ryot_core::game::Navigable;
 use ryot_core::prelude::Flags;
 use ryot_ray_casting::prelude::*;
 use ryot_utils::prelude::ThreadSafe;
+use std::time::Duration;
 
 pub trait TiledRayCastingApp {
     fn add_tiled_ray_casting<Marker: Copy + ThreadSafe>(&mut self) -> &mut Self;
+
+    fn add_tiled_projectiles<Marker: Copy + ThreadSafe>(&mut self) -> &mut Self;
 }
 
 impl TiledRayCastingApp for App {
     fn add_tiled_ray_casting<Marker: Copy + ThreadSafe>(&mut self) -> &mut Self {
         self.add_ray_casting::<Marker, TilePosition, Flags>()
     }
+
+    fn add_tiled_projectiles<Marker: Copy + ThreadSafe>(&mut self) -> &mut Self {
+        self.add_projectiles::<Marker, TilePosition, Flags>()
+            .add_systems(
+                Update,
+                spawn_projectile_missiles::<Marker>.after(RayCastingSystems::Process),
+            )
+    }
 }
 
 pub type TiledRayCasting<Marker> = RayCasting<Marker, TilePosition>;
 pub type TiledRayPropagation<Marker> = RayPropagation<Marker, TilePosition>;
 pub type TiledRadialArea = RadialArea<TilePosition>;
 pub type TiledPerspective = Perspective<TilePosition>;
+pub type TiledAreaShape = AreaShape<TilePosition>;
+pub type TiledFogOfWar<Marker> = FogOfWar<Marker, TilePosition>;
+pub type TiledProjectile<Marker> = Projectile<Marker, TilePosition>;
+pub type TiledProjectileHit<Marker> = ProjectileHit<Marker, TilePosition>;
+
+impl From<CardinalDirection> for ShapeDirection {
+    fn from(direction: CardinalDirection) -> Self {
+        match direction {
+            CardinalDirection::North => ShapeDirection::North,
+            CardinalDirection::East => ShapeDirection::East,
+            CardinalDirection::South => ShapeDirection::South,
+            CardinalDirection::West => ShapeDirection::West,
+        }
+    }
+}
+
+impl From<OrdinalDirection> for ShapeDirection {
+    fn from(direction: OrdinalDirection) -> Self {
+        CardinalDirection::from(direction).into()
+    }
+}
 
 pub fn tiled_ray_casting<Marker>(
     area: RadialArea<TilePosition>,
-    condition: fn(&TiledRayCasting<Marker>, &dyn Navigable, &TilePosition) -> bool,
+    condition: impl Fn(&TiledRayCasting<Marker>, &dyn Navigable, &TilePosition) -> bool
+        + Send
+        + Sync
+        + 'static,
 ) -> TiledRayCasting<Marker> {
     RayCasting::<Marker, TilePosition>::new(area, condition)
 }
 
-pub fn tiled_visible_ray_casting<Marker>(
+pub fn tiled_visible_ray_casting<Marker: 'static>(
     area: RadialArea<TilePosition>,
 ) -> TiledRayCasting<Marker> {
     visible_ray_casting::<Marker, TilePosition>(area)
 }
 
-pub fn tiled_walkable_ray_casting<Marker>(
+pub fn tiled_walkable_ray_casting<Marker: 'static>(
     area: RadialArea<TilePosition>,
 ) -> TiledRayCasting<Marker> {
     walkable_ray_casting::<Marker, TilePosition>(area)
 }
+
+/// Describes the missile drawn for the projectiles fired by an entity. When attached to the
+/// shooter, every projectile hit spawns a `MovementBundle` going from the shooter to the actual
+/// impact position, instead of the intended target.
+///
+/// The duration is the time the missile takes to reach the intended target, so the missile keeps
+/// the same speed when it's stopped midway by an obstacle.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ProjectileMissile {
+    pub layer: Layer,
+    pub id: u32,
+    pub duration: Duration,
+}
+
+impl ProjectileMissile {
+    pub fn new(layer: impl Into<Layer>, id: u32, duration: Duration) -> Self {
+        Self {
+            layer: layer.into(),
+            id,
+            duration,
+        }
+    }
+}
+
+/// Spawns the missile of every projectile hit whose shooter has a `ProjectileMissile`, ending at
+/// the impact position of the projectile.
+pub fn spawn_projectile_missiles<Marker: Copy + ThreadSafe>(
+    mut commands: Commands,
+    mut hits: EventReader<TiledProjectileHit<Marker>>,
+    q_missiles: Query<&ProjectileMissile>,
+) {
+    for hit in hits.read() {
+        let Ok(missile) = q_missiles.get(hit.shooter) else {
+            continue;
+        };
+
+        let intended_distance = hit.origin.distance(&hit.target);
+
+        let duration = if intended_distance > 0. {
+            missile
+                .duration
+                .mul_f32(hit.origin.distance(&hit.impact) / intended_distance)
+        } else {
+            missile.duration
+        };
+
+        commands.spawn(MovementBundle::missile(
+            missile.layer,
+            hit.origin.to_vec3(&missile.layer),
+            hit.impact.to_vec3(&missile.layer),
+            missile.id,
+            duration,
+        ));
+    }
+}